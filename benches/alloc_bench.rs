@@ -0,0 +1,93 @@
+//! Peak memory allocated while serializing a 100-message bundle,
+//! relative to the size of the packet produced, and the number of
+//! allocations made while serializing a message with 10 arguments.
+//! Run with `cargo bench --bench alloc_bench`.
+#[macro_use]
+extern crate serde_derive;
+extern crate serde_osc;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use serde_osc::{ser, BundleBuilder, Framing, MessageBuilder, OscTimeTag};
+
+/// Wraps the system allocator, tracking the most memory allocated at once
+/// and the number of allocations.
+struct PeakAlloc {
+    current: AtomicUsize,
+    peak: AtomicUsize,
+    count: AtomicUsize,
+}
+
+unsafe impl GlobalAlloc for PeakAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.count.fetch_add(1, Ordering::SeqCst);
+        let current = self.current.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+        self.peak.fetch_max(current, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.current.fetch_sub(layout.size(), Ordering::SeqCst);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOC: PeakAlloc = PeakAlloc { current: AtomicUsize::new(0), peak: AtomicUsize::new(0), count: AtomicUsize::new(0) };
+
+/// Most bytes allocated at once while running `f`, beyond what was allocated beforehand.
+fn peak_during<T, F: FnOnce() -> T>(f: F) -> (usize, T) {
+    let before = ALLOC.current.load(Ordering::SeqCst);
+    ALLOC.peak.store(before, Ordering::SeqCst);
+    let result = f();
+    (ALLOC.peak.load(Ordering::SeqCst) - before, result)
+}
+
+/// Number of allocations (including reallocations) made while running `f`.
+fn allocations_during<T, F: FnOnce() -> T>(f: F) -> (usize, T) {
+    let before = ALLOC.count.load(Ordering::SeqCst);
+    let result = f();
+    (ALLOC.count.load(Ordering::SeqCst) - before, result)
+}
+
+#[derive(Debug, Serialize)]
+struct SingleArgMsg {
+    address: String,
+    args: (i32,),
+}
+
+#[derive(Debug, Serialize)]
+struct Bundle {
+    timestamp: (u32, u32),
+    messages: Vec<SingleArgMsg>,
+}
+
+fn main() {
+    let bundle = Bundle {
+        timestamp: (0x01020304, 0x05060708),
+        messages: (0..100).map(|i| SingleArgMsg{ address: format!("/foo/{}", i), args: (i,) }).collect(),
+    };
+    for &(name, framing) in &[("framed", Framing::Framed), ("unframed", Framing::Unframed)] {
+        let (peak, packet) = peak_during(|| ser::to_vec(&bundle, framing).unwrap());
+        println!("to_vec/{}: packet {} bytes, peak allocation {} bytes ({:.2}x)",
+            name, packet.len(), peak, peak as f64 / packet.len() as f64);
+        let (peak, _) = peak_during(|| ser::to_write(&mut io::sink(), &bundle, framing).unwrap());
+        println!("to_write/{}: peak allocation {} bytes ({:.2}x)",
+            name, peak, peak as f64 / packet.len() as f64);
+    }
+
+    let msg = ("/ten".to_owned(), (1i32, 2i32, 3i32, 4i32, 5i32, 6.0f32, 7.0f32, 8.0f32, 9.0f32, "ten".to_owned()));
+    let (count, _) = allocations_during(|| ser::to_vec(&msg, Framing::Framed).unwrap());
+    println!("to_vec/10 args: {} allocations", count);
+    let mut builder = MessageBuilder::with_capacity("/ten", 10).unwrap();
+    builder.i32(1).i32(2).i32(3).i32(4).i32(5).f32(6.0).f32(7.0).f32(8.0).f32(9.0).str("ten");
+    let (count, _) = allocations_during(|| builder.build_vec().unwrap());
+    println!("MessageBuilder::build_vec/10 args: {} allocations", count);
+
+    let mut builder = BundleBuilder::with_capacity(OscTimeTag::IMMEDIATE, 100);
+    for _ in 0..100 {
+        builder.push(MessageBuilder::new("/ten").unwrap().i32(1).f32(2.0).str("three").build());
+    }
+    let (count, _) = allocations_during(|| builder.build_vec().unwrap());
+    println!("BundleBuilder::build_vec/100 messages: {} allocations", count);
+}
@@ -0,0 +1,132 @@
+#[macro_use]
+extern crate criterion;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde_bytes;
+extern crate serde_osc;
+
+use criterion::{black_box, Criterion};
+use serde_bytes::ByteBuf;
+use serde_osc::{de, ser, Framing};
+use serde_osc::bench::OscWriter;
+
+#[derive(Debug, Deserialize, Serialize)]
+struct SimpleMsg {
+    address: String,
+    args: (i32,),
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct BlobMsg {
+    address: String,
+    args: (ByteBuf,),
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Bundle {
+    timestamp: (u32, u32),
+    messages: [SimpleMsg; 10],
+}
+
+fn simple_msg() -> SimpleMsg {
+    SimpleMsg {
+        address: "/foo".to_owned(),
+        args: (1,),
+    }
+}
+
+fn blob_msg() -> BlobMsg {
+    BlobMsg {
+        address: "/foo".to_owned(),
+        args: (ByteBuf::from(vec![0xa5; 1024*1024]),),
+    }
+}
+
+fn bundle_10() -> Bundle {
+    let msg = |i| SimpleMsg{ address: format!("/foo/{}", i), args: (i,) };
+    Bundle {
+        timestamp: (0x01020304, 0x05060708),
+        messages: [msg(0), msg(1), msg(2), msg(3), msg(4),
+            msg(5), msg(6), msg(7), msg(8), msg(9)],
+    }
+}
+
+/// Hand-encode `simple_msg()`, bypassing serde entirely.
+fn manual_simple() -> Vec<u8> {
+    let mut output = Vec::new();
+    // "/foo\0\0\0\0" + ",i\0\0" + 4 bytes of argument data
+    output.osc_write_i32(16).unwrap();
+    output.osc_write_str("/foo").unwrap();
+    output.osc_write_str(",i").unwrap();
+    output.osc_write_i32(1).unwrap();
+    output
+}
+
+/// Hand-encode `blob_msg()`, bypassing serde entirely.
+fn manual_blob(blob: &[u8]) -> Vec<u8> {
+    let mut output = Vec::new();
+    // "/foo\0\0\0\0" + ",b\0\0" + blob length + blob data
+    output.osc_write_i32(16 + blob.len() as i32).unwrap();
+    output.osc_write_str("/foo").unwrap();
+    output.osc_write_str(",b").unwrap();
+    output.osc_write_blob(blob).unwrap();
+    output
+}
+
+fn serialize_simple(c: &mut Criterion) {
+    let msg = simple_msg();
+    assert_eq!(ser::to_vec(&msg, Framing::Framed).unwrap(), manual_simple());
+
+    let mut group = c.benchmark_group("serialize_simple");
+    group.bench_function("serde", |b| b.iter(|| ser::to_vec(black_box(&msg), Framing::Framed).unwrap()));
+    group.bench_function("manual", |b| b.iter(manual_simple));
+    group.finish();
+}
+
+fn serialize_blob(c: &mut Criterion) {
+    let msg = blob_msg();
+    assert_eq!(ser::to_vec(&msg, Framing::Framed).unwrap(), manual_blob(&msg.args.0));
+
+    let mut group = c.benchmark_group("serialize_blob");
+    group.bench_function("serde", |b| b.iter(|| ser::to_vec(black_box(&msg), Framing::Framed).unwrap()));
+    group.bench_function("manual", |b| b.iter(|| manual_blob(black_box(&msg.args.0))));
+    group.finish();
+}
+
+fn deserialize_simple(c: &mut Criterion) {
+    let data = ser::to_vec(&simple_msg(), Framing::Framed).unwrap();
+    c.bench_function("deserialize_simple", |b| b.iter(|| {
+        de::from_slice::<SimpleMsg>(black_box(&data), Framing::Framed).unwrap()
+    }));
+}
+
+fn deserialize_blob(c: &mut Criterion) {
+    let data = ser::to_vec(&blob_msg(), Framing::Framed).unwrap();
+    c.bench_function("deserialize_blob", |b| b.iter(|| {
+        de::from_slice::<BlobMsg>(black_box(&data), Framing::Framed).unwrap()
+    }));
+}
+
+fn serialize_bundle_10(c: &mut Criterion) {
+    let bundle = bundle_10();
+    c.bench_function("serialize_bundle_10", |b| b.iter(|| {
+        ser::to_vec(black_box(&bundle), Framing::Framed).unwrap()
+    }));
+}
+
+fn deserialize_bundle_10(c: &mut Criterion) {
+    let data = ser::to_vec(&bundle_10(), Framing::Framed).unwrap();
+    c.bench_function("deserialize_bundle_10", |b| b.iter(|| {
+        de::from_slice::<Bundle>(black_box(&data), Framing::Framed).unwrap()
+    }));
+}
+
+criterion_group!(benches,
+    serialize_simple,
+    serialize_blob,
+    deserialize_simple,
+    deserialize_blob,
+    serialize_bundle_10,
+    deserialize_bundle_10
+);
+criterion_main!(benches);
@@ -0,0 +1,115 @@
+//! Throughput of whole messages and bundles, in each framing,
+//! as a baseline for spotting regressions.
+#[macro_use]
+extern crate criterion;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde_bytes;
+extern crate serde_osc;
+
+use criterion::{black_box, BenchmarkId, Criterion};
+use serde_bytes::ByteBuf;
+use serde_osc::{de, ser, Framing};
+
+const FRAMINGS: [(&str, Framing); 2] = [("framed", Framing::Framed), ("unframed", Framing::Unframed)];
+
+#[derive(Debug, Deserialize, Serialize)]
+struct FourArgMsg {
+    address: String,
+    args: (i32, f32, String, ByteBuf),
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct SingleArgMsg {
+    address: String,
+    args: (i32,),
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Bundle {
+    timestamp: (u32, u32),
+    messages: Vec<SingleArgMsg>,
+}
+
+fn four_arg_msg() -> FourArgMsg {
+    FourArgMsg {
+        address: "/synth/voice/1".to_owned(),
+        args: (440, 0.5, "sine".to_owned(), ByteBuf::from(vec![0xa5; 64])),
+    }
+}
+
+fn bundle_100() -> Bundle {
+    Bundle {
+        timestamp: (0x01020304, 0x05060708),
+        messages: (0..100).map(|i| SingleArgMsg{ address: format!("/foo/{}", i), args: (i,) }).collect(),
+    }
+}
+
+fn serialize_message(c: &mut Criterion) {
+    let msg = four_arg_msg();
+    let mut group = c.benchmark_group("serialize_message");
+    for &(name, framing) in &FRAMINGS {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &framing, |b, &framing| {
+            b.iter(|| ser::to_vec(black_box(&msg), framing).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn deserialize_message(c: &mut Criterion) {
+    let mut group = c.benchmark_group("deserialize_message");
+    for &(name, framing) in &FRAMINGS {
+        let data = ser::to_vec(&four_arg_msg(), framing).unwrap();
+        group.bench_with_input(BenchmarkId::from_parameter(name), &framing, |b, &framing| {
+            b.iter(|| de::from_slice::<FourArgMsg>(black_box(&data), framing).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn serialize_bundle(c: &mut Criterion) {
+    let bundle = bundle_100();
+    let mut group = c.benchmark_group("serialize_bundle_100");
+    for &(name, framing) in &FRAMINGS {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &framing, |b, &framing| {
+            b.iter(|| ser::to_vec(black_box(&bundle), framing).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn deserialize_bundle(c: &mut Criterion) {
+    let mut group = c.benchmark_group("deserialize_bundle_100");
+    for &(name, framing) in &FRAMINGS {
+        let data = ser::to_vec(&bundle_100(), framing).unwrap();
+        group.bench_with_input(BenchmarkId::from_parameter(name), &framing, |b, &framing| {
+            b.iter(|| de::from_slice::<Bundle>(black_box(&data), framing).unwrap())
+        });
+    }
+    group.finish();
+}
+
+/// Cost of measuring a packet's size without keeping the output,
+/// compared with serializing it.
+fn measure_vs_to_vec(c: &mut Criterion) {
+    let bundle = bundle_100();
+    let mut group = c.benchmark_group("measure_vs_to_vec");
+    for &(name, framing) in &FRAMINGS {
+        group.bench_with_input(BenchmarkId::new("measure_packet_size", name), &framing, |b, &framing| {
+            b.iter(|| ser::measure_packet_size(black_box(&bundle), framing).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("to_vec", name), &framing, |b, &framing| {
+            b.iter(|| ser::to_vec(black_box(&bundle), framing).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches,
+    serialize_message,
+    deserialize_message,
+    serialize_bundle,
+    deserialize_bundle,
+    measure_vs_to_vec
+);
+criterion_main!(benches);
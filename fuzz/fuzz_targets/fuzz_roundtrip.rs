@@ -0,0 +1,40 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use serde_osc::{de, ser, Framing, OscArg, OscBundle, OscMessage, OscPacket};
+
+/// Known-good packets, covering every argument type.
+fn corpus() -> Vec<OscPacket> {
+    let msg = OscMessage::new("/all/types", vec![
+        OscArg::I32(-1), OscArg::F32(0.5), OscArg::String("str".to_owned()), OscArg::Blob(vec![1, 2, 3]),
+        OscArg::I64(1 << 40), OscArg::F64(-0.25), OscArg::Char('c'), OscArg::Bool(true), OscArg::Nil,
+        OscArg::Impulse, OscArg::Symbol("sym".to_owned()), OscArg::U32(7),
+        OscArg::Array(vec![OscArg::I32(1), OscArg::Array(vec![])]),
+    ]).unwrap();
+    let empty = OscMessage::new("/", vec![]).unwrap();
+    let mut inner = OscBundle::empty((1, 2));
+    inner.push(msg.clone());
+    let mut outer = OscBundle::empty((0, 1));
+    outer.push(inner);
+    outer.push(empty.clone());
+    vec![msg.into(), empty.into(), outer.into()]
+}
+
+fuzz_target!(|data: &[u8]| {
+    // Pick a packet, and flip some bits of one of its bytes.
+    if data.len() < 4 {
+        return;
+    }
+    let corpus = corpus();
+    let packet = &corpus[data[0] as usize % corpus.len()];
+    let mut bytes = ser::to_vec(packet, Framing::Framed).unwrap();
+    let index = u16::from_be_bytes([data[1], data[2]]) as usize % bytes.len();
+    bytes[index] ^= data[3];
+
+    match de::from_slice::<OscPacket>(&bytes, Framing::Framed) {
+        // An unmodified packet must survive the roundtrip.
+        Ok(parsed) => if data[3] == 0 {
+            assert_eq!(&parsed, packet);
+        },
+        Err(_) => assert!(data[3] != 0, "valid packet failed to parse"),
+    }
+});
@@ -0,0 +1,8 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use serde_osc::{de, Framing, OscPacket};
+
+fuzz_target!(|data: &[u8]| {
+    // Any input must either parse or produce an error; never panic.
+    let _ = de::from_slice::<OscPacket>(data, Framing::Unframed);
+});
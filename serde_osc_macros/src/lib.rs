@@ -0,0 +1,192 @@
+//! Procedural macros for serde_osc. Use them via serde_osc's `derive` feature,
+//! rather than depending on this crate directly.
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{Attribute, Data, DeriveInput, Error, Expr, ExprLit, Field, Fields, Lit, LitStr, Meta, Variant};
+
+/// Characters that the OSC spec forbids in addresses,
+/// as checked by `serde_osc::validate_address`.
+const INVALID_CHARS: &str = " #*,?[]{}";
+
+/// Create a `serde_osc::OscAddress` from a string literal, which is validated
+/// at compile time with the same rules as `serde_osc::validate_address`.
+/// An invalid address fails to compile, naming the offending character and its position.
+#[proc_macro]
+pub fn osc_address(input: TokenStream) -> TokenStream {
+    let address = syn::parse_macro_input!(input as LitStr);
+    match check_address(&address) {
+        Ok(()) => quote!(::serde_osc::address::OscAddress::from_validated(#address)).into(),
+        Err(err) => compile_error(err),
+    }
+}
+
+/// Allows fields of a struct to be annotated with `#[osc_address = "/some/address"]`.
+/// Place it above `#[derive(Serialize, Deserialize)]`.
+///
+/// The annotated field (typically of type `()`) then serializes as the given address,
+/// whatever its value, and deserializes only from that exact address, to its type's
+/// `Default`. The address is validated at compile time, as by `osc_address!`.
+#[proc_macro_attribute]
+pub fn osc_addressed(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = TokenStream2::from(args);
+    if !args.is_empty() {
+        return compile_error(Error::new_spanned(args, "osc_addressed takes no arguments"));
+    }
+    let mut input = syn::parse_macro_input!(input as DeriveInput);
+    match expand_addressed(&mut input) {
+        Ok(markers) => quote!(#input #markers).into(),
+        Err(err) => compile_error(err),
+    }
+}
+
+/// Replace each `#[osc_address = ".."]` attribute with serde attributes that
+/// (de)serialize the field through `serde_osc::address::FixedAddress`.
+/// Returns the marker types, one per annotated field, that name the addresses.
+fn expand_addressed(input: &mut DeriveInput) -> syn::Result<TokenStream2> {
+    let name = input.ident.clone();
+    let fields = match input.data {
+        Data::Struct(ref mut data) => &mut data.fields,
+        _ => return Err(Error::new_spanned(&input.ident, "osc_addressed can only be applied to structs")),
+    };
+    let mut markers = TokenStream2::new();
+    for (index, field) in fields.iter_mut().enumerate() {
+        let address = match take_osc_address(field)? {
+            Some(address) => address,
+            None => continue,
+        };
+        check_address(&address)?;
+        let field_name = field.ident.as_ref().map_or_else(|| index.to_string(), ToString::to_string);
+        let marker = format_ident!("__OscAddress_{}_{}", name, field_name);
+        let fixed = format!("::serde_osc::address::FixedAddress::<{}>", marker);
+        let serialize = format!("{}::serialize", fixed);
+        let deserialize = format!("{}::deserialize", fixed);
+        field.attrs.push(syn::parse_quote!(#[serde(serialize_with = #serialize, deserialize_with = #deserialize)]));
+        markers.extend(quote! {
+            #[doc(hidden)]
+            #[allow(non_camel_case_types)]
+            struct #marker;
+            impl ::serde_osc::address::AddressName for #marker {
+                const ADDRESS: &'static str = #address;
+            }
+        });
+    }
+    Ok(markers)
+}
+
+/// Remove the field's `#[osc_address = ".."]` attribute, if it has one, returning the address.
+fn take_osc_address(field: &mut Field) -> syn::Result<Option<LitStr>> {
+    let position = match field.attrs.iter().position(|attr| attr.path().is_ident("osc_address")) {
+        Some(position) => position,
+        None => return Ok(None),
+    };
+    let attr: Attribute = field.attrs.remove(position);
+    if let Meta::NameValue(ref meta) = attr.meta {
+        if let Expr::Lit(ExprLit{ lit: Lit::Str(ref address), .. }) = meta.value {
+            return Ok(Some(address.clone()));
+        }
+    }
+    Err(Error::new_spanned(attr, "expected #[osc_address = \"/some/address\"]"))
+}
+
+/// Report `err` at its span. Unlike `Error::to_compile_error`, this doesn't
+/// refer to `::core`, which 2015 edition crates can't name.
+fn compile_error(err: Error) -> TokenStream {
+    err.into_iter().map(|err| {
+        let message = err.to_string();
+        TokenStream::from(quote_spanned!(err.span()=> compile_error! { #message }))
+    }).collect()
+}
+
+/// Check `address` as `serde_osc::validate_address` would, failing with
+/// the same description as its `OscError::InvalidAddress`.
+fn check_address(address: &LitStr) -> syn::Result<()> {
+    let value = address.value();
+    let invalid = if value.starts_with('/') {
+        value.char_indices().find(|&(_, c)| INVALID_CHARS.contains(c))
+    } else {
+        Some((0, value.chars().next().unwrap_or('\0')))
+    };
+    match invalid {
+        Some((position, invalid_char)) => Err(Error::new(address.span(), format!(
+            "Invalid OSC address {:?}: unexpected {:?} at position {}", value, invalid_char, position))),
+        None => Ok(()),
+    }
+}
+
+/// Generates `fn dispatch(msg: &OscMessage) -> Option<ResultE<Self>>` for an enum,
+/// each of whose variants is annotated with `#[osc_path = "/some/pattern"]`.
+///
+/// `dispatch` picks the first variant whose pattern matches the message's
+/// address, and deserializes the message's arguments into that variant's
+/// fields, in order. It returns `None` if no variant matches.
+#[proc_macro_derive(OscDispatch, attributes(osc_path))]
+pub fn derive_osc_dispatch(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => compile_error(err),
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let data = match input.data {
+        Data::Enum(ref data) => data,
+        _ => return Err(Error::new_spanned(&input.ident, "OscDispatch can only be derived for enums")),
+    };
+    let name = &input.ident;
+    let mut arms = Vec::new();
+    for variant in &data.variants {
+        let path = osc_path(variant)?;
+        let ident = &variant.ident;
+        let types: Vec<_> = variant.fields.iter().map(|field| &field.ty).collect();
+        let bindings: Vec<_> = (0..types.len()).map(|i| format_ident!("arg{}", i)).collect();
+        let construct = match variant.fields {
+            Fields::Named(ref fields) => {
+                let names = fields.named.iter().map(|field| &field.ident);
+                quote!(#name::#ident { #(#names: #bindings),* })
+            },
+            Fields::Unnamed(_) => quote!(#name::#ident(#(#bindings),*)),
+            Fields::Unit => quote!(#name::#ident),
+        };
+        arms.push(quote! {
+            if let ::std::option::Option::Some(result) = msg.try_match::<(#(#types,)*)>(#path) {
+                return ::std::option::Option::Some(result.map(|(#(#bindings,)*)| #construct));
+            }
+        });
+    }
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Deserialize `msg` into the first variant whose `osc_path` matches its address.
+            pub fn dispatch(msg: &::serde_osc::OscMessage)
+                -> ::std::option::Option<::serde_osc::error::ResultE<Self>>
+            {
+                #(#arms)*
+                ::std::option::Option::None
+            }
+        }
+    })
+}
+
+/// The pattern given by the variant's `#[osc_path = "..."]` attribute.
+fn osc_path(variant: &Variant) -> syn::Result<LitStr> {
+    for attr in &variant.attrs {
+        if !attr.path().is_ident("osc_path") {
+            continue;
+        }
+        if let Meta::NameValue(ref meta) = attr.meta {
+            if let Expr::Lit(ExprLit{ lit: Lit::Str(ref path), .. }) = meta.value {
+                return Ok(path.clone());
+            }
+        }
+        return Err(Error::new_spanned(attr, "expected #[osc_path = \"/some/pattern\"]"));
+    }
+    Err(Error::new_spanned(&variant.ident, "missing #[osc_path = \"/some/pattern\"] attribute"))
+}
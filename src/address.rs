@@ -0,0 +1,172 @@
+//! Validated OSC addresses.
+
+use std::fmt;
+#[cfg(feature = "derive")]
+use std::marker::PhantomData;
+use std::ops::Deref;
+use serde::de::{Deserialize, Deserializer};
+use serde::de;
+use serde::ser::{Serialize, Serializer};
+
+use error::{OscError, ResultE};
+
+/// Characters that the OSC spec forbids in addresses.
+/// Most of these have special meaning in address patterns.
+const INVALID_CHARS: &[u8] = b" #*,?[]{}";
+
+/// An OSC address which is known to be valid, i.e. it begins with '/'
+/// and contains none of the characters: space # * , ? [ ] { }
+///
+/// This serializes as a plain string, and validation is performed on deserialization.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OscAddress(String);
+
+impl OscAddress {
+    pub fn new(address: &str) -> ResultE<Self> {
+        validate_address(address)?;
+        Ok(OscAddress(address.to_owned()))
+    }
+    /// Wrap `address` without checking it.
+    /// The caller must ensure that it has already passed `validate_address`.
+    #[doc(hidden)]
+    pub fn from_validated(address: &str) -> Self {
+        OscAddress(address.to_owned())
+    }
+    /// Iterate over the '/'-separated parts of the address.
+    /// e.g. "/audio/play" yields "audio", then "play".
+    pub fn components(&self) -> impl Iterator<Item = &str> {
+        self.0[1..].split('/')
+    }
+    /// Join `parts` into an address, e.g. `["audio", "play"]` into "/audio/play".
+    /// Fails with `OscError::InvalidAddress` if any part contains a '/',
+    /// or a character forbidden in addresses, or if there are no parts.
+    pub fn from_parts(parts: &[&str]) -> ResultE<Self> {
+        let mut address = String::new();
+        for part in parts {
+            push_component(&mut address, part)?;
+        }
+        Self::new(&address)
+    }
+    /// This address, extended by one more component,
+    /// e.g. "/audio" joined with "play" is "/audio/play".
+    /// Fails under the same conditions as `from_parts`.
+    pub fn join(&self, component: &str) -> ResultE<Self> {
+        // Don't double the slash of the root address.
+        let mut address = self.0.trim_end_matches('/').to_owned();
+        push_component(&mut address, component)?;
+        Self::new(&address)
+    }
+}
+
+/// Append '/' and `component` to `address`, checking that `component` contains no '/'.
+fn push_component(address: &mut String, component: &str) -> ResultE<()> {
+    address.push('/');
+    if let Some(position) = component.find('/') {
+        return Err(OscError::InvalidAddress {
+            position: address.len() + position,
+            address: format!("{}{}", address, component),
+            invalid_char: '/',
+        });
+    }
+    address.push_str(component);
+    Ok(())
+}
+
+/// Check that `address` is a valid OSC address, i.e. that it begins with '/'
+/// and contains none of the characters: space # * , ? [ ] { }
+///
+/// Fails with `OscError::InvalidAddress`, which indicates the first offending character.
+pub fn validate_address(address: &str) -> ResultE<()> {
+    check_leading_slash(address)?;
+    check_chars(address)
+}
+
+fn check_chars(address: &str) -> ResultE<()> {
+    let is_invalid = |c: char| c.is_ascii() && INVALID_CHARS.contains(&(c as u8));
+    match address.char_indices().find(|&(_, c)| is_invalid(c)) {
+        Some((position, invalid_char)) => Err(OscError::InvalidAddress {
+            address: address.to_owned(),
+            position,
+            invalid_char,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Check only that an address (or address pattern) begins with '/'.
+pub(crate) fn check_leading_slash(address: &str) -> ResultE<()> {
+    if address.starts_with('/') {
+        return Ok(());
+    }
+    Err(OscError::InvalidAddress {
+        address: address.to_owned(),
+        position: 0,
+        invalid_char: address.chars().next().unwrap_or('\0'),
+    })
+}
+
+impl Deref for OscAddress {
+    type Target = str;
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for OscAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Serialize for OscAddress {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for OscAddress {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        let address = String::deserialize(deserializer)?;
+        OscAddress::new(&address).map_err(de::Error::custom)
+    }
+}
+
+/// Names the address of a field annotated with `#[osc_address = ".."]`.
+/// Implemented by `#[osc_addressed]`.
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub trait AddressName {
+    const ADDRESS: &'static str;
+}
+
+/// The `serialize_with` and `deserialize_with` functions
+/// that `#[osc_addressed]` gives fields annotated with `#[osc_address = ".."]`.
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub struct FixedAddress<A>(PhantomData<A>);
+
+#[cfg(feature = "derive")]
+impl<A: AddressName> FixedAddress<A> {
+    /// Serialize the fixed address, in place of the field's value.
+    pub fn serialize<T, S>(_value: &T, serializer: S) -> Result<S::Ok, S::Error>
+        where T: ?Sized, S: Serializer
+    {
+        serializer.serialize_str(A::ADDRESS)
+    }
+    /// Check that the received address is the fixed one,
+    /// in which case the field takes its default value.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+        where T: Default, D: Deserializer<'de>
+    {
+        let address = String::deserialize(deserializer)?;
+        if address != A::ADDRESS {
+            return Err(de::Error::custom(format_args!(
+                "expected address {:?}, found {:?}", A::ADDRESS, address)));
+        }
+        Ok(T::default())
+    }
+}
@@ -0,0 +1,5 @@
+//! Not part of the public API.
+//!
+//! The benchmarks compare the serde path against hand-written encoding,
+//! which requires access to the otherwise-private OSC writing primitives.
+pub use ser::osc_writer::OscWriter;
@@ -0,0 +1,160 @@
+//! Builders for constructing packets one piece at a time.
+
+use de;
+use error::{OscError, ResultE};
+use framing::Framing;
+use packet::{OscBundle, OscMessage, OscPacket};
+use ser::bundle_serializer::{check_raw_element, BundleSerializer};
+use types::{OscArg, OscTimeTag};
+
+/// Build an [`OscMessage`] by appending one argument at a time.
+///
+/// ```
+/// # use serde_osc::MessageBuilder;
+/// let packet = MessageBuilder::new("/audio/play").unwrap()
+///     .i32(1)
+///     .f32(44100.0)
+///     .build_vec()
+///     .unwrap();
+/// ```
+///
+/// [`OscMessage`]: ../packet/struct.OscMessage.html
+#[derive(Clone, Debug)]
+pub struct MessageBuilder {
+    msg: OscMessage,
+}
+
+impl MessageBuilder {
+    /// Begin a message, checking that `address` is a well-formed address pattern.
+    pub fn new(address: &str) -> ResultE<Self> {
+        Self::with_capacity(address, 0)
+    }
+    /// Begin a message with room for `args` arguments before reallocating.
+    pub fn with_capacity(address: &str, args: usize) -> ResultE<Self> {
+        Ok(Self {
+            msg: OscMessage::new(address, Vec::with_capacity(args))?,
+        })
+    }
+    pub fn arg(&mut self, arg: OscArg) -> &mut Self {
+        self.msg.args.push(arg);
+        self
+    }
+    pub fn i32(&mut self, value: i32) -> &mut Self {
+        self.arg(OscArg::I32(value))
+    }
+    pub fn f32(&mut self, value: f32) -> &mut Self {
+        self.arg(OscArg::F32(value))
+    }
+    pub fn str(&mut self, value: &str) -> &mut Self {
+        self.arg(OscArg::String(value.to_owned()))
+    }
+    pub fn blob(&mut self, value: &[u8]) -> &mut Self {
+        self.arg(OscArg::Blob(value.to_owned()))
+    }
+    pub fn build(&self) -> OscMessage {
+        self.msg.clone()
+    }
+    /// Serialize the message into a length-prefixed OSC packet.
+    pub fn build_vec(&self) -> ResultE<Vec<u8>> {
+        // The message's size is known exactly, so nothing needs reallocating.
+        let mut output = Vec::with_capacity(4 + self.msg.byte_len());
+        self.msg.to_serializer()?.write_into(&mut output, Framing::Framed)?;
+        Ok(output)
+    }
+}
+
+/// Size assumed for each element of a bundle (including its length prefix)
+/// when reserving space to serialize it, enough for a short message with a few arguments.
+const AVERAGE_ELEMENT_BYTES: usize = 64;
+
+/// Build an [`OscBundle`] by appending one element at a time.
+/// Elements may be messages or other bundles, either as packets
+/// or already serialized (e.g. when forwarding received packets).
+///
+/// [`OscBundle`]: ../packet/struct.OscBundle.html
+#[derive(Clone, Debug)]
+pub struct BundleBuilder {
+    timetag: OscTimeTag,
+    elements: Vec<Element>,
+}
+
+#[derive(Clone, Debug)]
+enum Element {
+    Packet(OscPacket),
+    /// An unframed packet, as passed to `push_raw`, along with its parsed form.
+    Raw(Vec<u8>, OscPacket),
+}
+
+impl BundleBuilder {
+    pub fn new(timetag: OscTimeTag) -> Self {
+        Self::with_capacity(timetag, 0)
+    }
+    /// Begin a bundle with room for `elements` elements before reallocating.
+    pub fn with_capacity(timetag: OscTimeTag, elements: usize) -> Self {
+        Self {
+            timetag,
+            elements: Vec::with_capacity(elements),
+        }
+    }
+    /// Begin a bundle that is to be executed immediately.
+    pub fn immediate() -> Self {
+        Self::new(OscTimeTag::IMMEDIATE)
+    }
+    pub fn push<P: Into<OscPacket>>(&mut self, element: P) -> &mut Self {
+        self.elements.push(Element::Packet(element.into()));
+        self
+    }
+    /// Append an element that's already been serialized, without framing
+    /// (e.g. a received UDP payload), to be copied into the bundle as-is.
+    ///
+    /// The element is parsed here, failing with the error encountered if it
+    /// isn't a well-formed message or bundle (including if it isn't a multiple
+    /// of 4 bytes long), so that `build` needn't.
+    pub fn push_raw(&mut self, packet: &[u8]) -> ResultE<&mut Self> {
+        check_raw_element(packet)?;
+        let parsed = de::from_slice(packet, Framing::Unframed)?;
+        self.elements.push(Element::Raw(packet.to_owned(), parsed));
+        Ok(self)
+    }
+    /// Append a message that's already been serialized without framing,
+    /// e.g. by `to_vec(.., Framing::Unframed)`. Like `push_raw`, but also
+    /// fails with `OscError::BadFormat` if `bytes` holds a bundle.
+    pub fn with_message_bytes(&mut self, bytes: &[u8]) -> ResultE<&mut Self> {
+        if bytes.first() == Some(&b'#') {
+            return Err(OscError::BadFormat{ offset: 0, context: "expected a message, not a bundle" });
+        }
+        self.push_raw(bytes)
+    }
+    /// Append a message or bundle that's already been serialized without
+    /// framing. Equivalent to `push_raw`.
+    pub fn with_packet_bytes(&mut self, bytes: &[u8]) -> ResultE<&mut Self> {
+        self.push_raw(bytes)
+    }
+    /// The bundle built so far.
+    pub fn build(&self) -> OscBundle {
+        let elements = self.elements.iter().map(|element| match *element {
+            Element::Packet(ref packet) | Element::Raw(_, ref packet) => packet.clone(),
+        });
+        OscBundle::new(self.timetag, elements.collect())
+    }
+    /// Serialize the bundle into a length-prefixed OSC packet.
+    pub fn build_vec(&self) -> ResultE<Vec<u8>> {
+        let mut bundle = BundleSerializer::with_capacity(8 + self.elements_bytes_hint());
+        bundle.write_timetag(self.timetag.into())?;
+        for element in &self.elements {
+            match *element {
+                Element::Packet(ref packet) => bundle.push_element(packet)?,
+                Element::Raw(ref packet, _) => bundle.push_raw_bytes(packet)?,
+            }
+        }
+        let mut output = Vec::new();
+        bundle.write_into(&mut output, Framing::Framed)?;
+        Ok(output)
+    }
+    /// Roughly how many bytes the elements will take once serialized.
+    /// Measuring them exactly would mean walking every nested bundle,
+    /// so this assumes they're of a typical size.
+    fn elements_bytes_hint(&self) -> usize {
+        self.elements.len() * AVERAGE_ELEMENT_BYTES
+    }
+}
@@ -0,0 +1,90 @@
+use std::convert::TryInto;
+use std::io::Cursor;
+use byteorder::{BigEndian, ByteOrder};
+use bytes::{Buf, BufMut, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+
+use config::OscConfig;
+use de;
+use error::{OscError, ResultE};
+use framing::Framing;
+use packet::OscPacket;
+use ser;
+
+/// A [`tokio_util`] codec for length-prefixed packets (i.e. `Framing::Framed`),
+/// for use with `FramedRead` and `FramedWrite` over a stream such as TCP.
+///
+/// Received packets are decoded with the limits of an [`OscConfig`]:
+/// `OscConfig::default()` unless another is given via `with_config`.
+///
+/// [`tokio_util`]: https://docs.rs/tokio-util
+/// [`OscConfig`]: ../config/struct.OscConfig.html
+#[derive(Clone, Debug, Default)]
+pub struct OscCodec {
+    /// Length of the packet currently being received, once its prefix has been read.
+    length: Option<usize>,
+    config: OscConfig,
+}
+
+impl OscCodec {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Decode packets with the limits in `config` rather than the defaults.
+    pub fn with_config(config: OscConfig) -> Self {
+        Self {
+            length: None,
+            config,
+        }
+    }
+}
+
+impl Decoder for OscCodec {
+    type Item = OscPacket;
+    type Error = OscError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> ResultE<Option<OscPacket>> {
+        let length = match self.length {
+            Some(length) => length,
+            None => {
+                if src.len() < 4 {
+                    return Ok(None);
+                }
+                let length = src.get_i32().try_into()?;
+                // Checked before any space is reserved for the packet,
+                // since the length comes from the peer.
+                if length > self.config.max_packet_size {
+                    return Err(OscError::BadFormat{ offset: 0, context: "invalid packet length" });
+                }
+                self.length = Some(length);
+                length
+            },
+        };
+        if src.len() < length {
+            // Wait for the rest of the packet.
+            src.reserve(length - src.len());
+            return Ok(None);
+        }
+        self.length = None;
+        let payload = src.split_to(length);
+        de::from_read_with_config(Cursor::new(&payload[..]), Framing::Unframed, &self.config).map(Some)
+    }
+}
+
+impl Encoder<OscPacket> for OscCodec {
+    type Error = OscError;
+
+    fn encode(&mut self, item: OscPacket, dst: &mut BytesMut) -> ResultE<()> {
+        // The length isn't known until the packet has been serialized,
+        // so leave room for it and fill it in afterward.
+        let start = dst.len();
+        dst.put_i32(0);
+        if let Err(e) = ser::to_write(&mut dst.writer(), &item, Framing::Unframed) {
+            dst.truncate(start);
+            return Err(e);
+        }
+        let length = (dst.len() - start - 4).try_into()?;
+        BigEndian::write_i32(&mut dst[start..start + 4], length);
+        Ok(())
+    }
+}
@@ -0,0 +1,145 @@
+/// Limits applied while (de)serializing packets, via functions such as
+/// [`from_read_with_config`].
+///
+/// Packets arriving over a network can't be trusted: without limits, a peer
+/// could send a packet holding a 2 GB blob, which would be read into memory
+/// in full. (Space is never allocated for more data than has actually
+/// arrived, however.) Functions that don't take a config (e.g. [`from_read`])
+/// apply no limits, so should only be used with trusted data.
+///
+/// The defaults accommodate any reasonable OSC traffic:
+///
+/// * packets of up to 64 KiB (the largest possible UDP datagram);
+/// * blobs of up to 1 MiB;
+/// * strings of up to 1 KiB;
+/// * bundles nested up to 8 levels deep.
+///
+/// Even without a config, bundles and arrays can't be nested
+/// more than 64 levels deep.
+///
+/// ```
+/// # use serde_osc::OscConfig;
+/// let config = OscConfig::default()
+///     .max_packet_size(1024)
+///     .max_bundle_depth(1);
+/// ```
+///
+/// [`from_read_with_config`]: ../de/fn.from_read_with_config.html
+/// [`from_read`]: ../de/fn.from_read.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OscConfig {
+    pub(crate) max_packet_size: usize,
+    pub(crate) max_blob_size: usize,
+    pub(crate) max_string_len: usize,
+    pub(crate) max_bundle_depth: usize,
+    /// Reject messages with a missing type tag, or one not beginning with ','.
+    pub(crate) require_type_tag: bool,
+    /// Reject strings and blobs whose padding bytes aren't zero.
+    pub(crate) strict_padding: bool,
+    /// Skip over arguments with unrecognized type tags, rather than failing.
+    pub(crate) skip_unknown_types: bool,
+    /// Reject messages with more arguments than the consumer deserialized.
+    pub(crate) strict_arg_count: bool,
+    /// Reject messages with data beyond the arguments their type tag describes.
+    /// Only set by `from_read_exact`.
+    pub(crate) reject_trailing_data: bool,
+}
+
+/// Most levels of bundles, or of arrays within a message, that may ever be nested,
+/// whatever the config. Deserializing recurses once per level, so deeper
+/// packets could overflow the stack.
+pub(crate) const MAX_NESTING: usize = 64;
+
+/// Used where no config is explicitly provided.
+pub(crate) static UNLIMITED: OscConfig = OscConfig {
+    max_packet_size: usize::MAX,
+    max_blob_size: usize::MAX,
+    max_string_len: usize::MAX,
+    max_bundle_depth: usize::MAX,
+    require_type_tag: false,
+    strict_padding: true,
+    skip_unknown_types: false,
+    strict_arg_count: false,
+    reject_trailing_data: false,
+};
+
+impl Default for OscConfig {
+    fn default() -> Self {
+        Self {
+            max_packet_size: 64 * 1024,
+            max_blob_size: 1024 * 1024,
+            max_string_len: 1024,
+            max_bundle_depth: 8,
+            require_type_tag: false,
+            strict_padding: true,
+            skip_unknown_types: false,
+            strict_arg_count: false,
+            reject_trailing_data: false,
+        }
+    }
+}
+
+impl OscConfig {
+    /// The default limits, while rejecting anything that deviates from the spec:
+    /// missing type tags, type tags not beginning with ',', non-zero padding,
+    /// and unrecognized argument types.
+    /// Suitable for security-sensitive contexts.
+    pub fn strict() -> Self {
+        Self {
+            require_type_tag: true,
+            strict_padding: true,
+            skip_unknown_types: false,
+            ..Self::default()
+        }
+    }
+    /// The default limits, while accepting anything that can be made sense of:
+    /// a missing type tag is taken to mean no arguments, as OSC 1.0 recommends,
+    /// padding bytes are ignored, and arguments of unrecognized types are skipped
+    /// (assuming they carry no data).
+    ///
+    /// The default config is lenient about type tags, but not padding or unknown types.
+    pub fn lenient() -> Self {
+        Self {
+            require_type_tag: false,
+            strict_padding: false,
+            skip_unknown_types: true,
+            ..Self::default()
+        }
+    }
+    /// Maximum size of a packet, in bytes, excluding any framing.
+    pub fn max_packet_size(mut self, bytes: usize) -> Self {
+        self.max_packet_size = bytes;
+        self
+    }
+    /// Maximum size of a blob argument's data, in bytes.
+    pub fn max_blob_size(mut self, bytes: usize) -> Self {
+        self.max_blob_size = bytes;
+        self
+    }
+    /// Maximum length of any string (including addresses and type tags), in bytes.
+    pub fn max_string_len(mut self, chars: usize) -> Self {
+        self.max_string_len = chars;
+        self
+    }
+    /// Maximum number of bundles that may enclose a message.
+    /// 0 disallows bundles entirely. Regardless of this setting,
+    /// bundles can't be nested more than 64 levels deep.
+    pub fn max_bundle_depth(mut self, levels: usize) -> Self {
+        self.max_bundle_depth = levels;
+        self
+    }
+    /// Whether to reject strings and blobs whose padding bytes aren't zero,
+    /// with `OscError::BadPadding`. This is the default, as the spec requires it,
+    /// but some hardware fills the padding with garbage.
+    pub fn strict_padding(mut self, strict: bool) -> Self {
+        self.strict_padding = strict;
+        self
+    }
+    /// Whether to reject messages with arguments left over once the value
+    /// being deserialized is complete, e.g. a message with 5 arguments when
+    /// deserializing a struct with 3 fields. Such arguments are normally ignored.
+    pub fn strict_arg_count(mut self, strict: bool) -> Self {
+        self.strict_arg_count = strict;
+        self
+    }
+}
@@ -1,46 +1,88 @@
-use std::io::{Read, Take};
+use std::io::Read;
 use std::vec;
 use serde::de;
 use serde::de::{DeserializeSeed, SeqAccess, Visitor};
 
-use error::{Error, ResultE};
+use config::{OscConfig, MAX_NESTING};
+use error::{OscError, ResultE};
+use types::{OscColor, OscMidi};
 use super::osc_reader::OscReader;
+use super::pkt_reader::PktReader;
 use super::osc_type::OscType;
 use super::maybe_skip_comma::MaybeSkipComma;
 
+/// Every type tag that `ArgVisitor::parse_arg` understands.
+const KNOWN_TAGS: &[u8] = b"ifuhdcrmtTFNIsSb[]";
+
 #[derive(Debug)]
 pub struct ArgDeserializer<'a, R: Read + 'a> {
     data: Option<ArgVisitor<'a, R>>,
+    /// Offset of the type tag, for error reporting.
+    position: usize,
 }
 
 /// Deserializes the argument data of an OSC message.
 #[derive(Debug)]
 pub struct ArgVisitor<'a, R: Read + 'a> {
-    read: &'a mut Take<R>,
+    read: &'a mut PktReader<R>,
     /// calling .next() on this returns the OSC char code of the next argument,
     /// e.g. 'i' for i32, 'f' for f32, etc.
     /// We store this as an iterator to avoid tracking the index of the current arg.
     arg_types : MaybeSkipComma<vec::IntoIter<u8>>,
+    config: &'a OscConfig,
+    /// Index of the next argument, for error reporting.
+    arg_index: usize,
+    /// Number of arrays enclosing the argument being parsed.
+    array_depth: usize,
+    /// Whether the consumer asked for another argument after the last one.
+    exhausted: bool,
 }
 
 impl<'a, R: Read + 'a> ArgDeserializer<'a, R> {
-    pub fn new(read: &'a mut Take<R>) -> ResultE<Self> {
+    pub fn new(read: &'a mut PktReader<R>, config: &'a OscConfig) -> ResultE<Self> {
         Ok(Self {
-            data: Some(ArgVisitor::new(read)?),
+            position: read.position(),
+            data: Some(ArgVisitor::new(read, config)?),
         })
     }
+    /// Deserialize the arguments into a type which (normally) needs `expected` of them.
+    /// If there are too few, this is reported as `OscError::ArgMiscount`.
+    fn deserialize_counted<'de, V>(&mut self, expected: usize, visitor: V) -> ResultE<V::Value>
+        where V: Visitor<'de>
+    {
+        match self.data.take() {
+            Some(mut data) => match visitor.visit_seq(&mut data) {
+                Err(_) if data.exhausted => Err(OscError::ArgMiscount{ expected, found: data.arg_index }),
+                Err(error) => Err(error),
+                Ok(value) => {
+                    data.finish()?;
+                    Ok(value)
+                },
+            },
+            // The arguments can only be deserialized once.
+            None => Err(OscError::BadFormat{ offset: self.position, context: "arguments already deserialized" }),
+        }
+    }
 }
 impl<'de, 'a, R> de::Deserializer<'de> for &'a mut ArgDeserializer<'a, R>
     where R: Read + 'a
 {
-    type Error = Error;
+    type Error = OscError;
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
     fn deserialize_any<V>(self, visitor: V) -> ResultE<V::Value>
         where V: Visitor<'de>
     {
         match self.data.take() {
-            Some(data) => visitor.visit_seq(data),
+            Some(mut data) => {
+                let value = visitor.visit_seq(&mut data)?;
+                data.finish()?;
+                Ok(value)
+            },
             // The arguments can only be deserialized once.
-            None => Err(Error::BadFormat),
+            None => Err(OscError::BadFormat{ offset: self.position, context: "arguments already deserialized" }),
         }
     }
     // default serde impls don't equate units to empty sequences.
@@ -53,11 +95,11 @@ impl<'de, 'a, R> de::Deserializer<'de> for &'a mut ArgDeserializer<'a, R>
                     // We have no arguments; decoding a unit is ok!
                     None => visitor.visit_unit(),
                     // Cannot deserialize a unit from a non-empty sequence!
-                    Some(_) => Err(Error::BadFormat),
+                    Some(_) => Err(OscError::BadFormat{ offset: self.position, context: "expected no arguments" }),
                 }
             },
             // The arguments can only be deserialized once.
-            None => Err(Error::BadFormat),
+            None => Err(OscError::BadFormat{ offset: self.position, context: "arguments already deserialized" }),
         }
     }
     fn deserialize_unit_struct<V>(
@@ -69,13 +111,39 @@ impl<'de, 'a, R> de::Deserializer<'de> for &'a mut ArgDeserializer<'a, R>
     {
         self.deserialize_unit(visitor)
     }
+    // For fixed-length types, we can tell how many arguments are missing.
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> ResultE<V::Value>
+        where V: Visitor<'de>
+    {
+        self.deserialize_counted(len, visitor)
+    }
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V
+    ) -> ResultE<V::Value>
+        where V: Visitor<'de>
+    {
+        self.deserialize_counted(len, visitor)
+    }
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V
+    ) -> ResultE<V::Value>
+        where V: Visitor<'de>
+    {
+        self.deserialize_counted(fields.len(), visitor)
+    }
 
     // This struct only deserializes sequences; ignore all type hints.
     // More info: https://github.com/serde-rs/serde/blob/b7d6c5d9f7b3085a4d40a446eeb95976d2337e07/serde/src/macros.rs#L106
     forward_to_deserialize_any! {
         bool u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 char str string option
         seq bytes byte_buf map newtype_struct
-        tuple_struct struct identifier tuple enum ignored_any
+        identifier enum ignored_any
     }
 }
 
@@ -83,26 +151,99 @@ impl<'de, 'a, R> de::Deserializer<'de> for &'a mut ArgDeserializer<'a, R>
 impl<'a, R> ArgVisitor<'a, R>
     where R: Read + 'a
 {
-    pub fn new(read: &'a mut Take<R>) -> ResultE<Self> {
-        let arg_types = read.read_0term_bytes().map(|bytes| MaybeSkipComma::new(bytes.into_iter()))?;
+    pub fn new(read: &'a mut PktReader<R>, config: &'a OscConfig) -> ResultE<Self> {
+        let position = read.position();
+        // OSC 1.0 recommends treating a missing type tag as an absence of arguments.
+        let type_tag = match read.limit() {
+            0 => Vec::new(),
+            _ => read.read_0term_bytes(config)?,
+        };
+        if config.require_type_tag && type_tag.first() != Some(&b',') {
+            return Err(OscError::BadFormat{ offset: position, context: "type tag must begin with ','" });
+        }
+        let arg_types = MaybeSkipComma::new(type_tag.into_iter());
         Ok(ArgVisitor {
             read,
             arg_types,
+            config,
+            arg_index: 0,
+            array_depth: 0,
+            exhausted: false,
         })
     }
-    fn parse_next(&mut self) -> ResultE<Option<OscType>> {
-        match self.arg_types.next() {
+    pub fn parse_next(&mut self) -> ResultE<Option<OscType>> {
+        match self.next_tag() {
             None => Ok(None),
-            Some(tag) => self.parse_arg(tag).map(|arg| Some(arg)),
+            Some(tag) => {
+                let arg = self.parse_arg(tag)?;
+                self.arg_index += 1;
+                Ok(Some(arg))
+            },
         }
     }
+    /// Called once the consumer is done with the arguments.
+    /// Fails if any remain and the config requires them all to be consumed,
+    /// or if there's data after the last argument and the config forbids that.
+    fn finish(&mut self) -> ResultE<()> {
+        let remaining = self.next_tag().is_some();
+        if self.config.strict_arg_count && remaining {
+            return Err(OscError::BadFormat{ offset: self.read.position(), context: "unconsumed arguments" });
+        }
+        if self.config.reject_trailing_data && !remaining && self.read.limit() != 0 {
+            return Err(OscError::BadFormat{ offset: self.read.position(), context: "data after last argument" });
+        }
+        Ok(())
+    }
+    /// Get the type of the next argument, passing over any unrecognized types if configured to.
+    fn next_tag(&mut self) -> Option<u8> {
+        let skip_unknown = self.config.skip_unknown_types;
+        self.arg_types.by_ref().find(|tag| !skip_unknown || KNOWN_TAGS.contains(tag))
+    }
     fn parse_arg(&mut self, typecode: u8) -> ResultE<OscType> {
+        #[cfg(feature = "tracing")]
+        tracing::trace!(type_code = %(typecode as char), arg_index = self.arg_index, "parsing argument");
         match typecode {
             b'i' => self.read.parse_i32().map(|i| { OscType::I32(i) }),
             b'f' => self.read.parse_f32().map(|f| { OscType::F32(f) }),
-            b's' => self.read.parse_str().map(|s| { OscType::String(s) }),
-            b'b' => self.read.parse_blob().map(|b| { OscType::Blob(b) }),
-            _ => Err(Error::UnsupportedType),
+            b'u' => self.read.parse_u32().map(|u| { OscType::U32(u) }),
+            b'h' => self.read.parse_i64().map(|h| { OscType::I64(h) }),
+            b'd' => self.read.parse_f64().map(|d| { OscType::F64(d) }),
+            b'c' => self.read.parse_char().map(|c| { OscType::Char(c) }),
+            b'r' => self.read.parse_color().map(|r| { OscType::Color(OscColor(r)) }),
+            b'm' => self.read.parse_midi().map(|m| { OscType::Midi(OscMidi(m)) }),
+            b't' => self.read.parse_timetag().map(|t| { OscType::TimeTag(t) }),
+            // These types have no data, only a tag.
+            b'T' => Ok(OscType::Bool(true)),
+            b'F' => Ok(OscType::Bool(false)),
+            b'N' => Ok(OscType::Nil),
+            b'I' => Ok(OscType::Impulse),
+            b's' => self.read.parse_str(self.config).map(|s| { OscType::String(s) }),
+            b'S' => self.read.parse_str(self.config).map(|s| { OscType::Symbol(s) }),
+            b'b' => self.read.parse_blob(self.config).map(|b| { OscType::Blob(b) }),
+            b'[' => self.parse_array().map(|a| { OscType::Array(a) }),
+            // An array end without a matching start.
+            b']' => Err(OscError::BadFormat{ offset: self.read.position(), context: "unmatched ']' in type tag" }),
+            _ => Err(OscError::UnsupportedType{ type_code: typecode, arg_index: self.arg_index }),
+        }
+    }
+    /// Parse the elements of an array, up to and including the ']' that closes it.
+    /// Nested arrays are handled by recursing through `parse_arg`.
+    fn parse_array(&mut self) -> ResultE<Vec<OscType>> {
+        if self.array_depth >= MAX_NESTING {
+            return Err(OscError::BadFormat{ offset: self.read.position(), context: "arrays nested too deeply" });
+        }
+        self.array_depth += 1;
+        let mut elements = Vec::new();
+        loop {
+            match self.next_tag() {
+                Some(b']') => {
+                    self.array_depth -= 1;
+                    return Ok(elements);
+                },
+                Some(tag) => elements.push(self.parse_arg(tag)?),
+                // The type tag ended before the array was closed.
+                None => return Err(OscError::BadFormat{ offset: self.read.position(), context: "unterminated array" }),
+            }
         }
     }
 }
@@ -111,7 +252,7 @@ impl<'a, R> ArgVisitor<'a, R>
 impl<'de, 'a, R> SeqAccess<'de> for ArgVisitor<'a, R>
     where R: Read + 'a
 {
-    type Error = Error;
+    type Error = OscError;
     fn next_element_seed<T>(&mut self, seed: T) -> ResultE<Option<T::Value>>
         where T: DeserializeSeed<'de>
     {
@@ -120,7 +261,10 @@ impl<'de, 'a, R> SeqAccess<'de> for ArgVisitor<'a, R>
         let value = self.parse_next()?;
         match value {
             // end of sequence
-            None => Ok(None),
+            None => {
+                self.exhausted = true;
+                Ok(None)
+            },
             Some(osc_arg) => seed.deserialize(osc_arg).map(Some),
         }
     }
@@ -1,19 +1,24 @@
-use std::io::{Read, Take};
+use std::io::Read;
 use std::mem;
 use serde::de;
 use serde::de::{DeserializeSeed, SeqAccess, Visitor};
 
-use error::{Error, ResultE};
+use config::OscConfig;
+use error::{OscError, ResultE};
 use super::iter_visitor::IterVisitor;
 use super::osc_reader::OscReader;
+use super::pkt_reader::PktReader;
 use super::pkt_deserializer::PktDeserializer;
 use super::prim_deserializer::PrimDeserializer;
 
 /// Deserializes a single bundle, within a packet.
 #[derive(Debug)]
 pub struct BundleVisitor<'a, R: Read + 'a> {
-    read: &'a mut Take<R>,
+    read: &'a mut PktReader<R>,
     state: State,
+    config: &'a OscConfig,
+    /// How many bundles enclose the elements, including this one.
+    depth: usize,
 }
 
 /// Which part of the bundle is being parsed
@@ -23,26 +28,34 @@ enum State {
     TimeTag,
     /// Parsing the body of the bundle: OSC Bundle Elements
     Elements,
+    /// No more data to deserialize from this bundle.
+    Done,
 }
 
 /// Struct to deserialize a single element from the OSC bundle
 enum BundleField<'a, R: Read + 'a> {
     TimeTag((u32, u32)),
-    Elements(&'a mut Take<R>),
+    Elements(ElemAccessor<'a, R>),
 }
 
 /// Deserializes each item (message/bundle) within the bundle element sequence.
 struct ElemAccessor<'a, R: Read + 'a> {
-    read: &'a mut Take<R>,
+    read: &'a mut PktReader<R>,
+    config: &'a OscConfig,
+    depth: usize,
+    /// Index of the next element, for error reporting.
+    index: usize,
 }
 
 impl<'a, R> BundleVisitor<'a, R>
     where R: Read + 'a
 {
-    pub fn new(read: &'a mut Take<R>) -> Self {
+    pub fn new(read: &'a mut PktReader<R>, config: &'a OscConfig, depth: usize) -> Self {
         Self {
             read: read,
             state: State::TimeTag,
+            config,
+            depth,
         }
     }
 }
@@ -51,18 +64,24 @@ impl<'a, R> BundleVisitor<'a, R>
 impl<'de, 'a, R> SeqAccess<'de> for BundleVisitor<'a, R>
     where R: Read + 'a
 {
-    type Error = Error;
+    type Error = OscError;
     fn next_element_seed<T>(&mut self, seed: T) -> ResultE<Option<T::Value>>
         where T: DeserializeSeed<'de>
     {
-        if self.read.limit() == 0 {
+        // The elements are presented as a sequence, even if there are none.
+        let elem = match mem::replace(&mut self.state, State::Done) {
+            State::TimeTag => {
+                self.state = State::Elements;
+                BundleField::TimeTag(self.read.parse_timetag()?)
+            },
+            State::Elements => BundleField::Elements(ElemAccessor {
+                read: self.read,
+                config: self.config,
+                depth: self.depth,
+                index: 0,
+            }),
             // end of bundle
-            return Ok(None);
-        }
-        let elem = match mem::replace(&mut self.state, State::Elements) {
-            State::TimeTag => BundleField::TimeTag(self.read.parse_timetag()?),
-            State::Elements => BundleField::Elements(self.read),
-            //State::Elements => BundleField::Packet(PktDeserializer::new(self.read)),
+            State::Done => return Ok(None),
         };
         seed.deserialize(elem).map(Some)
     }
@@ -72,7 +91,11 @@ impl<'de, 'a, R> SeqAccess<'de> for BundleVisitor<'a, R>
 impl<'de, 'a, R> de::Deserializer<'de> for BundleField<'a, R>
     where R: Read + 'a
 {
-    type Error = Error;
+    type Error = OscError;
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
     // deserializes a single item from the message, consuming self.
     fn deserialize_any<V>(self, visitor: V) -> ResultE<V::Value>
     where
@@ -81,9 +104,8 @@ impl<'de, 'a, R> de::Deserializer<'de> for BundleField<'a, R>
         match self {
             BundleField::TimeTag((sec, frac)) =>
                 visitor.visit_seq(IterVisitor([sec, frac].iter().cloned()
-                    .map(PrimDeserializer))),
-            BundleField::Elements(mut read) =>
-                visitor.visit_seq(ElemAccessor{ read }),
+                    .map(PrimDeserializer::<u32>))),
+            BundleField::Elements(elements) => visitor.visit_seq(elements),
         }
     }
 
@@ -100,11 +122,25 @@ impl<'de, 'a, R> de::Deserializer<'de> for BundleField<'a, R>
 impl<'de, 'a, R> SeqAccess<'de> for ElemAccessor<'a, R>
     where R: Read + 'a
 {
-    type Error = Error;
+    type Error = OscError;
     fn next_element_seed<T>(&mut self, seed: T) -> ResultE<Option<T::Value>>
         where T: DeserializeSeed<'de>
     {
-        // TODO: handle EOF by returning None
-        seed.deserialize(&mut PktDeserializer::new(self.read)).map(Some)
+        if self.read.limit() == 0 {
+            // No more elements in this bundle.
+            return Ok(None);
+        }
+        // Erase the reader's type. Otherwise each level of bundle nesting would
+        // need a new deserializer type, and recursive types (e.g. OscArg)
+        // could never be fully instantiated.
+        let start = self.read.position();
+        let mut read: &mut dyn Read = self.read;
+        let element_index = self.index;
+        self.index += 1;
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("bundle_element", element_index).entered();
+        seed.deserialize(&mut PktDeserializer::bundle_element(&mut read, self.config, self.depth, start))
+            .map(Some)
+            .map_err(|inner| OscError::InBundle{ element_index, inner: Box::new(inner) })
     }
 }
@@ -1,6 +1,6 @@
 use serde::de::{Deserializer, DeserializeSeed, SeqAccess};
 
-use error::{Error, ResultE};
+use error::{OscError, ResultE};
 
 /// If we want to deserialize an entire sequence simultaneously,
 /// e.g. reading (u32, u32) from the bitstream atomically,
@@ -17,9 +17,9 @@ pub struct IterVisitor<I>(pub I);
 
 
 impl<'de, I> SeqAccess<'de> for IterVisitor<I>
-    where I: Iterator, I::Item : Deserializer<'de, Error=Error>
+    where I: Iterator, I::Item : Deserializer<'de, Error=OscError>
 {
-    type Error = Error;
+    type Error = OscError;
     fn next_element_seed<T>(&mut self, seed: T) -> ResultE<Option<T::Value>>
         where T: DeserializeSeed<'de>
     {
@@ -1,6 +1,10 @@
 use std::io::{Cursor, Read};
 use serde;
-use error::ResultE;
+use config::{OscConfig, UNLIMITED};
+use error::{OscError, ResultE};
+use framing::Framing;
+use self::osc_reader::OscReader;
+use self::pkt_reader::PktReader;
 
 mod arg_visitor;
 mod bundle_visitor;
@@ -9,25 +13,74 @@ mod maybe_skip_comma;
 mod msg_visitor;
 mod osc_reader;
 mod osc_type;
+mod packet_iter;
+mod peekable;
 mod pkt_deserializer;
+mod pkt_reader;
 mod prim_deserializer;
+mod read_args;
+mod skip;
+#[cfg(feature = "tokio")]
+mod read_async;
 
 pub use self::pkt_deserializer::PktDeserializer as Deserializer;
+pub use error::OscError as Error;
+pub use self::packet_iter::{from_read_all, packets_from_read, packets_until_eof, PacketIter};
+pub use self::peekable::PeekablePacketReader;
+pub use self::read_args::{read_address_and_args, read_all_args, read_message_raw};
+pub use self::skip::{skip_arg, skip_packet};
+#[cfg(feature = "tokio")]
+pub use self::read_async::{from_read_async, from_read_async_with_config, ReadPacket};
 
 /// Deserialize an OSC packet from some readable device.
-pub fn from_read<'de, D, R>(mut rd: R) -> ResultE<D>
+///
+/// With `Framing::Framed`, exactly one length-prefixed packet is consumed, so
+/// this may be called repeatedly to read consecutive packets from a stream.
+/// With `Framing::Unframed`, the packet is taken to be everything up to EOF.
+/// With `Framing::Slip`, reading stops after the packet's closing `END` byte.
+pub fn from_read<'de, D, R>(mut rd: R, framing: Framing) -> ResultE<D>
     where R: Read, D: serde::de::Deserialize<'de>
 {
-    let mut de = Deserializer::new(&mut rd);
+    #[cfg(feature = "tracing")]
+    let _span = tracing::trace_span!("from_read", ?framing).entered();
+    let mut de = Deserializer::with_framing(&mut rd, framing);
     D::deserialize(&mut de)
 }
 
+/// Deserialize an OSC packet from some readable device, enforcing the limits in `config`.
+/// Packets that exceed them are rejected with `OscError::BadFormat`.
+pub fn from_read_with_config<'de, D, R>(mut rd: R, framing: Framing, config: &OscConfig) -> ResultE<D>
+    where R: Read, D: serde::de::Deserialize<'de>
+{
+    #[cfg(feature = "tracing")]
+    let _span = tracing::trace_span!("from_read", ?framing).entered();
+    let mut de = Deserializer::with_config(&mut rd, framing, config);
+    D::deserialize(&mut de)
+}
+
+/// Like `from_read`, but fails with `OscError::BadFormat` if there's any data
+/// beyond what the packet describes: either after a message's last argument,
+/// or left in `rd` after the packet (e.g. when it should hold exactly one UDP datagram).
+pub fn from_read_exact<D, R>(rd: R, framing: Framing) -> ResultE<D>
+    where R: Read, D: serde::de::DeserializeOwned
+{
+    let config = OscConfig { reject_trailing_data: true, ..UNLIMITED };
+    // Used just to count the bytes consumed, for the error offset.
+    let mut counted = PktReader::new(rd, 0, u64::MAX);
+    let value = from_read_with_config(&mut counted, framing, &config)?;
+    let mut byte = [0];
+    match counted.read(&mut byte)? {
+        0 => Ok(value),
+        _ => Err(OscError::BadFormat{ offset: counted.position() - 1, context: "unconsumed data after packet" }),
+    }
+}
+
 
 /// Deserialize an OSC packet from a `&[u8]` type.
 /// This is a wrapper around the `from_read` function.
 /// Pairs nicely with ser::to_vec, as Vec<u8> is coercable to &[u8].
-pub fn from_slice<'de, T>(slice: &[u8]) -> ResultE<T>
+pub fn from_slice<'de, T>(slice: &[u8], framing: Framing) -> ResultE<T>
     where T: serde::de::Deserialize<'de>
 {
-    from_read(Cursor::new(slice))
+    from_read(Cursor::new(slice), framing)
 }
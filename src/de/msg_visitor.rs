@@ -1,16 +1,19 @@
-use std::io::{Read, Take};
+use std::io::Read;
 use std::mem;
 use serde::de::{DeserializeSeed, SeqAccess};
 
-use error::{Error, ResultE};
+use config::OscConfig;
+use error::{OscError, ResultE};
+use super::pkt_reader::PktReader;
 use super::arg_visitor::ArgDeserializer;
 use super::osc_type::OscType;
 
 /// Deserializes a single message, within a packet.
 #[derive(Debug)]
 pub struct MsgVisitor<'a, R: Read + 'a> {
-    read: &'a mut Take<R>,
+    read: &'a mut PktReader<R>,
     state: State,
+    config: &'a OscConfig,
 }
 
 /// Which part of the OSC message is being parsed
@@ -27,10 +30,11 @@ enum State {
 impl<'a, R> MsgVisitor<'a, R>
     where R: Read + 'a
 {
-    pub fn new(read: &'a mut Take<R>, address: String) -> Self {
+    pub fn new(read: &'a mut PktReader<R>, address: String, config: &'a OscConfig) -> Self {
         Self {
             read: read,
             state: State::Address(address),
+            config,
         }
     }
 }
@@ -40,7 +44,7 @@ impl<'a, R> MsgVisitor<'a, R>
 impl<'de, 'a, R> SeqAccess<'de> for MsgVisitor<'a, R>
     where R: Read + 'a
 {
-    type Error = Error;
+    type Error = OscError;
     fn next_element_seed<T>(&mut self, seed: T) -> ResultE<Option<T::Value>>
         where T: DeserializeSeed<'de>
     {
@@ -51,7 +55,7 @@ impl<'de, 'a, R> SeqAccess<'de> for MsgVisitor<'a, R>
             },
             // parsed the address; now parse the args
             State::Typestring => {
-                (State::Done, seed.deserialize(&mut ArgDeserializer::new(self.read)?).map(Some))
+                (State::Done, seed.deserialize(&mut ArgDeserializer::new(self.read, self.config)?).map(Some))
             },
             // parsed the address and the args; nothing left to do
             State::Done => {
@@ -1,44 +1,91 @@
+use std::cmp;
 use std::convert::TryInto;
-use std::io::Read;
+use std::io;
+use std::io::{ErrorKind, Read};
 use byteorder::{BigEndian, ReadBytesExt};
 
-use error::{Error, ResultE};
+use config::OscConfig;
+use error::{OscError, ResultE};
 
-/// auto-implemented trait to parse OSC data from a Read object.
+/// Most space reserved for a blob's data before any of it has been read.
+const BLOB_PREALLOC: usize = 64 * 1024;
+
+/// Trait to parse OSC data from a Read object.
 pub trait OscReader: Read {
-    /// Read a null-terminated sequence of bytes & verify padding
-    fn read_0term_bytes(&mut self) -> ResultE<Vec<u8>> {
+    /// Offset of the next byte to be read, for use in error messages.
+    fn position(&self) -> usize;
+    /// Number of bytes left in the packet (or an upper bound, if not known).
+    fn remaining(&self) -> u64;
+    /// Read a null-terminated sequence of bytes & verify padding (if `config` requires).
+    /// Fails if there are more than `config.max_string_len` bytes before the terminator.
+    fn read_0term_bytes(&mut self, config: &OscConfig) -> ResultE<Vec<u8>> {
         let mut data = Vec::new();
         // Because of the 4-byte required padding, we can process 4 characters at a time
         let mut buf: [u8; 4] = [0, 0, 0, 0];
-        let mut num_zeros = 0;
-        while num_zeros == 0 {
+        loop {
             self.read_exact(&mut buf)?;
             // Copy the NON-NULL characters to the buffer.
-            num_zeros = buf.iter().filter(|c| **c == 0).count();
-            if buf[4-num_zeros..4].iter().any(|c| *c != 0) {
-                // We had data after the null terminator.
-                return Err(Error::BadPadding);
+            let len = buf.iter().position(|c| *c == 0).unwrap_or(4);
+            data.extend_from_slice(&buf[..len]);
+            if data.len() > config.max_string_len {
+                return Err(OscError::BadFormat{ offset: self.position() - 4, context: "string too long" });
+            }
+            if len < 4 {
+                if let Some(idx) = buf[len..].iter().position(|c| *c != 0) {
+                    if config.strict_padding {
+                        // We had data after the null terminator.
+                        return Err(OscError::BadPadding{ offset: self.position() - 4 + len + idx });
+                    }
+                }
+                return Ok(data);
             }
-            data.extend_from_slice(&buf[0..4-num_zeros]);
         }
-        Ok(data)
     }
     /// Read a null-terminated UTF-8 string & verify padding
-    fn parse_str(&mut self) -> ResultE<String> {
+    fn parse_str(&mut self, config: &OscConfig) -> ResultE<String> {
         // Note: although OSC specifies ascii only, we may have data >= 128 in the vector.
         // We can safely assume a UTF-8 encoding, because no byte of any multibyte UTF-8
         // contains a zero; the only zero possible in a UTF-8 string is the ASCII zero.
         // See the UTF-8 table here: https://en.wikipedia.org/wiki/UTF-8#History
-        let bytes = self.read_0term_bytes()?;
+        let bytes = self.read_0term_bytes(config)?;
         Ok(String::from_utf8(bytes)?)
     }
     fn parse_i32(&mut self) -> ResultE<i32> {
        Ok( self.read_i32::<BigEndian>()?)
     }
+    fn parse_u32(&mut self) -> ResultE<u32> {
+        Ok(self.read_u32::<BigEndian>()?)
+    }
+    fn parse_i64(&mut self) -> ResultE<i64> {
+        Ok(self.read_i64::<BigEndian>()?)
+    }
     fn parse_f32(&mut self) -> ResultE<f32> {
         Ok(self.read_f32::<BigEndian>()?)
     }
+    fn parse_f64(&mut self) -> ResultE<f64> {
+        Ok(self.read_f64::<BigEndian>()?)
+    }
+    /// Read an ASCII character, stored in the low byte of a 32-bit word.
+    fn parse_char(&mut self) -> ResultE<char> {
+        let value = self.read_u32::<BigEndian>()?;
+        if value > 0x7f {
+            // Not an ASCII character
+            return Err(OscError::BadFormat{ offset: self.position() - 4, context: "non-ASCII character" });
+        }
+        Ok(value as u8 as char)
+    }
+    /// Read a 32-bit RGBA color.
+    fn parse_color(&mut self) -> ResultE<[u8; 4]> {
+        let mut rgba = [0; 4];
+        self.read_exact(&mut rgba)?;
+        Ok(rgba)
+    }
+    /// Read a 4-byte MIDI message.
+    fn parse_midi(&mut self) -> ResultE<[u8; 4]> {
+        let mut msg = [0; 4];
+        self.read_exact(&mut msg)?;
+        Ok(msg)
+    }
     /// "Time tags are represented by a 64 bit fixed point number.
     ///  The first 32 bits specify the number of seconds since midnight on January 1, 1900,
     ///  and the last 32 bits specify fractional parts of a second to a precision of about 200 picoseconds.
@@ -48,24 +95,35 @@ pub trait OscReader: Read {
        let frac = self.read_u32::<BigEndian>()?;
        Ok((sec, frac))
     }
-    /// Read an OSC blob & verify padding.
+    /// Read an OSC blob & verify padding (if `config` requires).
     /// A blob consists of a length + u8 array.
-    fn parse_blob(&mut self) -> ResultE<Vec<u8>> {
+    /// Fails without allocating if the length exceeds `config.max_blob_size`,
+    /// or the number of bytes left in the packet.
+    fn parse_blob(&mut self, config: &OscConfig) -> ResultE<Vec<u8>> {
         let size: usize = self.parse_i32()?.try_into()?;
+        if size > config.max_blob_size {
+            return Err(OscError::BadFormat{ offset: self.position() - 4, context: "blob too long" });
+        }
+        if size as u64 > self.remaining() {
+            return Err(OscError::BadFormat{ offset: self.position() - 4, context: "blob longer than packet" });
+        }
         // Blobs are padded to a 4-byte boundary
         let padded_size = (size + 3) & !0x3;
-        // Read EXACTLY this much data:
-        let mut data = vec![0; padded_size];
-        self.read_exact(&mut data)?;
+        // Read EXACTLY this much data. The packet's length may itself be
+        // unknown or untrusted, so the buffer only grows as the data arrives.
+        let mut data = Vec::with_capacity(cmp::min(padded_size, BLOB_PREALLOC));
+        Read::take(&mut *self, padded_size as u64).read_to_end(&mut data)?;
+        if data.len() < padded_size {
+            return Err(io::Error::from(ErrorKind::UnexpectedEof).into());
+        }
         // Ensure these extra bytes where NULL (sanity check)
-        if data.drain(size..padded_size).all(|c| c == 0) {
-            Ok(data)
-        } else {
-            Err(Error::BadPadding)
+        if let Some(idx) = data[size..].iter().position(|c| *c != 0) {
+            if config.strict_padding {
+                return Err(OscError::BadPadding{ offset: self.position() - padded_size + size + idx });
+            }
         }
+        data.truncate(size);
+        Ok(data)
     }
 }
 
-/// Provide OSC reading functions to all types implementing Read
-impl<R: Read + ?Sized> OscReader for R {}
-
@@ -1,5 +1,11 @@
-use serde::de::{Deserializer, Visitor};
-use error::{Error, ResultE};
+use serde::de;
+use serde::de::{Deserializer, DeserializeSeed, EnumAccess, IntoDeserializer, Unexpected,
+    VariantAccess, Visitor};
+use serde::de::value::StrDeserializer;
+use error::{OscError, ResultE};
+use types::{OscArg, OscColor, OscMidi, ARG_NAME, COLOR_NAME, IMPULSE_NAME, MIDI_NAME, SYMBOL_NAME};
+use super::iter_visitor::IterVisitor;
+use super::prim_deserializer::PrimDeserializer;
 
 /// Struct to deserialize a single element from the OSC message sequence.
 /// (e.g. just the address, or the first argument, etc).
@@ -9,11 +15,72 @@ pub enum OscType {
     F32(f32),
     String(String),
     Blob(Vec<u8>),
+    U32(u32),
+    I64(i64),
+    F64(f64),
+    Char(char),
+    Color(OscColor),
+    Midi(OscMidi),
+    Bool(bool),
+    Nil,
+    Impulse,
+    Symbol(String),
+    TimeTag((u32, u32)),
+    Array(Vec<OscType>),
+}
+
+impl OscType {
+    /// Describe this argument for the purpose of a type mismatch error.
+    fn unexpected(&self) -> Unexpected<'_> {
+        match *self {
+            OscType::I32(i) => Unexpected::Signed(i.into()),
+            OscType::F32(f) => Unexpected::Float(f.into()),
+            OscType::String(ref s) => Unexpected::Str(s),
+            OscType::Blob(ref b) => Unexpected::Bytes(b),
+            OscType::U32(u) => Unexpected::Unsigned(u.into()),
+            OscType::I64(h) => Unexpected::Signed(h),
+            OscType::F64(d) => Unexpected::Float(d),
+            OscType::Char(c) => Unexpected::Char(c),
+            OscType::Color(_) => Unexpected::Other("OSC color"),
+            OscType::Midi(_) => Unexpected::Other("OSC MIDI message"),
+            OscType::Bool(b) => Unexpected::Bool(b),
+            OscType::Nil => Unexpected::Option,
+            OscType::Impulse => Unexpected::Other("OSC impulse"),
+            OscType::Array(_) => Unexpected::Seq,
+            OscType::Symbol(_) => Unexpected::Other("OSC symbol"),
+            OscType::TimeTag(_) => Unexpected::Other("OSC timetag"),
+        }
+    }
+    /// The `OscArg` that this would deserialize into.
+    pub fn into_arg(self) -> OscArg {
+        match self {
+            OscType::I32(i) => OscArg::I32(i),
+            OscType::F32(f) => OscArg::F32(f),
+            OscType::String(s) => OscArg::String(s),
+            OscType::Blob(b) => OscArg::Blob(b),
+            OscType::U32(u) => OscArg::U32(u),
+            OscType::I64(h) => OscArg::I64(h),
+            OscType::F64(d) => OscArg::F64(d),
+            OscType::Char(c) => OscArg::Char(c),
+            OscType::Color(r) => OscArg::Color(r),
+            OscType::Midi(m) => OscArg::Midi(m),
+            OscType::Bool(b) => OscArg::Bool(b),
+            OscType::Nil => OscArg::Nil,
+            OscType::Impulse => OscArg::Impulse,
+            OscType::Symbol(s) => OscArg::Symbol(s),
+            OscType::TimeTag(t) => OscArg::TimeTag(t.into()),
+            OscType::Array(a) => OscArg::Array(a.into_iter().map(OscType::into_arg).collect()),
+        }
+    }
 }
 
 
 impl<'de> Deserializer<'de> for OscType {
-    type Error = Error;
+    type Error = OscError;
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
     // deserializes a single item from the message, consuming self.
     fn deserialize_any<V>(self, visitor: V) -> ResultE<V::Value>
     where
@@ -27,16 +94,174 @@ impl<'de> Deserializer<'de> for OscType {
             //   will error! We should make use of the deserialize_seq function
             //   in this case.
             OscType::Blob(b) => visitor.visit_byte_buf(b),
+            OscType::U32(u) => visitor.visit_u32(u),
+            OscType::I64(h) => visitor.visit_i64(h),
+            OscType::F64(d) => visitor.visit_f64(d),
+            OscType::Char(c) => visitor.visit_char(c),
+            OscType::Bool(b) => visitor.visit_bool(b),
+            OscType::Nil => visitor.visit_none(),
+            OscType::Impulse => visitor.visit_unit(),
+            OscType::Array(elements) => visitor.visit_seq(IterVisitor(elements.into_iter())),
+            // A symbol is read as the string it holds. Only `OscArg` (see
+            // `deserialize_newtype_struct`) and `OscSymbol` tell the two apart.
+            OscType::Symbol(s) => visitor.visit_string(s),
+            // These would be indistinguishable from other types if visited
+            // by their contents, so they're presented as an enum instead,
+            // with the variant named after the type tag.
+            // Type hints (see below) still allow them to be read by their contents.
+            arg @ OscType::Color(_) |
+            arg @ OscType::Midi(_) |
+            arg @ OscType::TimeTag(_) => visitor.visit_enum(arg),
+        }
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> ResultE<V::Value>
+        where V: Visitor<'de>
+    {
+        match self {
+            OscType::Color(OscColor(bytes)) | OscType::Midi(OscMidi(bytes)) =>
+                visitor.visit_seq(IterVisitor(bytes.iter().cloned()
+                    .map(PrimDeserializer))),
+            OscType::TimeTag((sec, frac)) =>
+                visitor.visit_seq(IterVisitor([sec, frac].iter().cloned()
+                    .map(PrimDeserializer))),
+            other => other.deserialize_any(visitor),
+        }
+    }
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> ResultE<V::Value>
+        where V: Visitor<'de>
+    {
+        self.deserialize_seq(visitor)
+    }
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V
+    ) -> ResultE<V::Value>
+        where V: Visitor<'de>
+    {
+        self.deserialize_seq(visitor)
+    }
+    fn deserialize_string<V>(self, visitor: V) -> ResultE<V::Value>
+        where V: Visitor<'de>
+    {
+        match self {
+            OscType::Symbol(s) => visitor.visit_string(s),
+            other => other.deserialize_any(visitor),
+        }
+    }
+    fn deserialize_str<V>(self, visitor: V) -> ResultE<V::Value>
+        where V: Visitor<'de>
+    {
+        self.deserialize_string(visitor)
+    }
+
+    // Nil may be used as an absent Option, or as ().
+    fn deserialize_option<V>(self, visitor: V) -> ResultE<V::Value>
+        where V: Visitor<'de>
+    {
+        match self {
+            OscType::Nil => visitor.visit_none(),
+            other => visitor.visit_some(other),
+        }
+    }
+    fn deserialize_unit<V>(self, visitor: V) -> ResultE<V::Value>
+        where V: Visitor<'de>
+    {
+        match self {
+            OscType::Nil => visitor.visit_unit(),
+            other => other.deserialize_any(visitor),
+        }
+    }
+
+    // OSC types that have no serde equivalent are represented by newtypes.
+    // These must only be deserialized from an argument with the matching type tag.
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V
+    ) -> ResultE<V::Value>
+        where V: Visitor<'de>
+    {
+        match (name, self) {
+            (COLOR_NAME, arg @ OscType::Color(_)) |
+            (MIDI_NAME, arg @ OscType::Midi(_)) |
+            (SYMBOL_NAME, arg @ OscType::Symbol(_)) => visitor.visit_newtype_struct(arg),
+            (COLOR_NAME, other) |
+            (MIDI_NAME, other) |
+            (SYMBOL_NAME, other) => Err(de::Error::invalid_type(other.unexpected(), &visitor)),
+            // `OscArg` needs to keep symbols apart from strings.
+            (ARG_NAME, arg @ OscType::Symbol(_)) => visitor.visit_enum(arg),
+            (_, other) => other.deserialize_any(visitor),
+        }
+    }
+
+    // Likewise for data-less types, which are represented by unit structs.
+    fn deserialize_unit_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V
+    ) -> ResultE<V::Value>
+        where V: Visitor<'de>
+    {
+        match (name, self) {
+            (IMPULSE_NAME, OscType::Impulse) => visitor.visit_unit(),
+            (IMPULSE_NAME, other) => Err(de::Error::invalid_type(other.unexpected(), &visitor)),
+            (_, other) => other.deserialize_any(visitor),
         }
     }
 
-    // OSC messages are strongly typed, so we don't make use of any type hints.
+    // Otherwise, OSC messages are strongly typed, so we don't make use of any type hints.
     // More info: https://github.com/serde-rs/serde/blob/b7d6c5d9f7b3085a4d40a446eeb95976d2337e07/serde/src/macros.rs#L106
     forward_to_deserialize_any! {
-        bool u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 char str string unit option
-        seq bytes byte_buf map unit_struct newtype_struct
-        tuple_struct struct identifier tuple enum ignored_any
+        bool u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 char
+        bytes byte_buf map struct identifier enum ignored_any
     }
 }
 
+impl<'de> EnumAccess<'de> for OscType {
+    type Error = OscError;
+    type Variant = Self;
+    fn variant_seed<V>(self, seed: V) -> ResultE<(V::Value, Self)>
+        where V: DeserializeSeed<'de>
+    {
+        let tag = match self {
+            OscType::Color(_) => "r",
+            OscType::Midi(_) => "m",
+            OscType::Symbol(_) => "S",
+            OscType::TimeTag(_) => "t",
+            _ => return Err(OscError::UnsupportedValue),
+        };
+        let tag: StrDeserializer<OscError> = tag.into_deserializer();
+        let variant = seed.deserialize(tag)?;
+        Ok((variant, self))
+    }
+}
 
+/// Each variant holds the argument's contents, as a newtype.
+impl<'de> VariantAccess<'de> for OscType {
+    type Error = OscError;
+    fn newtype_variant_seed<T>(self, seed: T) -> ResultE<T::Value>
+        where T: DeserializeSeed<'de>
+    {
+        seed.deserialize(self)
+    }
+    fn unit_variant(self) -> ResultE<()> {
+        Err(OscError::UnsupportedValue)
+    }
+    fn tuple_variant<V>(self, _len: usize, _visitor: V) -> ResultE<V::Value>
+        where V: Visitor<'de>
+    {
+        Err(OscError::UnsupportedValue)
+    }
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        _visitor: V
+    ) -> ResultE<V::Value>
+        where V: Visitor<'de>
+    {
+        Err(OscError::UnsupportedValue)
+    }
+}
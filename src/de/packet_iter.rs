@@ -0,0 +1,95 @@
+use std::io::{Cursor, ErrorKind, Read};
+use std::marker::PhantomData;
+use serde::de::DeserializeOwned;
+
+use error::ResultE;
+use framing::Framing;
+
+/// Deserialize consecutive OSC packets from some readable device, until EOF.
+///
+/// See [`PacketIter`] for details.
+///
+/// [`PacketIter`]: struct.PacketIter.html
+pub fn packets_from_read<T, R>(reader: R, framing: Framing) -> PacketIter<T, R>
+    where R: Read, T: DeserializeOwned
+{
+    PacketIter {
+        reader,
+        framing,
+        done: false,
+        _packet: PhantomData,
+    }
+}
+
+/// Deserialize every packet from some readable device, until EOF.
+///
+/// Reaching EOF between two packets is the normal end of the stream, whereas
+/// reaching it in the middle of a packet is an error. Fails with the first
+/// error encountered.
+pub fn from_read_all<T, R>(reader: R, framing: Framing) -> ResultE<Vec<T>>
+    where R: Read, T: DeserializeOwned
+{
+    packets_from_read(reader, framing).collect()
+}
+
+/// Alias for [`from_read_all`].
+///
+/// [`from_read_all`]: fn.from_read_all.html
+pub fn packets_until_eof<T, R>(reader: R, framing: Framing) -> ResultE<Vec<T>>
+    where R: Read, T: DeserializeOwned
+{
+    from_read_all(reader, framing)
+}
+
+/// Iterator over the packets within a stream, returned by [`packets_from_read`].
+///
+/// Reaching EOF between two packets ends the iteration, whereas reaching
+/// EOF in the middle of a packet yields an error.
+/// Iteration also ends after the first error, as the position within the
+/// stream is unknown at that point.
+///
+/// [`packets_from_read`]: fn.packets_from_read.html
+#[derive(Debug)]
+pub struct PacketIter<T, R> {
+    reader: R,
+    framing: Framing,
+    done: bool,
+    _packet: PhantomData<fn() -> T>,
+}
+
+impl<T, R> PacketIter<T, R> {
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<T, R> Iterator for PacketIter<T, R>
+    where R: Read, T: DeserializeOwned
+{
+    type Item = ResultE<T>;
+    fn next(&mut self) -> Option<ResultE<T>> {
+        if self.done {
+            return None;
+        }
+        // Read the first byte on its own, to distinguish the end of the stream
+        // from a truncated packet.
+        let mut first = [0];
+        loop {
+            match self.reader.read(&mut first) {
+                Ok(0) => {
+                    self.done = true;
+                    return None;
+                },
+                Ok(_) => break,
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => {},
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e.into()));
+                },
+            }
+        }
+        let packet = super::from_read(Cursor::new(first).chain(&mut self.reader), self.framing);
+        self.done = packet.is_err();
+        Some(packet)
+    }
+}
@@ -0,0 +1,161 @@
+use std::cmp;
+use std::convert::TryFrom;
+use std::io;
+use std::io::{BufRead, Read};
+use std::str;
+use byteorder::{BigEndian, ReadBytesExt};
+
+use config::OscConfig;
+use error::{OscError, ResultE};
+use framing::Framing;
+use framing::slip;
+
+/// Wraps a stream of packets, so that the address of the next packet
+/// can be inspected before choosing which type to deserialize it into.
+///
+/// Peeking reads the whole of the next packet, and holds onto the bytes read.
+/// Reading from the `PeekablePacketReader` (e.g. by passing it to [`from_read`]
+/// with the same framing) then replays those bytes before continuing
+/// with the rest of the stream.
+///
+/// Peeked packets are limited to the `max_packet_size` of an [`OscConfig`]:
+/// `OscConfig::default()` unless another is given via `with_config`.
+///
+/// ```
+/// # use serde_osc::{to_vec, from_read, Framing};
+/// # use serde_osc::de::PeekablePacketReader;
+/// let packet = to_vec(&("/volume".to_owned(), (0.5f32,)), Framing::Framed).unwrap();
+/// let mut reader = PeekablePacketReader::new(&packet[..], Framing::Framed);
+/// assert_eq!(reader.peek_address().unwrap(), "/volume");
+/// let (_, (volume,)): (String, (f32,)) = from_read(&mut reader, Framing::Framed).unwrap();
+/// assert_eq!(volume, 0.5);
+/// ```
+///
+/// [`from_read`]: fn.from_read.html
+/// [`OscConfig`]: ../config/struct.OscConfig.html
+#[derive(Debug)]
+pub struct PeekablePacketReader<R> {
+    inner: R,
+    framing: Framing,
+    config: OscConfig,
+    /// Bytes read from `inner` while peeking, exactly as they were read.
+    replay: Vec<u8>,
+    /// How much of `replay` has since been read back out.
+    replay_pos: usize,
+    /// The peeked packet, with any framing removed.
+    packet: Option<Vec<u8>>,
+}
+
+/// Copies everything read through it into `record`.
+struct Recorder<'a, R: 'a> {
+    inner: &'a mut R,
+    record: &'a mut Vec<u8>,
+}
+
+impl<'a, R: Read> Read for Recorder<'a, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.inner.read(buf)?;
+        self.record.extend_from_slice(&buf[..len]);
+        Ok(len)
+    }
+}
+
+impl<R: BufRead> PeekablePacketReader<R> {
+    pub fn new(inner: R, framing: Framing) -> Self {
+        Self::with_config(inner, framing, OscConfig::default())
+    }
+    /// Peek packets no longer than `config` allows, rather than the default limit.
+    pub fn with_config(inner: R, framing: Framing, config: OscConfig) -> Self {
+        Self {
+            inner,
+            framing,
+            config,
+            replay: Vec::new(),
+            replay_pos: 0,
+            packet: None,
+        }
+    }
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+    /// The address of the next message, or "#bundle" if the next packet is a bundle.
+    pub fn peek_address(&mut self) -> ResultE<&str> {
+        let packet = self.peek_packet()?;
+        let len = packet.iter().position(|&b| b == 0)
+            .ok_or(OscError::BadFormat{ offset: packet.len(), context: "unterminated address" })?;
+        str::from_utf8(&packet[..len])
+            .map_err(|_| OscError::BadFormat{ offset: 0, context: "address is not valid UTF-8" })
+    }
+    /// Whether the next packet is a bundle, rather than a message.
+    ///
+    /// Fails if the next packet can't be read, like `peek_address`.
+    pub fn peek_is_bundle(&mut self) -> ResultE<bool> {
+        Ok(self.peek_packet()?.starts_with(b"#bundle\0"))
+    }
+    /// Read the next packet into the replay buffer, unless that's already been done.
+    fn peek_packet(&mut self) -> ResultE<&[u8]> {
+        if self.packet.is_none() {
+            let max_len = self.config.max_packet_size;
+            let packet = {
+                let mut recorder = Recorder{ inner: &mut self.inner, record: &mut self.replay };
+                match self.framing {
+                    Framing::Framed => {
+                        let len = usize::try_from(recorder.read_i32::<BigEndian>()?)?;
+                        if len > max_len {
+                            return Err(OscError::BadFormat{ offset: 0, context: "packet too long" });
+                        }
+                        let mut packet = vec![0; len];
+                        recorder.read_exact(&mut packet)?;
+                        packet
+                    },
+                    Framing::Unframed => {
+                        let mut packet = Vec::new();
+                        recorder.by_ref().take(max_len as u64 + 1).read_to_end(&mut packet)?;
+                        if packet.len() > max_len {
+                            return Err(OscError::BadFormat{ offset: max_len, context: "packet too long" });
+                        }
+                        packet
+                    },
+                    Framing::Slip => slip::read_frame(&mut recorder, max_len)?,
+                }
+            };
+            self.packet = Some(packet);
+        }
+        Ok(self.packet.as_ref().unwrap())
+    }
+}
+
+impl<R: BufRead> Read for PeekablePacketReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = {
+            let mut available = self.fill_buf()?;
+            available.read(buf)?
+        };
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+impl<R: BufRead> BufRead for PeekablePacketReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.replay_pos < self.replay.len() {
+            Ok(&self.replay[self.replay_pos..])
+        } else {
+            self.inner.fill_buf()
+        }
+    }
+    fn consume(&mut self, amt: usize) {
+        if self.replay_pos < self.replay.len() {
+            self.replay_pos = cmp::min(self.replay_pos + amt, self.replay.len());
+            if self.replay_pos == self.replay.len() {
+                // The peeked packet has been read back out; the next peek
+                // reads a new one.
+                self.replay.clear();
+                self.replay_pos = 0;
+                self.packet = None;
+            }
+        } else {
+            self.inner.consume(amt);
+        }
+    }
+}
@@ -1,15 +1,19 @@
-use std::io::Read;
+use std::io;
+use std::io::{Cursor, ErrorKind, Read};
 use byteorder::{BigEndian, ReadBytesExt};
 use serde::de;
 use serde::de::Visitor;
 
-use error::{Error, ResultE};
+use config::{OscConfig, MAX_NESTING, UNLIMITED};
+use error::{OscError, ResultE};
+use framing::{slip, Framing};
 use super::osc_reader::OscReader;
+use super::pkt_reader::PktReader;
 use super::msg_visitor::MsgVisitor;
 use super::bundle_visitor::BundleVisitor;
 
 /// Deserializes an entire OSC packet or bundle element (they are syntactically identical).
-/// An OSC packet consists of an `i32` indicating its length, followed by
+/// An OSC packet consists of an `i32` indicating its length (unless unframed), followed by
 /// the packet contents: EITHER a message OR a bundle.
 ///
 /// This is designed to be symmetric with the [`serde_osc::ser::Serializer`] behavior,
@@ -25,41 +29,72 @@ use super::bundle_visitor::BundleVisitor;
 #[derive(Debug)]
 pub struct PktDeserializer<'a, R: Read + 'a> {
     reader: &'a mut R,
+    framing: Framing,
+    config: &'a OscConfig,
+    /// How many bundles enclose this packet.
+    depth: usize,
+    /// Offset of this packet within the outermost packet, for error reporting.
+    start: usize,
 }
 
 impl<'a, R> PktDeserializer<'a, R>
     where R: Read + 'a
 {
+    /// Create a deserializer that expects the packet to be prefixed by its length.
     pub fn new(reader: &'a mut R) -> Self {
-        Self{ reader }
+        Self::with_framing(reader, Framing::Framed)
+    }
+    pub fn with_framing(reader: &'a mut R, framing: Framing) -> Self {
+        Self::with_config(reader, framing, &UNLIMITED)
+    }
+    pub fn with_config(reader: &'a mut R, framing: Framing, config: &'a OscConfig) -> Self {
+        Self{ reader, framing, config, depth: 0, start: 0 }
+    }
+    /// Create a deserializer for an element within a bundle, which begins at offset `start`.
+    pub(crate) fn bundle_element(reader: &'a mut R, config: &'a OscConfig, depth: usize, start: usize) -> Self {
+        Self{ reader, framing: Framing::Framed, config, depth, start }
     }
 }
 
 impl<'de, 'a, R> de::Deserializer<'de> for &'a mut PktDeserializer<'a, R>
     where R: Read + 'a
 {
-    type Error = Error;
+    type Error = OscError;
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all, fields(framing = ?self.framing, depth = self.depth)))]
     fn deserialize_any<V>(self, visitor: V) -> ResultE<V::Value>
         where V: Visitor<'de>
     {
-        // First, extract the length of the packet.
-        let length = self.reader.read_i32::<BigEndian>()?;
-        let mut reader = self.reader.take(length as u64);
-        // See if packet is a bundle or a message.
-        let address = reader.parse_str()?;
-        let result = match address.as_str() {
-            "#bundle" => visitor.visit_seq(BundleVisitor::new(&mut reader)),
-            _ => visitor.visit_seq(MsgVisitor::new(&mut reader, address)),
-        };
-        // If the consumer only handled a portion of the sequence, we still
-        // need to advance the reader so as to be ready for any next message.
-        // TODO: it should be possible to read any extra chars w/o allocating.
-        // Tracking: https://github.com/rust-lang/rust/issues/13989
-        let size = reader.limit() as usize;
-        let mut extra_chars = Vec::with_capacity(size);
-        extra_chars.resize(size, Default::default());
-        reader.read_exact(&mut extra_chars)?;
-        result
+        let max_size = self.config.max_packet_size;
+        match self.framing {
+            Framing::Framed => {
+                // First, extract the length of the packet.
+                let length = self.reader.read_i32::<BigEndian>()?;
+                if length < 0 || length as u64 > max_size as u64 {
+                    return Err(OscError::BadFormat{ offset: self.start, context: "invalid packet length" });
+                }
+                let reader = PktReader::new(&mut *self.reader, self.start + 4, length as u64);
+                visit_packet(reader, visitor, self.config, self.depth)
+            },
+            Framing::Unframed => {
+                // The packet is everything that remains.
+                let mut packet = Vec::new();
+                self.reader.take((max_size as u64).saturating_add(1)).read_to_end(&mut packet)?;
+                if packet.len() > max_size {
+                    return Err(OscError::BadFormat{ offset: max_size, context: "packet too long" });
+                }
+                let length = packet.len() as u64;
+                visit_packet(PktReader::new(Cursor::new(packet), 0, length), visitor, self.config, self.depth)
+            },
+            Framing::Slip => {
+                let packet = slip::read_frame(self.reader, max_size)?;
+                let length = packet.len() as u64;
+                visit_packet(PktReader::new(Cursor::new(packet), 0, length), visitor, self.config, self.depth)
+            },
+        }
     }
 
     // This struct only deserializes sequences; ignore all type hints.
@@ -70,3 +105,31 @@ impl<'de, 'a, R> de::Deserializer<'de> for &'a mut PktDeserializer<'a, R>
         tuple_struct struct identifier tuple enum ignored_any
     }
 }
+
+/// Deserialize the contents of a packet, once its length is known.
+fn visit_packet<'de, R, V>(mut reader: PktReader<R>, visitor: V, config: &OscConfig, depth: usize) -> ResultE<V::Value>
+    where R: Read, V: Visitor<'de>
+{
+    if reader.limit() == 0 {
+        return Err(OscError::BadFormat{ offset: reader.position(), context: "empty packet" });
+    }
+    #[cfg(feature = "tracing")]
+    let packet_size_bytes = reader.limit();
+    // See if packet is a bundle or a message.
+    let address = reader.parse_str(config)?;
+    #[cfg(feature = "tracing")]
+    tracing::trace!(packet_size_bytes, is_bundle = address == "#bundle", "reading packet");
+    let result = match address.as_str() {
+        "#bundle" if depth >= config.max_bundle_depth || depth >= MAX_NESTING =>
+            Err(OscError::BadFormat{ offset: reader.position(), context: "bundles nested too deeply" }),
+        "#bundle" => visitor.visit_seq(BundleVisitor::new(&mut reader, config, depth + 1)),
+        _ => visitor.visit_seq(MsgVisitor::new(&mut reader, address, config)),
+    };
+    // If the consumer only handled a portion of the sequence, we still
+    // need to advance the reader so as to be ready for any next message.
+    let size = reader.limit();
+    if io::copy(&mut reader, &mut io::sink())? != size {
+        return Err(io::Error::from(ErrorKind::UnexpectedEof).into());
+    }
+    result
+}
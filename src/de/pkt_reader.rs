@@ -0,0 +1,51 @@
+use std::cmp;
+use std::io;
+use std::io::Read;
+
+use super::osc_reader::OscReader;
+
+/// Reads the contents of a single packet.
+/// Like `std::io::Take`, this won't read beyond the end of the packet,
+/// but it also tracks the offset of each byte, so that errors can report
+/// where they occurred.
+#[derive(Debug)]
+pub struct PktReader<R> {
+    inner: R,
+    /// Offset of the next byte, relative to the start of the outermost packet.
+    position: usize,
+    /// Number of bytes remaining in this packet.
+    limit: u64,
+}
+
+impl<R> PktReader<R> {
+    /// Read at most `limit` bytes from `inner`, the first of which is at `position`.
+    pub fn new(inner: R, position: usize, limit: u64) -> Self {
+        Self{ inner, position, limit }
+    }
+    /// Number of bytes remaining in this packet.
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+}
+
+impl<R: Read> OscReader for PktReader<R> {
+    fn position(&self) -> usize {
+        self.position
+    }
+    fn remaining(&self) -> u64 {
+        self.limit
+    }
+}
+
+impl<R: Read> Read for PktReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.limit == 0 {
+            return Ok(0);
+        }
+        let max = cmp::min(buf.len() as u64, self.limit) as usize;
+        let len = self.inner.read(&mut buf[..max])?;
+        self.position += len;
+        self.limit -= len as u64;
+        Ok(len)
+    }
+}
@@ -1,27 +1,48 @@
 use serde::de::{Deserializer, Visitor};
 
-use error::{Error, ResultE};
+use error::{OscError, ResultE};
 
 /// Serde gymnastics.
-/// Implements the Deserializer trait for primitive types.
-/// Currently only implemented for the types needed by osc_serde,
-/// but could easily be extended via a macro.
+/// Implements the Deserializer trait for primitive types,
+/// e.g. to present the parts of a timetag as a sequence of u32s.
 #[derive(Debug)]
 pub struct PrimDeserializer<T>(pub T);
 
-impl<'de> Deserializer<'de> for PrimDeserializer<u32> {
-    type Error = Error;
-    fn deserialize_any<V>(self, visitor: V) -> ResultE<V::Value>
-        where V: Visitor<'de>
-    {
-        visitor.visit_u32(self.0)
-    }
+/// Implement `Deserializer` for `PrimDeserializer<$prim>`,
+/// handing the value to the visitor via `$visit` whatever type is requested.
+macro_rules! impl_prim_deserializer {
+    ($($prim:ty => $visit:ident),*) => {
+        $(
+            impl<'de> Deserializer<'de> for PrimDeserializer<$prim> {
+                type Error = OscError;
+                fn is_human_readable(&self) -> bool {
+                    false
+                }
 
-    // Ignore type hints
-    // More info: https://github.com/serde-rs/serde/blob/b7d6c5d9f7b3085a4d40a446eeb95976d2337e07/serde/src/macros.rs#L106
-    forward_to_deserialize_any! {
-        bool u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 char str string unit option
-        seq bytes byte_buf map unit_struct newtype_struct
-        tuple_struct struct identifier tuple enum ignored_any
-    }
+                fn deserialize_any<V>(self, visitor: V) -> ResultE<V::Value>
+                    where V: Visitor<'de>
+                {
+                    visitor.$visit(self.0)
+                }
+
+                // Ignore type hints
+                // More info: https://github.com/serde-rs/serde/blob/b7d6c5d9f7b3085a4d40a446eeb95976d2337e07/serde/src/macros.rs#L106
+                forward_to_deserialize_any! {
+                    bool u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 char str string unit option
+                    seq bytes byte_buf map unit_struct newtype_struct
+                    tuple_struct struct identifier tuple enum ignored_any
+                }
+            }
+        )*
+    };
+}
+
+impl_prim_deserializer! {
+    u8 => visit_u8,
+    i32 => visit_i32,
+    u32 => visit_u32,
+    i64 => visit_i64,
+    u64 => visit_u64,
+    f32 => visit_f32,
+    f64 => visit_f64
 }
@@ -0,0 +1,62 @@
+use std::io::Read;
+
+use config::OscConfig;
+use error::{OscError, ResultE};
+use types::OscArg;
+use super::arg_visitor::ArgVisitor;
+use super::osc_reader::OscReader;
+use super::pkt_reader::PktReader;
+
+/// Read a message's type tag, then each argument it describes, from `reader`.
+/// The address must already have been read (see `read_address_and_args`).
+///
+/// This parses the arguments directly into `OscArg`s, without going through serde.
+/// Arguments are read with the default `OscConfig`, and error offsets are
+/// relative to the start of the type tag.
+pub fn read_all_args<R: Read>(reader: &mut R) -> ResultE<Vec<OscArg>> {
+    let config = OscConfig::default();
+    let mut read = PktReader::new(reader, 0, u64::MAX);
+    parse_args(&mut read, &config)
+}
+
+/// Read an unframed message's address, then its arguments, from `reader`.
+/// Error offsets are relative to the start of the address.
+pub fn read_address_and_args<R: Read>(reader: &mut R) -> ResultE<(String, Vec<OscArg>)> {
+    let config = OscConfig::default();
+    let mut read = PktReader::new(reader, 0, u64::MAX);
+    let address = read.parse_str(&config)?;
+    let args = parse_args(&mut read, &config)?;
+    Ok((address, args))
+}
+
+/// Read an unframed message's address and type tag (including the ','), then
+/// the remainder of `reader` as its encoded arguments, without parsing them.
+/// This is the inverse of `ser::write_message_raw`.
+///
+/// Fails with `OscError::BadFormat` if the packet is a bundle,
+/// or its type tag is missing or doesn't begin with ','.
+pub fn read_message_raw<R: Read>(reader: &mut R) -> ResultE<(String, String, Vec<u8>)> {
+    let config = OscConfig::default();
+    let mut read = PktReader::new(reader, 0, u64::MAX);
+    let address = read.parse_str(&config)?;
+    if address == "#bundle" {
+        return Err(OscError::BadFormat{ offset: 0, context: "expected a message, not a bundle" });
+    }
+    let position = read.position();
+    let type_tag = read.parse_str(&config)?;
+    if !type_tag.starts_with(',') {
+        return Err(OscError::BadFormat{ offset: position, context: "type tag must begin with ','" });
+    }
+    let mut data = Vec::new();
+    read.read_to_end(&mut data)?;
+    Ok((address, type_tag, data))
+}
+
+fn parse_args<R: Read>(read: &mut PktReader<R>, config: &OscConfig) -> ResultE<Vec<OscArg>> {
+    let mut visitor = ArgVisitor::new(read, config)?;
+    let mut args = Vec::new();
+    while let Some(arg) = visitor.parse_next()? {
+        args.push(arg.into_arg());
+    }
+    Ok(args)
+}
@@ -0,0 +1,160 @@
+use std::cmp;
+use std::convert::TryInto;
+use std::future::Future;
+use std::io;
+use std::io::{Cursor, ErrorKind};
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use byteorder::{BigEndian, ByteOrder};
+use serde::de::DeserializeOwned;
+use tokio::io::{AsyncRead, ReadBuf};
+
+use config::{OscConfig, UNLIMITED};
+use error::{OscError, ResultE};
+use framing::Framing;
+use framing::slip::FrameDecoder;
+
+/// How many more bytes to request at a time when the packet's length isn't known.
+/// When it is, at least this many, and at most as many as have been read so far,
+/// so that the space reserved is never much more than the data actually received.
+const READ_CHUNK: usize = 512;
+
+/// Deserialize an OSC packet from some asynchronously readable device.
+/// This is the async equivalent of [`from_read`], and consumes the same bytes.
+///
+/// Only the reading is asynchronous: once the whole packet has arrived,
+/// it's deserialized synchronously.
+///
+/// [`from_read`]: fn.from_read.html
+pub fn from_read_async<D, R>(reader: &mut R, framing: Framing) -> ReadPacket<'_, D, R>
+    where R: AsyncRead + Unpin + ?Sized, D: DeserializeOwned
+{
+    from_read_async_with_config(reader, framing, &UNLIMITED)
+}
+
+/// Like `from_read_async`, but enforcing the limits in `config`, as
+/// [`from_read_with_config`] does. The packet's size is checked as it
+/// arrives, before it's all been buffered.
+///
+/// [`from_read_with_config`]: fn.from_read_with_config.html
+pub fn from_read_async_with_config<'a, D, R>(reader: &'a mut R, framing: Framing, config: &OscConfig) -> ReadPacket<'a, D, R>
+    where R: AsyncRead + Unpin + ?Sized, D: DeserializeOwned
+{
+    ReadPacket {
+        reader,
+        framing,
+        config: *config,
+        prefix: [0; 4],
+        prefix_len: 0,
+        length: 0,
+        payload: Vec::new(),
+        payload_len: 0,
+        slip: FrameDecoder::default(),
+        _value: PhantomData,
+    }
+}
+
+/// Future returned by [`from_read_async`].
+///
+/// [`from_read_async`]: fn.from_read_async.html
+#[derive(Debug)]
+pub struct ReadPacket<'a, D, R: 'a + ?Sized> {
+    reader: &'a mut R,
+    framing: Framing,
+    config: OscConfig,
+    /// For `Framing::Framed`: the length prefix, as it arrives,
+    /// and the length it holds once complete.
+    prefix: [u8; 4],
+    prefix_len: usize,
+    length: usize,
+    /// The packet itself, and how much of it has been read so far.
+    payload: Vec<u8>,
+    payload_len: usize,
+    slip: FrameDecoder,
+    _value: PhantomData<fn() -> D>,
+}
+
+impl<'a, D, R> ReadPacket<'a, D, R>
+    where R: AsyncRead + Unpin + ?Sized
+{
+    /// Read into `buf`, returning how many bytes were read (0 indicates EOF).
+    fn poll_read_into(reader: &mut R, cx: &mut Context, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        let mut buf = ReadBuf::new(buf);
+        ready!(Pin::new(reader).poll_read(cx, &mut buf))?;
+        Poll::Ready(Ok(buf.filled().len()))
+    }
+    /// Read the rest of the packet into `self.payload`.
+    fn poll_payload(&mut self, cx: &mut Context) -> Poll<ResultE<()>> {
+        match self.framing {
+            Framing::Framed => {
+                while self.prefix_len < 4 {
+                    let len = ready!(Self::poll_read_into(self.reader, cx, &mut self.prefix[self.prefix_len..]))?;
+                    if len == 0 {
+                        return Poll::Ready(Err(io::Error::from(ErrorKind::UnexpectedEof).into()));
+                    }
+                    self.prefix_len += len;
+                    if self.prefix_len == 4 {
+                        self.length = BigEndian::read_i32(&self.prefix).try_into()?;
+                        if self.length > self.config.max_packet_size {
+                            return Poll::Ready(Err(OscError::BadFormat{ offset: 0, context: "invalid packet length" }));
+                        }
+                    }
+                }
+                while self.payload_len < self.length {
+                    if self.payload_len == self.payload.len() {
+                        // The length came from the peer, so space is only
+                        // reserved as the data arrives.
+                        let grow = cmp::max(READ_CHUNK, self.payload_len);
+                        self.payload.resize(cmp::min(self.length, self.payload_len + grow), 0);
+                    }
+                    let len = ready!(Self::poll_read_into(self.reader, cx, &mut self.payload[self.payload_len..]))?;
+                    if len == 0 {
+                        return Poll::Ready(Err(io::Error::from(ErrorKind::UnexpectedEof).into()));
+                    }
+                    self.payload_len += len;
+                }
+            },
+            Framing::Unframed => loop {
+                // The packet is everything up to EOF.
+                self.payload.resize(self.payload_len + READ_CHUNK, 0);
+                let len = ready!(Self::poll_read_into(self.reader, cx, &mut self.payload[self.payload_len..]))?;
+                self.payload_len += len;
+                if self.payload_len > self.config.max_packet_size {
+                    let offset = self.config.max_packet_size;
+                    return Poll::Ready(Err(OscError::BadFormat{ offset, context: "packet too long" }));
+                }
+                if len == 0 {
+                    self.payload.truncate(self.payload_len);
+                    break;
+                }
+            },
+            Framing::Slip => loop {
+                // Read one byte at a time, so as not to consume any of the next packet.
+                let mut byte = [0];
+                let len = ready!(Self::poll_read_into(self.reader, cx, &mut byte))?;
+                if len == 0 {
+                    return Poll::Ready(Err(io::Error::from(ErrorKind::UnexpectedEof).into()));
+                }
+                if let Some(packet) = self.slip.push(byte[0])? {
+                    self.payload = packet;
+                    break;
+                }
+                self.slip.check_len(self.config.max_packet_size)?;
+            },
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<'a, D, R> Future for ReadPacket<'a, D, R>
+    where R: AsyncRead + Unpin + ?Sized, D: DeserializeOwned
+{
+    type Output = ResultE<D>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<ResultE<D>> {
+        let this = self.get_mut();
+        ready!(this.poll_payload(cx))?;
+        let payload = Cursor::new(&this.payload[..]);
+        Poll::Ready(super::from_read_with_config(payload, Framing::Unframed, &this.config))
+    }
+}
@@ -0,0 +1,52 @@
+use std::convert::TryFrom;
+use std::io;
+use std::io::{ErrorKind, Read};
+use byteorder::{BigEndian, ReadBytesExt};
+
+use error::{OscError, ResultE};
+
+/// Discard the next length-prefixed (`Framing::Framed`) packet from `reader`,
+/// without parsing its contents.
+pub fn skip_packet<R: Read>(reader: &mut R) -> ResultE<()> {
+    let len = u64::try_from(reader.read_i32::<BigEndian>()?)?;
+    discard(reader, len)
+}
+
+/// Discard the data of one argument with the given type tag from `reader`,
+/// leaving it positioned at the start of the next argument.
+///
+/// Fails with `OscError::UnsupportedType` if the type tag isn't recognized,
+/// as the size of its data can't be known. The error's `arg_index` is always 0.
+pub fn skip_arg<R: Read>(reader: &mut R, typecode: u8) -> ResultE<()> {
+    match typecode {
+        b'i' | b'f' | b'u' | b'c' | b'r' | b'm' => discard(reader, 4),
+        b'h' | b'd' | b't' => discard(reader, 8),
+        // Strings are null-terminated, and padded to a multiple of 4 bytes,
+        // so the last 4-byte word always contains a 0.
+        b's' | b'S' => {
+            let mut word = [0; 4];
+            loop {
+                reader.read_exact(&mut word)?;
+                if word.contains(&0) {
+                    return Ok(());
+                }
+            }
+        },
+        b'b' => {
+            let size = u64::try_from(reader.read_i32::<BigEndian>()?)?;
+            discard(reader, (size + 3) & !0x3)
+        },
+        // No data.
+        b'T' | b'F' | b'N' | b'I' | b'[' | b']' => Ok(()),
+        type_code => Err(OscError::UnsupportedType{ type_code, arg_index: 0 }),
+    }
+}
+
+/// Read and throw away exactly `len` bytes.
+fn discard<R: Read>(reader: &mut R, len: u64) -> ResultE<()> {
+    let copied = io::copy(&mut reader.take(len), &mut io::sink())?;
+    if copied < len {
+        return Err(io::Error::from(ErrorKind::UnexpectedEof).into());
+    }
+    Ok(())
+}
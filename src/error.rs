@@ -6,25 +6,58 @@ use std::num;
 use std::string;
 use serde::{de, ser};
 
-/// Alias for a `Result` with the error type [`serde_osc::error::Error`].
+/// Alias for a `Result` with the error type [`serde_osc::error::OscError`].
 ///
-/// [`serde_osc::error::Error`]: enum.Error.html
-pub type ResultE<T> = Result<T, Error>;
+/// [`serde_osc::error::OscError`]: enum.OscError.html
+pub type ResultE<T> = Result<T, OscError>;
+
+/// The former name of [`OscError`], kept for compatibility.
+///
+/// [`OscError`]: enum.OscError.html
+pub type Error = OscError;
 
 
 /// Unified error type used in both serialization and deserialization.
 #[derive(Debug)]
-pub enum Error {
+pub enum OscError {
     /// User provided error message (via `serde::de::Error::custom`)
     Message(String),
-    /// Unknown argument type (i.e. not a 'f'=f32, 'i'=i32, etc)
-    UnsupportedType,
+    /// Unknown argument type (i.e. not a 'f'=f32, 'i'=i32, etc),
+    /// or a value the type can't hold (i.e. a non-ASCII 'c'=char)
+    UnsupportedType {
+        /// The unrecognized type tag.
+        type_code: u8,
+        /// Index of the argument within the message
+        /// (for an element of an array argument, the index of the array).
+        arg_index: usize,
+    },
+    /// A value with no OSC equivalent, e.g. a serde data type that OSC
+    /// has no way of representing.
+    UnsupportedValue,
+    /// The message had fewer arguments than the type being deserialized requires,
+    /// or than an argument index (e.g. given to `OscMessage::map_arg`) requires.
+    ArgMiscount {
+        expected: usize,
+        found: usize,
+    },
     /// Packet doesn't obey correct format; mismatched lengths, or
     /// attempt to read more arguments than were in the typestring (e.g.)
-    BadFormat,
+    BadFormat {
+        /// Byte offset within the packet at which the problem was detected.
+        /// This counts from the start of the length prefix for `Framing::Framed`,
+        /// and from the start of the decoded packet for `Framing::Slip`
+        /// (except for errors in the SLIP encoding itself, which count encoded bytes).
+        /// It's 0 for errors which don't arise from parsing, e.g. when serializing.
+        offset: usize,
+        /// Brief description of what was wrong.
+        context: &'static str,
+    },
     /// OSC expects all data to be aligned to 4 bytes lengths.
     /// Likely violators of this are strings, especially those at the end of a packet.
-    BadPadding,
+    BadPadding {
+        /// Byte offset of the first non-zero padding byte, as for `BadFormat`.
+        offset: usize,
+    },
     /// Error encountered due to `std::io::Read`
     Io(io::Error),
     /// Error converting between parsed type and what it represents.
@@ -33,74 +66,139 @@ pub enum Error {
     /// We store ascii strings as UTF-8.
     /// Technically, this is safe, but if we received non-ascii data, we could have invalid UTF-8
     StrParseError(string::FromUtf8Error),
+    /// An OSC address (or address pattern) didn't begin with '/',
+    /// or contained a character that isn't allowed where it appears.
+    InvalidAddress {
+        address: String,
+        /// Byte offset of the offending character.
+        position: usize,
+        /// The offending character; for an address not beginning with '/',
+        /// this is its first character (or '\0' if it's empty).
+        invalid_char: char,
+    },
+    /// An error within one of a bundle's elements.
+    /// Errors in nested bundles are wrapped once for each level of nesting.
+    InBundle {
+        /// Index of the element within the bundle.
+        element_index: usize,
+        /// The error encountered within that element.
+        inner: Box<OscError>,
+    },
 }
 
 
+impl OscError {
+    /// Whether this is an I/O failure, which may be worth retrying.
+    /// Like the other predicates, this looks through any `InBundle` wrapping.
+    pub fn is_io(&self) -> bool {
+        matches!(*self.innermost(), OscError::Io(_))
+    }
+    /// Whether the packet was malformed.
+    pub fn is_format(&self) -> bool {
+        matches!(*self.innermost(), OscError::BadFormat{ .. })
+    }
+    /// Whether the packet contained an argument of an unrecognized type.
+    pub fn is_unsupported_type(&self) -> bool {
+        matches!(*self.innermost(), OscError::UnsupportedType{ .. })
+    }
+    /// Whether the packet contained non-zero padding.
+    pub fn is_bad_padding(&self) -> bool {
+        matches!(*self.innermost(), OscError::BadPadding{ .. })
+    }
+    /// Whether a length or other integer was out of range.
+    pub fn is_bad_cast(&self) -> bool {
+        matches!(*self.innermost(), OscError::BadCast(_))
+    }
+    /// The error within any bundles.
+    fn innermost(&self) -> &OscError {
+        match *self {
+            OscError::InBundle{ ref inner, .. } => inner.innermost(),
+            ref error => error,
+        }
+    }
+}
+
 /// Conversion from `io::Error` for use with the `?` operator
-impl From<io::Error> for Error {
+impl From<io::Error> for OscError {
     fn from(e: io::Error) -> Self {
-        Error::Io(e)
+        OscError::Io(e)
     }
 }
 
 /// Conversion from `num::TryFromIntError` for use with the `?` operator
-impl From<num::TryFromIntError> for Error {
+impl From<num::TryFromIntError> for OscError {
     fn from(e: num::TryFromIntError) -> Self {
-        Error::BadCast(e)
+        OscError::BadCast(e)
     }
 }
 
 /// Conversion from `string::FromUtf8Error` for use with the `?` operator
-impl From<string::FromUtf8Error> for Error {
+impl From<string::FromUtf8Error> for OscError {
     fn from(e: string::FromUtf8Error) -> Self {
-        Error::StrParseError(e)
+        OscError::StrParseError(e)
     }
 }
 
 
-impl Display for Error {
+impl Display for OscError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            Error::Message(ref msg) => write!(f, "serde_osc error: {}", msg),
-            Error::UnsupportedType => write!(f, "Unsupported OSC type"),
-            Error::BadFormat => write!(f, "Bad OSC packet format"),
-            Error::BadPadding => write!(f, "OSC data not padded to 4-byte boundary"),
-            Error::Io(ref err) => err.fmt(f),
-            Error::BadCast(ref err) => err.fmt(f),
-            Error::StrParseError(_) => write!(f, "OSC string contains illegal (non-ascii) characters"),
+            OscError::Message(ref msg) => write!(f, "serde_osc error: {}", msg),
+            OscError::UnsupportedType{ type_code, arg_index } =>
+                write!(f, "Unsupported OSC type {:?} for argument {}", type_code as char, arg_index),
+            OscError::UnsupportedValue => write!(f, "Value not representable in OSC"),
+            OscError::ArgMiscount{ expected, found } =>
+                write!(f, "Expected {} OSC arguments, but found {}", expected, found),
+            OscError::BadFormat{ offset, context } =>
+                write!(f, "Bad OSC packet format at byte {}: {}", offset, context),
+            OscError::BadPadding{ offset } =>
+                write!(f, "OSC data not padded to 4-byte boundary (non-zero byte at {})", offset),
+            OscError::Io(ref err) => err.fmt(f),
+            OscError::BadCast(ref err) => err.fmt(f),
+            OscError::StrParseError(_) => write!(f, "OSC string contains illegal (non-ascii) characters"),
+            OscError::InvalidAddress{ ref address, position, invalid_char } =>
+                write!(f, "Invalid OSC address {:?}: unexpected {:?} at position {}",
+                    address, invalid_char, position),
+            OscError::InBundle{ element_index, ref inner } =>
+                write!(f, "In bundle element {}: {}", element_index, inner),
         }
     }
 }
 
-impl std::error::Error for Error {
+impl std::error::Error for OscError {
     fn description(&self) -> &str {
         match *self {
-            Error::Message(ref msg) => msg,
-            Error::UnsupportedType => "Unsupported OSC type",
-            Error::BadFormat => "OSC argument count mismatch",
-            Error::BadPadding => "Incorrect OSC data padding",
-            Error::Io(ref io_error) => io_error.description(),
-            Error::BadCast(ref cast_error) => cast_error.description(),
-            Error::StrParseError(ref utf_error) => utf_error.description(),
+            OscError::Message(ref msg) => msg,
+            OscError::UnsupportedType{ .. } => "Unsupported OSC type",
+            OscError::UnsupportedValue => "Value not representable in OSC",
+            OscError::ArgMiscount{ .. } => "OSC argument count mismatch",
+            OscError::BadFormat{ .. } => "OSC argument count mismatch",
+            OscError::BadPadding{ .. } => "Incorrect OSC data padding",
+            OscError::Io(ref io_error) => io_error.description(),
+            OscError::BadCast(ref cast_error) => cast_error.description(),
+            OscError::StrParseError(ref utf_error) => utf_error.description(),
+            OscError::InvalidAddress{ .. } => "Invalid OSC address",
+            OscError::InBundle{ .. } => "Error within OSC bundle element",
         }
     }
-    fn cause(&self) -> Option<&std::error::Error> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match *self {
-            Error::Io(ref io_error) => Some(io_error),
-            Error::BadCast(ref cast_error) => Some(cast_error),
-            Error::StrParseError(ref utf_error) => Some(utf_error),
+            OscError::Io(ref io_error) => Some(io_error),
+            OscError::BadCast(ref cast_error) => Some(cast_error),
+            OscError::StrParseError(ref utf_error) => Some(utf_error),
+            OscError::InBundle{ ref inner, .. } => Some(&**inner),
             _ => None,
         }
     }
 }
 
-impl de::Error for Error {
+impl de::Error for OscError {
     fn custom<T: Display>(msg: T) -> Self {
-        Error::Message(msg.to_string())
+        OscError::Message(msg.to_string())
     }
 }
-impl ser::Error for Error {
+impl ser::Error for OscError {
     fn custom<T: Display>(msg: T) -> Self {
-        Error::Message(msg.to_string())
+        OscError::Message(msg.to_string())
     }
 }
@@ -0,0 +1,23 @@
+pub mod slip;
+
+pub use self::slip::SlipFramed;
+
+/// How packets are delimited on the underlying transport.
+///
+/// OSC 1.0 leaves this up to the transport: packet-oriented transports
+/// (e.g. UDP) carry exactly one packet per datagram, whereas stream-oriented
+/// transports (e.g. TCP) prefix each packet with its length, as an `i32`.
+/// OSC 1.1 additionally recommends SLIP for serial transports.
+/// Elements within a bundle are always length-prefixed, regardless of framing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Framing {
+    /// Each packet is preceded by its length in bytes, as a big-endian `i32`.
+    /// Use this for streams, such as TCP or files containing several packets.
+    Framed,
+    /// The packet occupies the entire payload, with no length prefix.
+    /// Use this for datagrams, such as UDP.
+    Unframed,
+    /// The packet is delimited and escaped according to [SLIP](slip/index.html).
+    /// Use this for serial lines.
+    Slip,
+}
@@ -0,0 +1,205 @@
+//! SLIP ([RFC 1055]) framing, as used for OSC over serial lines.
+//!
+//! Each packet is delimited by `END` bytes. Any `END` or `ESC` bytes within
+//! the packet are replaced by a two-byte escape sequence. Following OSC 1.1,
+//! packets are encoded with an `END` both before and after them ("double-ended" SLIP).
+//!
+//! [RFC 1055]: https://tools.ietf.org/html/rfc1055
+
+use std::cmp;
+use std::io;
+use std::io::{ErrorKind, Read};
+use std::mem;
+use byteorder::ReadBytesExt;
+
+use error::{OscError, ResultE};
+
+/// Delimits packets.
+pub const END: u8 = 0xC0;
+/// Begins an escape sequence.
+pub const ESC: u8 = 0xDB;
+/// `ESC ESC_END` encodes a literal `END` byte.
+pub const ESC_END: u8 = 0xDC;
+/// `ESC ESC_ESC` encodes a literal `ESC` byte.
+pub const ESC_ESC: u8 = 0xDD;
+
+/// How many bytes `SlipFramed` requests from its reader at a time.
+const READ_CHUNK: usize = 512;
+
+/// SLIP-encode a single packet.
+pub fn encode(packet: &[u8]) -> Vec<u8> {
+    let mut encoded = Vec::with_capacity(packet.len() + 2);
+    encoded.push(END);
+    for &byte in packet {
+        match byte {
+            END => encoded.extend_from_slice(&[ESC, ESC_END]),
+            ESC => encoded.extend_from_slice(&[ESC, ESC_ESC]),
+            _ => encoded.push(byte),
+        }
+    }
+    encoded.push(END);
+    encoded
+}
+
+/// Decode a single SLIP-encoded packet.
+/// The leading and trailing `END` bytes are optional, but `data` must not
+/// contain more than one packet.
+pub fn decode(data: &[u8]) -> ResultE<Vec<u8>> {
+    let mut decoder = FrameDecoder::default();
+    let mut packet = None;
+    for (offset, &byte) in data.iter().enumerate() {
+        if let Some(decoded) = decoder.push(byte)? {
+            if packet.is_some() {
+                return Err(OscError::BadFormat{ offset, context: "more than one SLIP packet" });
+            }
+            packet = Some(decoded);
+        }
+    }
+    if decoder.in_progress() {
+        if packet.is_some() {
+            return Err(OscError::BadFormat{ offset: data.len(), context: "more than one SLIP packet" });
+        }
+        packet = Some(decoder.finish()?);
+    }
+    Ok(packet.unwrap_or_default())
+}
+
+/// Read exactly one SLIP-encoded packet from `read`, and decode it.
+/// No bytes beyond the packet's closing `END` are consumed.
+/// Fails once the decoded packet exceeds `max_len` bytes.
+pub(crate) fn read_frame<R: Read>(read: &mut R, max_len: usize) -> ResultE<Vec<u8>> {
+    let mut decoder = FrameDecoder::default();
+    loop {
+        if let Some(packet) = decoder.push(read.read_u8()?)? {
+            return Ok(packet);
+        }
+        decoder.check_len(max_len)?;
+    }
+}
+
+/// Incrementally decodes SLIP data, one byte at a time.
+#[derive(Debug, Default)]
+pub(crate) struct FrameDecoder {
+    packet: Vec<u8>,
+    escaped: bool,
+    /// Number of encoded bytes consumed so far, for error reporting.
+    offset: usize,
+}
+
+impl FrameDecoder {
+    /// Consume one byte of encoded data, returning the packet once it's complete.
+    pub(crate) fn push(&mut self, byte: u8) -> ResultE<Option<Vec<u8>>> {
+        self.offset += 1;
+        if self.escaped {
+            self.escaped = false;
+            match byte {
+                ESC_END => self.packet.push(END),
+                ESC_ESC => self.packet.push(ESC),
+                _ => return Err(OscError::BadFormat{ offset: self.offset - 1, context: "invalid SLIP escape" }),
+            }
+        } else {
+            match byte {
+                // Leading END, or the space between two packets.
+                END if self.packet.is_empty() => {},
+                END => return Ok(Some(mem::take(&mut self.packet))),
+                ESC => self.escaped = true,
+                _ => self.packet.push(byte),
+            }
+        }
+        Ok(None)
+    }
+    /// Fail if the packet decoded so far exceeds `max_len` bytes.
+    pub(crate) fn check_len(&self, max_len: usize) -> ResultE<()> {
+        if self.packet.len() > max_len {
+            return Err(OscError::BadFormat{ offset: self.offset, context: "packet too long" });
+        }
+        Ok(())
+    }
+    /// Whether a packet has begun, but not yet been terminated.
+    fn in_progress(&self) -> bool {
+        self.escaped || !self.packet.is_empty()
+    }
+    /// Treat the end of input as the end of the current packet.
+    fn finish(self) -> ResultE<Vec<u8>> {
+        if self.escaped {
+            Err(OscError::BadFormat{ offset: self.offset, context: "unterminated SLIP escape" })
+        } else {
+            Ok(self.packet)
+        }
+    }
+}
+
+/// Adapts a reader of SLIP-encoded data into one of decoded packets.
+///
+/// Each call to `read` yields data from at most one packet. Whatever doesn't
+/// fit into the caller's buffer is held onto and returned by the next call,
+/// as is any encoded data read past the end of that packet.
+#[derive(Debug)]
+pub struct SlipFramed<R: Read> {
+    inner: R,
+    /// Encoded data that has been read, but not yet decoded.
+    raw: Vec<u8>,
+    raw_pos: usize,
+    decoder: FrameDecoder,
+    /// The decoded packet currently being read out.
+    packet: Vec<u8>,
+    packet_pos: usize,
+}
+
+impl<R: Read> SlipFramed<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            raw: Vec::new(),
+            raw_pos: 0,
+            decoder: FrameDecoder::default(),
+            packet: Vec::new(),
+            packet_pos: 0,
+        }
+    }
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+    /// Decode the next packet, or return `None` upon a clean EOF.
+    fn next_packet(&mut self) -> io::Result<Option<Vec<u8>>> {
+        loop {
+            while self.raw_pos < self.raw.len() {
+                let byte = self.raw[self.raw_pos];
+                self.raw_pos += 1;
+                let decoded = self.decoder.push(byte)
+                    .map_err(|e| io::Error::new(ErrorKind::InvalidData, e))?;
+                if decoded.is_some() {
+                    return Ok(decoded);
+                }
+            }
+            self.raw.resize(READ_CHUNK, 0);
+            let len = self.inner.read(&mut self.raw)?;
+            self.raw.truncate(len);
+            self.raw_pos = 0;
+            if len == 0 {
+                if self.decoder.in_progress() {
+                    return Err(ErrorKind::UnexpectedEof.into());
+                }
+                return Ok(None);
+            }
+        }
+    }
+}
+
+impl<R: Read> Read for SlipFramed<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.packet_pos == self.packet.len() {
+            match self.next_packet()? {
+                Some(packet) => {
+                    self.packet = packet;
+                    self.packet_pos = 0;
+                },
+                None => return Ok(0),
+            }
+        }
+        let len = cmp::min(buf.len(), self.packet.len() - self.packet_pos);
+        buf[..len].copy_from_slice(&self.packet[self.packet_pos..self.packet_pos + len]);
+        self.packet_pos += len;
+        Ok(len)
+    }
+}
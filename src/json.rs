@@ -0,0 +1,130 @@
+//! Tagged representation of `OscArg`, used by human-readable formats such as JSON.
+//!
+//! Within an OSC packet an argument's type is given by the message's type tag,
+//! so `OscArg` serializes as its bare value. Elsewhere that information would
+//! be lost (an `I32` and an `I64` both look like a number), so each argument
+//! is instead written alongside its type tag: `{"type":"i","value":42}`.
+//! Blobs are base64-encoded, and arguments without data (e.g. `T`, `N`)
+//! have no "value" field.
+//!
+//! The OSC (de)serializers report themselves as not human-readable,
+//! so never see this representation.
+
+use std::fmt;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde::de::{Deserializer, MapAccess, Unexpected, Visitor};
+use serde::de;
+use serde::ser::{SerializeStruct, Serializer};
+
+use types::OscArg;
+
+const NAME: &str = "OscArg";
+const FIELDS: &[&str] = &["type", "value"];
+const TAGS: &[&str] = &["i", "f", "s", "b", "h", "d", "c", "r", "m", "T", "F", "N", "I", "S", "t", "u", "["];
+
+pub(crate) fn serialize_arg<S>(arg: &OscArg, serializer: S) -> Result<S::Ok, S::Error>
+    where S: Serializer
+{
+    let tag = match *arg {
+        OscArg::I32(_) => "i",
+        OscArg::F32(_) => "f",
+        OscArg::String(_) => "s",
+        OscArg::Blob(_) => "b",
+        OscArg::I64(_) => "h",
+        OscArg::F64(_) => "d",
+        OscArg::Char(_) => "c",
+        OscArg::Color(_) => "r",
+        OscArg::Midi(_) => "m",
+        OscArg::Bool(true) => "T",
+        OscArg::Bool(false) => "F",
+        OscArg::Nil => "N",
+        OscArg::Impulse => "I",
+        OscArg::Symbol(_) => "S",
+        OscArg::TimeTag(_) => "t",
+        OscArg::U32(_) => "u",
+        OscArg::Array(_) => "[",
+        #[cfg(feature = "bytes")]
+        OscArg::BlobBytes(_) => "b",
+    };
+    let has_value = !matches!(*arg, OscArg::Bool(_) | OscArg::Nil | OscArg::Impulse);
+    let mut state = serializer.serialize_struct(NAME, if has_value { 2 } else { 1 })?;
+    state.serialize_field("type", tag)?;
+    match *arg {
+        OscArg::I32(i) => state.serialize_field("value", &i)?,
+        OscArg::F32(f) => state.serialize_field("value", &f)?,
+        OscArg::String(ref s) | OscArg::Symbol(ref s) => state.serialize_field("value", s)?,
+        OscArg::Blob(ref b) => state.serialize_field("value", &BASE64.encode(b))?,
+        OscArg::I64(h) => state.serialize_field("value", &h)?,
+        OscArg::F64(d) => state.serialize_field("value", &d)?,
+        OscArg::Char(c) => state.serialize_field("value", &c)?,
+        OscArg::Color(ref r) => state.serialize_field("value", r)?,
+        OscArg::Midi(ref m) => state.serialize_field("value", m)?,
+        OscArg::Bool(_) | OscArg::Nil | OscArg::Impulse => {},
+        OscArg::TimeTag(ref t) => state.serialize_field("value", t)?,
+        OscArg::U32(u) => state.serialize_field("value", &u)?,
+        OscArg::Array(ref a) => state.serialize_field("value", a)?,
+        #[cfg(feature = "bytes")]
+        OscArg::BlobBytes(ref b) => state.serialize_field("value", &BASE64.encode(b))?,
+    }
+    state.end()
+}
+
+pub(crate) fn deserialize_arg<'de, D>(deserializer: D) -> Result<OscArg, D::Error>
+    where D: Deserializer<'de>
+{
+    deserializer.deserialize_struct(NAME, FIELDS, TaggedArgVisitor)
+}
+
+struct TaggedArgVisitor;
+
+impl<'de> Visitor<'de> for TaggedArgVisitor {
+    type Value = OscArg;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an OSC argument tagged with its type")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<OscArg, A::Error>
+        where A: MapAccess<'de>
+    {
+        // The type must come first, as it determines how to parse the value.
+        match map.next_key::<String>()? {
+            Some(ref key) if key == "type" => {},
+            _ => return Err(de::Error::missing_field("type")),
+        }
+        let tag: String = map.next_value()?;
+        match tag.as_str() {
+            "T" => return Ok(OscArg::Bool(true)),
+            "F" => return Ok(OscArg::Bool(false)),
+            "N" => return Ok(OscArg::Nil),
+            "I" => return Ok(OscArg::Impulse),
+            _ => {},
+        }
+        match map.next_key::<String>()? {
+            Some(ref key) if key == "value" => {},
+            _ => return Err(de::Error::missing_field("value")),
+        }
+        Ok(match tag.as_str() {
+            "i" => OscArg::I32(map.next_value()?),
+            "f" => OscArg::F32(map.next_value()?),
+            "s" => OscArg::String(map.next_value()?),
+            "b" => {
+                let encoded: String = map.next_value()?;
+                let blob = BASE64.decode(&encoded).map_err(|_|
+                    de::Error::invalid_value(Unexpected::Str(&encoded), &"a base64-encoded blob"))?;
+                OscArg::Blob(blob)
+            },
+            "h" => OscArg::I64(map.next_value()?),
+            "d" => OscArg::F64(map.next_value()?),
+            "c" => OscArg::Char(map.next_value()?),
+            "r" => OscArg::Color(map.next_value()?),
+            "m" => OscArg::Midi(map.next_value()?),
+            "S" => OscArg::Symbol(map.next_value()?),
+            "t" => OscArg::TimeTag(map.next_value()?),
+            "u" => OscArg::U32(map.next_value()?),
+            "[" => OscArg::Array(map.next_value()?),
+            other => return Err(de::Error::unknown_variant(other, TAGS)),
+        })
+    }
+}
@@ -19,7 +19,24 @@
 //! i.e., the values corresponding to each of these arguments.
 //! The full specification can be found at [http://opensoundcontrol.org/spec-1_0].
 //! Serde_osc implements version 1.0 of the spec (i.e. 'i', 'f', 's' and 'b'
-//! argument types).
+//! argument types), along with the following optional argument types from OSC 1.1:
+//!
+//! * 'h': `i64`
+//! * 'd': `f64`
+//! * 'c': `char` (ASCII only)
+//! * 'r': [`OscColor`]
+//! * 'm': [`OscMidi`]
+//! * 'S': [`OscSymbol`]
+//! * 'T' and 'F': `bool`
+//! * 'N': `None` or `()`
+//! * 'I': [`OscImpulse`]
+//! * 't': [`OscTimeTag`], or any nested `(u32, u32)`, just like a bundle's timetag
+//! * '[' and ']': arrays, from any nested sequence (e.g. a tuple or `Vec`) of arguments
+//!
+//! `u32` arguments are also supported, using the 'u' type tag found in some OSC 2.0 drafts.
+//!
+//! Arguments whose types aren't known until runtime can be (de)serialized via [`OscArg`],
+//! and entire packets of unknown shape via [`OscMessage`], [`OscBundle`] and [`OscPacket`].
 //!
 //! # Serde_osc usage
 //!
@@ -28,12 +45,40 @@
 //! sink/source that implements `std::io::Write` or `std::io::Read`, respectively.
 //!
 //! Convenience functions are also provided for some common formats; see
-//! [`serde_osc::to_vec`] and [`serde_osc::from_vec`].
+//! [`serde_osc::to_vec`] and [`serde_osc::from_slice`].
+//!
+//! ## Framing
+//!
+//! Each of the above takes a [`Framing`], which depends on the transport.
+//! Over a stream (e.g. TCP), packets are preceded by their length as an `i32`
+//! so that the receiver knows where one ends and the next begins: use `Framing::Framed`.
+//! A datagram (e.g. UDP) already holds exactly one packet, so no length is sent:
+//! use `Framing::Unframed`. Getting this wrong is the most common cause of
+//! undecodable packets, so the following wrappers are provided to spell it out:
+//!
+//! * [`serde_osc::to_udp_payload`] and [`serde_osc::from_udp_payload`], for datagrams.
+//! * [`serde_osc::to_tcp_stream`] and [`serde_osc::from_tcp_stream`], for streams.
+//!
+//! Serial lines (e.g. UART) instead delimit packets using SLIP: use `Framing::Slip`.
 //!
 //! [`serde_osc::to_write`]: ser/fn.to_write.html
 //! [`serde_osc::from_read`]: de/fn.from_read.html
 //! [`serde_osc::to_vec`]: ser/fn.to_vec.html
-//! [`serde_osc::from_vec`]: de/fn.from_vec.html
+//! [`serde_osc::from_slice`]: de/fn.from_slice.html
+//! [`Framing`]: framing/enum.Framing.html
+//! [`serde_osc::to_udp_payload`]: fn.to_udp_payload.html
+//! [`serde_osc::from_udp_payload`]: fn.from_udp_payload.html
+//! [`serde_osc::to_tcp_stream`]: fn.to_tcp_stream.html
+//! [`serde_osc::from_tcp_stream`]: fn.from_tcp_stream.html
+//! [`OscArg`]: types/enum.OscArg.html
+//! [`OscMessage`]: packet/struct.OscMessage.html
+//! [`OscBundle`]: packet/struct.OscBundle.html
+//! [`OscPacket`]: packet/enum.OscPacket.html
+//! [`OscColor`]: types/struct.OscColor.html
+//! [`OscMidi`]: types/struct.OscMidi.html
+//! [`OscImpulse`]: types/struct.OscImpulse.html
+//! [`OscSymbol`]: types/struct.OscSymbol.html
+//! [`OscTimeTag`]: types/struct.OscTimeTag.html
 //! [http://opensoundcontrol.org/spec-1_0]: http://opensoundcontrol.org/spec-1_0
 //!
 //! # Examples
@@ -49,7 +94,7 @@
 //! extern crate serde_osc;
 //!
 //! use serde_bytes::ByteBuf;
-//! use serde_osc::{de, ser};
+//! use serde_osc::{de, ser, Framing};
 //!
 //! /// Struct we'll serialize.
 //! /// This represents a single OSC message with three arguments:
@@ -75,11 +120,11 @@
 //!     println!("Serializing {:?}", message);
 //!
 //!     // Serialize the message to an OSC packet stored in a Vec<u8>
-//!     let as_vec = ser::to_vec(&message).unwrap();
+//!     let as_vec = ser::to_vec(&message, Framing::Framed).unwrap();
 //!     println!("Serialied to: {:?}", as_vec);
 //!
 //!     // Deserialize an OSC packet contained in a Vec<u8> into the Message struct
-//!     let received: Message = de::from_slice(&as_vec).unwrap();
+//!     let received: Message = de::from_slice(&as_vec, Framing::Framed).unwrap();
 //!     println!("Received: {:?}", received);
 //! }
 //! ```
@@ -90,6 +135,24 @@
 extern crate byteorder;
 #[macro_use]
 extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+#[cfg(feature = "bytes")]
+extern crate bytes;
+#[cfg(feature = "tokio")]
+extern crate tokio;
+#[cfg(feature = "tokio")]
+extern crate tokio_util;
+#[cfg(feature = "json")]
+extern crate base64;
+#[cfg(feature = "json")]
+extern crate serde_json;
+#[cfg(feature = "derive")]
+extern crate serde_osc_macros;
+#[cfg(feature = "testing")]
+extern crate arbitrary;
+#[cfg(feature = "tracing")]
+extern crate tracing;
 
 /// Errors returned upon serialization/deserialization failure.
 pub mod error;
@@ -97,6 +160,167 @@ pub mod error;
 pub mod de;
 /// OSC packet serialization framework.
 pub mod ser;
+/// Delimiting of packets on the underlying transport.
+pub mod framing;
+/// Limits on the packets that may be (de)serialized.
+pub mod config;
+/// Types for OSC arguments that have no native Rust equivalent.
+pub mod types;
+/// Validated OSC addresses.
+pub mod address;
+/// Matching of OSC address patterns against addresses.
+pub mod pattern;
+/// Dispatch of messages to handlers by address pattern.
+pub mod router;
+/// Descriptions of the addresses a server responds to.
+pub mod namespace;
+/// Delivery of bundled messages at the times given by their timetags.
+pub mod schedule;
+/// Builders for constructing packets one piece at a time.
+pub mod builder;
+/// Types for whole OSC packets, whose shape is only known at runtime.
+pub mod packet;
+/// Sending and receiving packets over UDP.
+pub mod udp;
+/// Codec for (de)serializing packets with tokio. Requires the `tokio` feature.
+#[cfg(feature = "tokio")]
+pub mod codec;
+#[cfg(feature = "json")]
+mod json;
+#[cfg(feature = "testing")]
+mod testing;
+/// Internal encoding primitives, exposed only for the benchmarks under `benches/`.
+#[doc(hidden)]
+pub mod bench;
+
+pub use de::{from_read, from_read_all, from_read_with_config, from_slice, packets_from_read};
+pub use ser::{to_write, to_write_with_config, to_vec};
+pub use framing::Framing;
+pub use config::OscConfig;
+pub use error::OscError;
+pub use address::{validate_address, OscAddress};
+pub use builder::{BundleBuilder, MessageBuilder};
+pub use pattern::{match_osc_pattern, OscAddressPattern};
+pub use router::{HandlerId, OscDispatcher, OscRouter};
+pub use namespace::{OscNamespace, OscNodeInfo};
+pub use schedule::BundleDispatcher;
+pub use packet::{OscBundle, OscMessage, OscPacket};
+pub use udp::{UdpOscReceiver, UdpOscSender};
+pub use types::{OscArg, OscColor, OscImpulse, OscMidi, OscSymbol, OscTimeTag, OscTypeTag};
+#[cfg(feature = "derive")]
+pub use serde_osc_macros::OscDispatch;
+/// ```
+/// #[macro_use]
+/// extern crate serde_osc;
+///
+/// fn main() {
+///     let address = osc_address!("/audio/play");
+///     assert_eq!(&*address, "/audio/play");
+/// }
+/// ```
+///
+/// Invalid addresses fail to compile:
+///
+/// ```compile_fail
+/// #[macro_use]
+/// extern crate serde_osc;
+///
+/// fn main() {
+///     let address = osc_address!("/audio/play now");
+/// }
+/// ```
+///
+/// ```compile_fail
+/// #[macro_use]
+/// extern crate serde_osc;
+///
+/// fn main() {
+///     let address = osc_address!("audio/play");
+/// }
+/// ```
+#[cfg(feature = "derive")]
+pub use serde_osc_macros::osc_address;
+/// ```
+/// #[macro_use]
+/// extern crate serde_derive;
+/// extern crate serde_osc;
+///
+/// use serde_osc::{de, ser, osc_addressed, Framing};
+///
+/// #[osc_addressed]
+/// #[derive(Debug, Serialize, Deserialize, PartialEq)]
+/// struct Play {
+///     #[osc_address = "/audio/play"]
+///     address: (),
+///     args: (f32,),
+/// }
+///
+/// fn main() {
+///     let play = Play { address: (), args: (0.5,) };
+///     let bytes = ser::to_vec(&play, Framing::Unframed).unwrap();
+///     assert_eq!(de::from_slice::<Play>(&bytes, Framing::Unframed).unwrap(), play);
+/// }
+/// ```
+///
+/// Invalid addresses fail to compile:
+///
+/// ```compile_fail
+/// #[macro_use]
+/// extern crate serde_derive;
+/// extern crate serde_osc;
+///
+/// use serde_osc::osc_addressed;
+///
+/// #[osc_addressed]
+/// #[derive(Serialize, Deserialize)]
+/// struct Play {
+///     #[osc_address = "/audio/{play}"]
+///     address: (),
+///     args: (f32,),
+/// }
+///
+/// fn main() {}
+/// ```
+#[cfg(feature = "derive")]
+pub use serde_osc_macros::osc_addressed;
+#[cfg(feature = "tokio")]
+pub use codec::OscCodec;
+#[cfg(feature = "tokio")]
+pub use de::{from_read_async, from_read_async_with_config};
+#[cfg(feature = "tokio")]
+pub use ser::to_write_async;
+
+use std::io::{Read, Write};
+use error::ResultE;
+
+/// Deserialize a packet received as a single datagram (e.g. over UDP).
+/// Equivalent to `from_slice(data, Framing::Unframed)`.
+pub fn from_udp_payload<'de, T>(data: &[u8]) -> ResultE<T>
+    where T: serde::de::Deserialize<'de>
+{
+    from_slice(data, Framing::Unframed)
+}
+
+/// Serialize `value` into a packet to be sent as a single datagram (e.g. over UDP).
+/// Equivalent to `to_vec(value, Framing::Unframed)`.
+pub fn to_udp_payload<T>(value: &T) -> ResultE<Vec<u8>>
+    where T: serde::ser::Serialize + ?Sized
+{
+    to_vec(value, Framing::Unframed)
+}
+
+/// Deserialize the next length-prefixed packet from a stream (e.g. a `TcpStream`).
+/// Equivalent to `from_read(stream, Framing::Framed)`.
+pub fn from_tcp_stream<'de, T, R>(stream: R) -> ResultE<T>
+    where R: Read, T: serde::de::Deserialize<'de>
+{
+    from_read(stream, Framing::Framed)
+}
 
-pub use de::{from_read, from_slice};
-pub use ser::{to_write, to_vec};
+/// Serialize `value` as a length-prefixed packet onto a stream (e.g. a `TcpStream`).
+/// Equivalent to `to_write(stream, value, Framing::Framed)`.
+pub fn to_tcp_stream<T, W>(stream: &mut W, value: &T) -> ResultE<()>
+    where W: Write, T: serde::ser::Serialize + ?Sized
+{
+    to_write(stream, value, Framing::Framed)
+}
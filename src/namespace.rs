@@ -0,0 +1,158 @@
+//! Description of the addresses a server responds to, for advertising
+//! them to clients (e.g. via [OSCQuery](https://github.com/Vidvox/OSCQueryProposal)).
+
+use std::collections::BTreeMap;
+
+use address::OscAddress;
+use pattern::match_osc_pattern;
+
+/// Access to the value at an address: neither read nor written,
+/// e.g. a method which just triggers an action.
+pub const ACCESS_NONE: u8 = 0;
+/// The value at an address may be read.
+pub const ACCESS_READ: u8 = 1;
+/// The value at an address may be written.
+pub const ACCESS_WRITE: u8 = 2;
+/// The value at an address may be both read and written.
+pub const ACCESS_READ_WRITE: u8 = ACCESS_READ | ACCESS_WRITE;
+
+/// What's known about a single address, as described by OSCQuery.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OscNodeInfo {
+    /// Human-readable description of the address.
+    pub description: String,
+    /// The type tag of the arguments the address takes, without the leading ','.
+    pub type_tag: Option<String>,
+    /// One of the `ACCESS_*` constants.
+    pub access: u8,
+}
+
+/// A tree of addresses, each with an `OscNodeInfo`.
+/// Each node of the tree is one '/'-separated part of an address.
+///
+/// ```
+/// # use serde_osc::OscAddress;
+/// # use serde_osc::namespace::{OscNamespace, OscNodeInfo, ACCESS_WRITE};
+/// let mut namespace = OscNamespace::new();
+/// let info = OscNodeInfo { description: "Oscillator frequency".to_owned(), type_tag: Some("f".to_owned()), access: ACCESS_WRITE };
+/// namespace.insert(&OscAddress::new("/synth/1/freq").unwrap(), info.clone());
+/// namespace.insert(&OscAddress::new("/synth/2/freq").unwrap(), info);
+/// assert_eq!(namespace.query("/synth/*/freq").len(), 2);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OscNamespace {
+    root: Node,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Node {
+    /// Present if this node's address has been inserted,
+    /// rather than just being the prefix of one that has.
+    info: Option<OscNodeInfo>,
+    children: BTreeMap<String, Node>,
+}
+
+impl OscNamespace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Describe `addr`, returning its previous description, if any.
+    pub fn insert(&mut self, addr: &OscAddress, info: OscNodeInfo) -> Option<OscNodeInfo> {
+        let node = addr.components().fold(&mut self.root, |node, component|
+            node.children.entry(component.to_owned()).or_default());
+        node.info.replace(info)
+    }
+    pub fn get(&self, addr: &OscAddress) -> Option<&OscNodeInfo> {
+        let mut node = &self.root;
+        for component in addr.components() {
+            node = node.children.get(component)?;
+        }
+        node.info.as_ref()
+    }
+    /// Remove the description of `addr`, returning whether there was one.
+    /// Addresses beneath `addr` (e.g. "/a/b" beneath "/a") are unaffected.
+    pub fn remove(&mut self, addr: &OscAddress) -> bool {
+        self.root.remove(&addr.components().collect::<Vec<_>>())
+    }
+    /// Every described address which `pattern` matches, in lexicographic order
+    /// of their components. A malformed pattern matches nothing.
+    pub fn query(&self, pattern: &str) -> Vec<OscAddress> {
+        let mut matches = Vec::new();
+        self.root.visit(&mut String::new(), &mut |address, _| {
+            if match_osc_pattern(pattern, address) {
+                matches.push(OscAddress::from_validated(address));
+            }
+        });
+        matches
+    }
+    /// The namespace in the format of an OSCQuery response to a request for "/".
+    /// Requires the `json` feature.
+    ///
+    /// Each node is an object with a "FULL_PATH", plus "DESCRIPTION",
+    /// "ACCESS" and (if known) "TYPE" for described addresses,
+    /// and "CONTENTS" for those with addresses beneath them.
+    #[cfg(feature = "json")]
+    pub fn to_query_json(&self) -> ::serde_json::Value {
+        self.root.to_query_json("/".to_owned())
+    }
+}
+
+impl Node {
+    /// Remove the info at `path` below this node, along with any nodes left empty.
+    fn remove(&mut self, path: &[&str]) -> bool {
+        match path.split_first() {
+            None => self.info.take().is_some(),
+            Some((first, rest)) => {
+                let (removed, now_empty) = match self.children.get_mut(*first) {
+                    Some(child) => (child.remove(rest), child.is_empty()),
+                    None => return false,
+                };
+                if now_empty {
+                    self.children.remove(*first);
+                }
+                removed
+            },
+        }
+    }
+    fn is_empty(&self) -> bool {
+        self.info.is_none() && self.children.is_empty()
+    }
+    /// Call `f` for each described node beneath this one (which is at `address`).
+    fn visit<F: FnMut(&str, &OscNodeInfo)>(&self, address: &mut String, f: &mut F) {
+        let len = address.len();
+        for (component, child) in &self.children {
+            address.push('/');
+            address.push_str(component);
+            if let Some(ref info) = child.info {
+                f(address, info);
+            }
+            child.visit(address, f);
+            address.truncate(len);
+        }
+    }
+    #[cfg(feature = "json")]
+    fn to_query_json(&self, full_path: String) -> ::serde_json::Value {
+        use serde_json::{Map, Value};
+
+        let mut obj = Map::new();
+        if let Some(ref info) = self.info {
+            obj.insert("DESCRIPTION".to_owned(), Value::from(info.description.clone()));
+            obj.insert("ACCESS".to_owned(), Value::from(info.access));
+            if let Some(ref type_tag) = info.type_tag {
+                obj.insert("TYPE".to_owned(), Value::from(type_tag.clone()));
+            }
+        }
+        if !self.children.is_empty() {
+            let prefix = if full_path == "/" { "" } else { &full_path };
+            let contents = self.children.iter()
+                .map(|(component, child)| {
+                    let path = format!("{}/{}", prefix, component);
+                    (component.clone(), child.to_query_json(path))
+                })
+                .collect();
+            obj.insert("CONTENTS".to_owned(), Value::Object(contents));
+        }
+        obj.insert("FULL_PATH".to_owned(), Value::from(full_path));
+        Value::Object(obj)
+    }
+}
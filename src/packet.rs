@@ -0,0 +1,640 @@
+//! Concrete OSC packet types, for when the shape of the packets being
+//! sent or received isn't known at compile time.
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::{Cursor, Read, Write};
+use std::iter::FromIterator;
+use std::vec;
+use std::slice;
+use serde::de::{Deserialize, DeserializeOwned, Deserializer, IgnoredAny, SeqAccess, Visitor};
+use serde::de;
+use serde::ser::{Serialize, Serializer};
+
+use error::{OscError, ResultE};
+use pattern::{match_osc_pattern, validate_pattern};
+use de::{from_slice, read_all_args};
+use framing::Framing;
+use ser::to_vec;
+use ser::msg_serializer::MsgSerializer;
+use types::{padded_str_len, OscArg, OscTimeTag};
+
+/// Create an [`OscMessage`] from an address and any number of arguments,
+/// each converted via `OscArg::from`. Panics if the address doesn't begin with a '/'.
+///
+/// The argument's type determines its variant, so use suffixes to pick
+/// between e.g. `I32` and `I64` (unsuffixed literals are `i32` and `f64`).
+/// String literals become `String`s, and byte string literals `Blob`s.
+///
+/// ```
+/// #[macro_use]
+/// extern crate serde_osc;
+/// use serde_osc::{OscArg, OscMessage};
+///
+/// fn main() {
+///     let msg = osc_message!("/synth/note", 60, 0.5f32, "sine", b"\xde\xad");
+///     assert_eq!(msg, OscMessage::new("/synth/note", vec![
+///         OscArg::I32(60), OscArg::F32(0.5),
+///         OscArg::String("sine".to_owned()), OscArg::Blob(vec![0xde, 0xad]),
+///     ]).unwrap());
+/// }
+/// ```
+///
+/// Arguments with no OSC equivalent fail to compile:
+///
+/// ```compile_fail
+/// #[macro_use]
+/// extern crate serde_osc;
+///
+/// fn main() {
+///     let msg = osc_message!("/synth/note", 60u16);
+/// }
+/// ```
+///
+/// [`OscMessage`]: packet/struct.OscMessage.html
+#[macro_export]
+macro_rules! osc_message {
+    ($address:expr $(, $arg:expr)* $(,)*) => {
+        $crate::packet::OscMessage::new($address, vec![$($crate::types::OscArg::from($arg)),*])
+            .unwrap()
+    };
+}
+
+/// An OSC message with any address and any number of arguments.
+///
+/// This serializes exactly as a `(String, Vec<OscArg>)` would.
+///
+/// Messages are `Eq` and `Hash`, comparing float arguments by their bit
+/// patterns (see [`OscArg`]), so they can be used as `HashMap` keys.
+///
+/// [`OscArg`]: enum.OscArg.html
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct OscMessage {
+    pub(crate) address: String,
+    pub(crate) args: Vec<OscArg>,
+}
+
+impl OscMessage {
+    /// Create a message, checking that `address` is a well-formed address pattern
+    /// (see `OscAddressPattern::compile`), as every address is.
+    /// This fails with `Error::InvalidAddress` otherwise.
+    pub fn new<S: Into<String>>(address: S, args: Vec<OscArg>) -> ResultE<Self> {
+        let address = address.into();
+        // The address may be a pattern, so don't check for the characters
+        // which OscAddress forbids.
+        validate_pattern(&address)?;
+        Ok(Self {
+            address,
+            args,
+        })
+    }
+    /// Create a message from its type tag (with or without the leading ',')
+    /// and its encoded argument data, e.g. as split out of a packet by `de::read_message_raw`.
+    ///
+    /// Fails with `Error::InvalidAddress` if `address` isn't a well-formed pattern,
+    /// or as deserialization would if the arguments don't match the type tag.
+    /// Error offsets count from the start of the type tag, as though it were
+    /// padded and followed by `raw_args`.
+    pub fn from_parts(address: &str, type_tag: &[u8], raw_args: &[u8]) -> ResultE<Self> {
+        validate_pattern(address)?;
+        // Pad the type tag as it would be within a packet.
+        let tag_len = type_tag.len() + 4 - type_tag.len() % 4;
+        let mut tag = type_tag.to_vec();
+        tag.resize(tag_len, 0);
+        let mut reader = Cursor::new(tag).chain(raw_args);
+        let args = read_all_args(&mut reader)?;
+        let unread = reader.get_ref().1.len();
+        if unread != 0 {
+            let offset = tag_len + raw_args.len() - unread;
+            return Err(OscError::BadFormat{ offset, context: "data after last argument" });
+        }
+        Ok(Self {
+            address: address.to_owned(),
+            args,
+        })
+    }
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+    /// Iterate over the '/'-separated parts of the address.
+    /// e.g. "/audio/synth/1" yields "audio", "synth", then "1".
+    pub fn address_components(&self) -> impl Iterator<Item = &str> {
+        // Received messages' addresses aren't validated, so may lack the '/'.
+        let address = self.address.strip_prefix('/').unwrap_or(&self.address);
+        address.split('/')
+    }
+    pub fn args(&self) -> &[OscArg] {
+        &self.args
+    }
+    pub fn args_count(&self) -> usize {
+        self.args.len()
+    }
+    pub fn arg(&self, index: usize) -> Option<&OscArg> {
+        self.args.get(index)
+    }
+    pub fn arg_mut(&mut self, index: usize) -> Option<&mut OscArg> {
+        self.args.get_mut(index)
+    }
+    pub fn push_arg(&mut self, arg: OscArg) {
+        self.args.push(arg);
+    }
+    pub fn pop_arg(&mut self) -> Option<OscArg> {
+        self.args.pop()
+    }
+    /// Insert `arg` at position `index`, shifting later arguments along.
+    /// Panics if `index > self.args_count()`.
+    pub fn insert_arg(&mut self, index: usize, arg: OscArg) {
+        self.args.insert(index, arg);
+    }
+    /// Remove and return the argument at position `index`, shifting later
+    /// arguments back. Panics if `index` is out of bounds.
+    pub fn remove_arg(&mut self, index: usize) -> OscArg {
+        self.args.remove(index)
+    }
+    /// A copy of this message, with `f` applied to the argument at position `index`.
+    /// Fails with `OscError::ArgMiscount` if `index` is out of bounds,
+    /// i.e. the message has fewer than `index + 1` arguments.
+    pub fn map_arg<F: Fn(OscArg) -> OscArg>(&self, index: usize, f: F) -> ResultE<OscMessage> {
+        if index >= self.args.len() {
+            return Err(OscError::ArgMiscount{ expected: index + 1, found: self.args.len() });
+        }
+        let mut msg = self.clone();
+        let arg = msg.args.remove(index);
+        msg.args.insert(index, f(arg));
+        Ok(msg)
+    }
+    /// Remove every argument for which `f` returns false, keeping the rest in order.
+    pub fn retain_args<F: Fn(&OscArg) -> bool>(&mut self, f: F) {
+        self.args.retain(f);
+    }
+    /// If this message's address matches `pattern`, deserialize its
+    /// arguments into `T` (e.g. a tuple, or a struct with one field per argument).
+    /// Returns `None` if the address doesn't match, which is always the case
+    /// for a malformed pattern.
+    ///
+    /// ```
+    /// # use serde_osc::{OscArg, OscMessage};
+    /// let msg = OscMessage::new("/synth/1/freq", vec![OscArg::F32(440.0)]).unwrap();
+    /// let freq: Option<(f32,)> = msg.try_match("/synth/*/freq").map(Result::unwrap);
+    /// assert_eq!(freq, Some((440.0,)));
+    /// ```
+    pub fn try_match<T: DeserializeOwned>(&self, pattern: &str) -> Option<ResultE<T>> {
+        if !match_osc_pattern(pattern, &self.address) {
+            return None;
+        }
+        Some(self.deserialize_args_into())
+    }
+    /// Deserialize this message's arguments into `T` (e.g. a tuple, or a struct
+    /// with one field per argument), regardless of its address.
+    ///
+    /// ```
+    /// # use serde_osc::{OscArg, OscMessage};
+    /// let msg = OscMessage::new("/foo", vec![OscArg::I32(1), OscArg::F32(2.0)]).unwrap();
+    /// assert_eq!(msg.deserialize_args_into::<(i32, f32)>().unwrap(), (1, 2.0));
+    /// ```
+    pub fn deserialize_args_into<T: DeserializeOwned>(&self) -> ResultE<T> {
+        // Go via the wire format, so that arguments convert exactly as they
+        // would had the message been received as `(String, T)`.
+        let packet = to_vec(self, Framing::Unframed)?;
+        let (_, args) = from_slice::<(IgnoredAny, T)>(&packet, Framing::Unframed)?;
+        Ok(args)
+    }
+    /// The type tag this message serializes with, e.g. ",ifs" for
+    /// an `I32`, an `F32` and a `String` argument.
+    pub fn type_string(&self) -> String {
+        let mut tag = String::from(",");
+        for arg in &self.args {
+            arg.push_tag(&mut tag);
+        }
+        tag
+    }
+    /// Number of bytes this message occupies once serialized,
+    /// excluding any framing (i.e. the size of a UDP payload).
+    pub fn byte_len(&self) -> usize {
+        let (tag_len, data_len) = self.tag_and_data_len();
+        padded_str_len(&self.address) + tag_len + data_len
+    }
+    /// Bytes occupied by the padded type tag, and by the argument data.
+    fn tag_and_data_len(&self) -> (usize, usize) {
+        // The type tag begins with a ','.
+        let tag_len = 1 + self.args.iter().map(OscArg::tag_len).sum::<usize>();
+        let data_len = self.args.iter().map(OscArg::wire_size).sum();
+        // The type tag is padded just like a string.
+        (tag_len + 4 - tag_len % 4, data_len)
+    }
+    /// Serialize the address and arguments into buffers sized to hold them,
+    /// ready to be written with any framing.
+    pub(crate) fn to_serializer(&self) -> ResultE<MsgSerializer> {
+        let (tag_len, data_len) = self.tag_and_data_len();
+        let mut ser = MsgSerializer::with_capacity(padded_str_len(&self.address) + tag_len, data_len);
+        ser.write_address(&self.address)?;
+        self.args.serialize(&mut ser)?;
+        Ok(ser)
+    }
+    /// Write this message without any framing, for protocols that delimit packets themselves.
+    /// Equivalent to `to_write(writer, self, Framing::Unframed)`, but serializes
+    /// straight into buffers sized for the message rather than through `to_write`.
+    pub fn serialize_without_length_prefix<W: Write>(&self, writer: &mut W) -> ResultE<()> {
+        self.to_serializer()?.write_into(writer, Framing::Unframed)
+    }
+}
+
+/// The address, type tag and arguments, separated by spaces,
+/// e.g. `/synth/note ,ifs 60 0.5 "sine"`.
+impl fmt::Display for OscMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.address, self.type_string())?;
+        for arg in &self.args {
+            f.write_str(" ")?;
+            arg.fmt_value(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// An OSC bundle: a timetag, and any number of messages or other bundles.
+///
+/// This serializes exactly as a `((u32, u32), Vec<OscPacket>)` would.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OscBundle {
+    timetag: OscTimeTag,
+    elements: Vec<OscPacket>,
+}
+
+impl OscBundle {
+    pub fn new<T: Into<OscTimeTag>>(timetag: T, elements: Vec<OscPacket>) -> Self {
+        Self {
+            timetag: timetag.into(),
+            elements,
+        }
+    }
+    /// Create a bundle with no elements.
+    pub fn empty<T: Into<OscTimeTag>>(timetag: T) -> Self {
+        Self::new(timetag, Vec::new())
+    }
+    pub fn push<P: Into<OscPacket>>(&mut self, element: P) {
+        self.elements.push(element.into());
+    }
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+    pub fn timetag(&self) -> OscTimeTag {
+        self.timetag
+    }
+    pub fn elements(&self) -> &[OscPacket] {
+        &self.elements
+    }
+    pub fn elements_mut(&mut self) -> &mut [OscPacket] {
+        &mut self.elements
+    }
+    /// Number of bytes this bundle occupies once serialized,
+    /// excluding any framing (i.e. the size of a UDP payload).
+    pub fn byte_len(&self) -> usize {
+        // "#bundle", timetag, then each element preceded by its length.
+        8 + 8 + self.elements.iter().map(|elem| 4 + elem.byte_len()).sum::<usize>()
+    }
+    /// Sort the elements into the order in which they're due, recursing into
+    /// nested bundles. Messages are due at this bundle's timetag, and nested
+    /// bundles at their own. The sort is stable, so messages keep their order
+    /// relative to one another.
+    pub fn sort_by_timetag(&mut self) {
+        let timetag = self.timetag;
+        for element in &mut self.elements {
+            if let OscPacket::Bundle(ref mut bundle) = *element {
+                bundle.sort_by_timetag();
+            }
+        }
+        self.elements.sort_by_key(|element| match *element {
+            OscPacket::Message(_) => timetag,
+            OscPacket::Bundle(ref bundle) => bundle.timetag,
+        });
+    }
+    /// Combine the elements of two bundles, those of `self` first.
+    /// The result has the earlier of the two timetags, so messages
+    /// from the later bundle will be executed early.
+    pub fn merge(mut self, other: OscBundle) -> OscBundle {
+        self.timetag = self.timetag.min(other.timetag);
+        self.elements.extend(other.elements);
+        self
+    }
+    /// Every message within this bundle or any nested bundle, paired with the
+    /// timetag of the bundle immediately enclosing it.
+    pub fn flatten(&self) -> Vec<(OscTimeTag, OscMessage)> {
+        let mut messages = Vec::new();
+        self.flatten_into(&mut messages);
+        messages
+    }
+    /// The first message (searching depth-first, in order, through nested
+    /// bundles) whose address is exactly `address`.
+    pub fn find_message(&self, address: &str) -> Option<&OscMessage> {
+        self.elements.iter().filter_map(|element| match *element {
+            OscPacket::Message(ref msg) if msg.address == address => Some(msg),
+            OscPacket::Message(_) => None,
+            OscPacket::Bundle(ref bundle) => bundle.find_message(address),
+        }).next()
+    }
+    /// Every message (including those within nested bundles) whose address
+    /// matches `pattern`, in depth-first order. A malformed pattern matches nothing.
+    pub fn find_all_messages(&self, pattern: &str) -> Vec<&OscMessage> {
+        let mut messages = Vec::new();
+        self.find_all_into(pattern, &mut messages);
+        messages
+    }
+    fn find_all_into<'a>(&'a self, pattern: &str, messages: &mut Vec<&'a OscMessage>) {
+        for element in &self.elements {
+            match *element {
+                OscPacket::Message(ref msg) => if match_osc_pattern(pattern, &msg.address) {
+                    messages.push(msg);
+                },
+                OscPacket::Bundle(ref bundle) => bundle.find_all_into(pattern, messages),
+            }
+        }
+    }
+    fn flatten_into(&self, messages: &mut Vec<(OscTimeTag, OscMessage)>) {
+        for element in &self.elements {
+            match *element {
+                OscPacket::Message(ref msg) => messages.push((self.timetag, msg.clone())),
+                OscPacket::Bundle(ref bundle) => bundle.flatten_into(messages),
+            }
+        }
+    }
+}
+
+impl IntoIterator for OscBundle {
+    type Item = OscPacket;
+    type IntoIter = vec::IntoIter<OscPacket>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.elements.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a OscBundle {
+    type Item = &'a OscPacket;
+    type IntoIter = slice::Iter<'a, OscPacket>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.elements.iter()
+    }
+}
+
+impl Extend<OscArg> for OscMessage {
+    fn extend<I: IntoIterator<Item = OscArg>>(&mut self, iter: I) {
+        self.args.extend(iter);
+    }
+}
+
+impl<P: Into<OscPacket>> Extend<P> for OscBundle {
+    fn extend<I: IntoIterator<Item = P>>(&mut self, iter: I) {
+        self.elements.extend(iter.into_iter().map(Into::into));
+    }
+}
+
+/// Collects packets into a bundle to be executed immediately.
+impl<P: Into<OscPacket>> FromIterator<P> for OscBundle {
+    fn from_iter<I: IntoIterator<Item = P>>(iter: I) -> Self {
+        let mut bundle = OscBundle::empty(OscTimeTag::IMMEDIATE);
+        bundle.extend(iter);
+        bundle
+    }
+}
+
+/// The timetag, followed by each element on its own indented line.
+impl fmt::Display for OscBundle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#bundle @{}", self.timetag)?;
+        for element in &self.elements {
+            // Indent every line, so that nested bundles are indented further.
+            for line in element.to_string().lines() {
+                write!(f, "\n  {}", line)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Either an OSC message or an OSC bundle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OscPacket {
+    Message(OscMessage),
+    Bundle(OscBundle),
+}
+
+impl OscPacket {
+    pub fn is_message(&self) -> bool {
+        self.as_message().is_some()
+    }
+    pub fn is_bundle(&self) -> bool {
+        self.as_bundle().is_some()
+    }
+    pub fn as_message(&self) -> Option<&OscMessage> {
+        match *self {
+            OscPacket::Message(ref msg) => Some(msg),
+            OscPacket::Bundle(_) => None,
+        }
+    }
+    pub fn as_bundle(&self) -> Option<&OscBundle> {
+        match *self {
+            OscPacket::Message(_) => None,
+            OscPacket::Bundle(ref bundle) => Some(bundle),
+        }
+    }
+    /// The address of a message, or `None` for a bundle.
+    pub fn address(&self) -> Option<&str> {
+        self.as_message().map(OscMessage::address)
+    }
+    /// The timetag of a bundle, or `None` for a message.
+    pub fn timetag(&self) -> Option<OscTimeTag> {
+        self.as_bundle().map(OscBundle::timetag)
+    }
+    /// Number of bytes this packet occupies once serialized,
+    /// excluding any framing (i.e. the size of a UDP payload).
+    pub fn byte_len(&self) -> usize {
+        match *self {
+            OscPacket::Message(ref msg) => msg.byte_len(),
+            OscPacket::Bundle(ref bundle) => bundle.byte_len(),
+        }
+    }
+    /// A copy of this packet, with the address of every message (including those
+    /// within nested bundles) replaced by the result of `f`. Bundles keep their
+    /// timetags and the order of their elements. Stops at the first error from `f`.
+    ///
+    /// The new addresses aren't checked until the packet is serialized.
+    ///
+    /// ```
+    /// # use serde_osc::{OscBundle, OscMessage, OscPacket, OscTimeTag};
+    /// let msg = OscMessage::new("/freq", vec![]).unwrap();
+    /// let packet = OscPacket::Bundle(OscBundle::new(OscTimeTag::IMMEDIATE, vec![msg.into()]));
+    /// let proxied = packet.transform_addresses(|address| Ok::<_, ()>(format!("/synth{}", address))).unwrap();
+    /// assert_eq!(proxied.as_bundle().unwrap().elements()[0].as_message().unwrap().address(), "/synth/freq");
+    /// ```
+    pub fn transform_addresses<F, E>(&self, f: F) -> Result<OscPacket, E>
+        where F: Fn(&str) -> Result<String, E>
+    {
+        self.transform_addresses_with(&f)
+    }
+    fn transform_addresses_with<F, E>(&self, f: &F) -> Result<OscPacket, E>
+        where F: Fn(&str) -> Result<String, E>
+    {
+        Ok(match *self {
+            OscPacket::Message(ref msg) => OscPacket::Message(OscMessage {
+                address: f(&msg.address)?,
+                args: msg.args.clone(),
+            }),
+            OscPacket::Bundle(ref bundle) => OscPacket::Bundle(OscBundle {
+                timetag: bundle.timetag,
+                elements: bundle.elements.iter()
+                    .map(|element| element.transform_addresses_with(f))
+                    .collect::<Result<_, E>>()?,
+            }),
+        })
+    }
+}
+
+impl fmt::Display for OscPacket {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            OscPacket::Message(ref msg) => msg.fmt(f),
+            OscPacket::Bundle(ref bundle) => bundle.fmt(f),
+        }
+    }
+}
+
+impl From<OscMessage> for OscPacket {
+    fn from(msg: OscMessage) -> Self {
+        OscPacket::Message(msg)
+    }
+}
+
+impl From<OscBundle> for OscPacket {
+    fn from(bundle: OscBundle) -> Self {
+        OscPacket::Bundle(bundle)
+    }
+}
+
+/// Fails with `BadFormat` if the packet is a bundle.
+impl TryFrom<OscPacket> for OscMessage {
+    type Error = OscError;
+    fn try_from(packet: OscPacket) -> ResultE<Self> {
+        match packet {
+            OscPacket::Message(msg) => Ok(msg),
+            OscPacket::Bundle(_) => Err(OscError::BadFormat{ offset: 0, context: "expected a message, found a bundle" }),
+        }
+    }
+}
+
+/// Fails with `BadFormat` if the packet is a message.
+impl TryFrom<OscPacket> for OscBundle {
+    type Error = OscError;
+    fn try_from(packet: OscPacket) -> ResultE<Self> {
+        match packet {
+            OscPacket::Bundle(bundle) => Ok(bundle),
+            OscPacket::Message(_) => Err(OscError::BadFormat{ offset: 0, context: "expected a bundle, found a message" }),
+        }
+    }
+}
+
+/// Serializes the message as a length-prefixed packet (`Framing::Framed`).
+///
+/// # Panics
+///
+/// If the message can't be serialized, e.g. because its address contains a space.
+impl From<OscMessage> for Vec<u8> {
+    fn from(msg: OscMessage) -> Self {
+        to_vec(&msg, Framing::Framed).expect("OscMessage could not be serialized")
+    }
+}
+
+/// Deserializes a length-prefixed packet (`Framing::Framed`).
+impl TryFrom<Vec<u8>> for OscPacket {
+    type Error = OscError;
+    fn try_from(data: Vec<u8>) -> ResultE<Self> {
+        from_slice(&data, Framing::Framed)
+    }
+}
+
+impl Serialize for OscPacket {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        match *self {
+            OscPacket::Message(ref msg) => msg.serialize(serializer),
+            OscPacket::Bundle(ref bundle) => bundle.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for OscPacket {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        deserializer.deserialize_any(OscPacketVisitor)
+    }
+}
+
+struct OscPacketVisitor;
+
+impl<'de> Visitor<'de> for OscPacketVisitor {
+    type Value = OscPacket;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an OSC message or bundle")
+    }
+
+    /// Messages and bundles are told apart by their first field:
+    /// an address for messages, or a timetag for bundles.
+    fn visit_seq<A>(self, mut seq: A) -> Result<OscPacket, A::Error>
+        where A: SeqAccess<'de>
+    {
+        match seq.next_element()? {
+            Some(PacketHead::Address(address)) => {
+                let args = seq.next_element()?.unwrap_or_default();
+                Ok(OscPacket::Message(OscMessage{ address, args }))
+            },
+            Some(PacketHead::TimeTag(timetag)) => {
+                let elements = seq.next_element()?.unwrap_or_default();
+                Ok(OscPacket::Bundle(OscBundle{ timetag, elements }))
+            },
+            None => Err(de::Error::invalid_length(0, &self)),
+        }
+    }
+}
+
+/// The first field of a packet.
+enum PacketHead {
+    Address(String),
+    TimeTag(OscTimeTag),
+}
+
+impl<'de> Deserialize<'de> for PacketHead {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        deserializer.deserialize_any(PacketHeadVisitor)
+    }
+}
+
+struct PacketHeadVisitor;
+
+impl<'de> Visitor<'de> for PacketHeadVisitor {
+    type Value = PacketHead;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an OSC address or timetag")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<PacketHead, E> {
+        Ok(PacketHead::Address(value.to_owned()))
+    }
+    fn visit_string<E>(self, value: String) -> Result<PacketHead, E> {
+        Ok(PacketHead::Address(value))
+    }
+    fn visit_seq<A>(self, mut seq: A) -> Result<PacketHead, A::Error>
+        where A: SeqAccess<'de>
+    {
+        let sec = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let frac = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        Ok(PacketHead::TimeTag(OscTimeTag{ seconds: sec, fraction: frac }))
+    }
+}
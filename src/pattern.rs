@@ -0,0 +1,246 @@
+//! OSC 1.0 address pattern matching.
+//!
+//! Within each '/'-separated part of an address pattern:
+//!
+//! * `?` matches any single character
+//! * `*` matches any sequence of zero or more characters
+//! * `[abc]` matches any one of the listed characters, and `[a-z]` any in the range.
+//!   A leading `!` (e.g. `[!abc]`) matches any character *not* listed.
+//! * `{foo,bar}` matches any one of the listed strings
+//!
+//! Wildcards never match across a '/'.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Deref;
+
+use address::check_leading_slash;
+use error::{OscError, ResultE};
+
+/// Check whether `address` is matched by `pattern`.
+/// A malformed pattern (e.g. with an unclosed '[') matches nothing.
+pub fn match_osc_pattern(pattern: &str, address: &str) -> bool {
+    let mut patterns = pattern.split('/');
+    let mut names = address.split('/');
+    loop {
+        match (patterns.next(), names.next()) {
+            (Some(pattern), Some(name)) => if !match_component(pattern, name) {
+                return false;
+            },
+            (None, None) => return true,
+            // Different number of components
+            _ => return false,
+        }
+    }
+}
+
+/// Match a single part of an address, which contains no '/'.
+///
+/// Rather than backtracking, which takes exponential time for patterns such
+/// as "*a*a*a*a*b", every position the pattern could have reached is tracked
+/// at once, so the time taken is at most proportional to the product of the
+/// lengths of the pattern and the name.
+fn match_component(pattern: &str, name: &str) -> bool {
+    let tokens = match tokenize(pattern) {
+        Some(tokens) => tokens,
+        None => return false,
+    };
+    let mut states = HashSet::new();
+    add_state(&tokens, &mut states, (0, 0, 0));
+    for c in name.chars() {
+        let mut next = HashSet::new();
+        for &(index, alternative, offset) in &states {
+            let token = match tokens.get(index) {
+                Some(token) => token,
+                // The end of the pattern has been reached, but not of the name.
+                None => continue,
+            };
+            match *token {
+                Token::AnySequence => add_state(&tokens, &mut next, (index, 0, 0)),
+                Token::AnyChar => add_state(&tokens, &mut next, (index + 1, 0, 0)),
+                Token::Char(expected) if expected == c =>
+                    add_state(&tokens, &mut next, (index + 1, 0, 0)),
+                Token::Set(set) if char_in_set(set, c) =>
+                    add_state(&tokens, &mut next, (index + 1, 0, 0)),
+                Token::Alternatives(ref alternatives)
+                    if alternatives[alternative][offset..].starts_with(c) =>
+                    add_state(&tokens, &mut next, (index, alternative, offset + c.len_utf8())),
+                _ => (),
+            }
+        }
+        if next.is_empty() {
+            return false;
+        }
+        states = next;
+    }
+    states.iter().any(|&(index, _, _)| index == tokens.len())
+}
+
+/// A single element of one part of a pattern.
+enum Token<'a> {
+    Char(char),
+    /// `?`
+    AnyChar,
+    /// `*`
+    AnySequence,
+    /// The contents of a `[...]`.
+    Set(&'a str),
+    /// The contents of a `{...}`, split at each ','.
+    Alternatives(Vec<&'a str>),
+}
+
+/// A point partway through matching a pattern: the index of the next token
+/// to match, and (within a `{...}`) which alternative is being matched
+/// and how many bytes of it have been matched so far.
+type State = (usize, usize, usize);
+
+/// Add `state` to `states`, along with every state that can be reached
+/// from it without consuming a character.
+fn add_state(tokens: &[Token], states: &mut HashSet<State>, state: State) {
+    let mut pending = vec![state];
+    while let Some(state) = pending.pop() {
+        if !states.insert(state) {
+            continue;
+        }
+        let (index, alternative, offset) = state;
+        let token = match tokens.get(index) {
+            Some(token) => token,
+            None => continue,
+        };
+        match *token {
+            // '*' may match nothing.
+            Token::AnySequence => pending.push((index + 1, 0, 0)),
+            Token::Alternatives(ref alternatives) => {
+                if offset == 0 {
+                    // Any of the alternatives may be begun.
+                    pending.extend((0..alternatives.len()).map(|other| (index, other, 0)));
+                }
+                if offset == alternatives[alternative].len() {
+                    pending.push((index + 1, 0, 0));
+                }
+            },
+            _ => (),
+        }
+    }
+}
+
+/// Split one part of a pattern into its tokens,
+/// or `None` if a '[' or '{' is never closed.
+fn tokenize<'a>(mut pattern: &'a str) -> Option<Vec<Token<'a>>> {
+    let mut tokens = Vec::new();
+    while let Some(c) = pattern.chars().next() {
+        pattern = &pattern[c.len_utf8()..];
+        let token = match c {
+            '?' => Token::AnyChar,
+            '*' => Token::AnySequence,
+            '[' | '{' => {
+                let close = pattern.find(if c == '[' { ']' } else { '}' })?;
+                let contents = &pattern[..close];
+                pattern = &pattern[close + 1..];
+                if c == '[' {
+                    Token::Set(contents)
+                } else {
+                    Token::Alternatives(contents.split(',').collect())
+                }
+            },
+            c => Token::Char(c),
+        };
+        tokens.push(token);
+    }
+    Some(tokens)
+}
+
+/// Check whether `c` is matched by the contents of a `[...]` pattern.
+fn char_in_set(set: &str, c: char) -> bool {
+    let (negate, set) = match set.chars().next() {
+        Some('!') => (true, &set[1..]),
+        _ => (false, set),
+    };
+    let set: Vec<char> = set.chars().collect();
+    let mut found = false;
+    let mut i = 0;
+    while i < set.len() {
+        // A '-' is only a range if it has a character on either side.
+        if i + 2 < set.len() && set[i+1] == '-' {
+            found |= set[i] <= c && c <= set[i+2];
+            i += 3;
+        } else {
+            found |= set[i] == c;
+            i += 1;
+        }
+    }
+    found != negate
+}
+
+/// An OSC address pattern which is known to be well-formed.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OscAddressPattern(String);
+
+impl OscAddressPattern {
+    /// Check that `pattern` begins with '/', that each '[' and '{' is closed
+    /// within the same part of the address, and that it contains none of the
+    /// characters that are never allowed in addresses.
+    pub fn compile(pattern: &str) -> ResultE<Self> {
+        validate_pattern(pattern)?;
+        Ok(OscAddressPattern(pattern.to_owned()))
+    }
+    pub fn matches(&self, address: &str) -> bool {
+        match_osc_pattern(&self.0, address)
+    }
+}
+
+/// Check that `pattern` is well-formed, as `OscAddressPattern::compile` does.
+/// This is applied to the address of every message, as any of them may be patterns.
+pub(crate) fn validate_pattern(pattern: &str) -> ResultE<()> {
+    check_leading_slash(pattern)?;
+    // The '[' or '{' currently open, if any.
+    let mut open = None;
+    for (position, c) in pattern.char_indices() {
+        let valid = match (open, c) {
+            (_, ' ') | (_, '#') => false,
+            (None, '[') | (None, '{') => {
+                open = Some((position, c));
+                true
+            },
+            (Some((_, '[')), ']') | (Some((_, '{')), '}') => {
+                open = None;
+                true
+            },
+            // Only meaningful within braces.
+            (None, ',') => false,
+            (None, ']') | (None, '}') => false,
+            // No nesting, and wildcards can't cross a '/'.
+            (Some(_), '/') | (Some(_), '[') | (Some(_), '{') |
+            (Some(_), ']') | (Some(_), '}') => false,
+            _ => true,
+        };
+        if !valid {
+            return Err(invalid_char(pattern, position, c));
+        }
+    }
+    match open {
+        Some((position, c)) => Err(invalid_char(pattern, position, c)),
+        None => Ok(()),
+    }
+}
+
+fn invalid_char(pattern: &str, position: usize, invalid_char: char) -> OscError {
+    OscError::InvalidAddress {
+        address: pattern.to_owned(),
+        position,
+        invalid_char,
+    }
+}
+
+impl Deref for OscAddressPattern {
+    type Target = str;
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for OscAddressPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
@@ -0,0 +1,143 @@
+//! Dispatch of incoming messages to handlers, by address pattern.
+
+use std::fmt;
+
+use error::ResultE;
+use packet::{OscMessage, OscPacket};
+use pattern::OscAddressPattern;
+
+type Handler = Box<dyn Fn(&OscMessage) -> ResultE<()>>;
+type DispatchHandler = Box<dyn FnMut(&OscMessage)>;
+
+/// Routes messages to handlers, according to which address pattern each
+/// message's address matches.
+///
+/// Routes are tried in the order they were added, and only the first
+/// matching route's handler is called, so more specific patterns
+/// should be added before more general ones.
+///
+/// ```
+/// # use serde_osc::{OscMessage, OscRouter};
+/// let mut router = OscRouter::new();
+/// router.add_route("/synth/*/freq", |msg| {
+///     println!("frequency: {:?}", msg.arg(0));
+///     Ok(())
+/// }).unwrap();
+/// let msg = OscMessage::new("/synth/1/freq", vec![]).unwrap();
+/// assert!(router.dispatch(&msg).unwrap());
+/// ```
+#[derive(Default)]
+pub struct OscRouter {
+    routes: Vec<(OscAddressPattern, Handler)>,
+}
+
+impl OscRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Call `handler` for messages whose address matches `pattern`.
+    /// Fails with `OscError::InvalidAddress` if the pattern is malformed.
+    pub fn add_route<H>(&mut self, pattern: &str, handler: H) -> ResultE<()>
+        where H: Fn(&OscMessage) -> ResultE<()> + 'static
+    {
+        let pattern = OscAddressPattern::compile(pattern)?;
+        self.routes.push((pattern, Box::new(handler)));
+        Ok(())
+    }
+    /// Pass `msg` to the handler of the first route it matches, returning
+    /// whether there was any such route. Errors from the handler are passed on.
+    pub fn dispatch(&self, msg: &OscMessage) -> ResultE<bool> {
+        match self.routes.iter().find(|route| route.0.matches(msg.address())) {
+            Some(route) => (route.1)(msg).map(|_| true),
+            None => Ok(false),
+        }
+    }
+}
+
+impl fmt::Debug for OscRouter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Handlers can't be printed, so just list the patterns.
+        f.debug_struct("OscRouter")
+            .field("routes", &self.routes.iter().map(|route| &route.0).collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// Identifies a handler added to an `OscDispatcher`, for later removal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct HandlerId(usize);
+
+/// Passes messages to *every* handler whose address pattern they match,
+/// unlike `OscRouter`, which stops at the first.
+///
+/// ```
+/// # use serde_osc::{OscDispatcher, OscMessage};
+/// let mut dispatcher = OscDispatcher::new();
+/// dispatcher.add_handler("/synth/*/freq", |msg| println!("frequency: {:?}", msg.arg(0))).unwrap();
+/// dispatcher.add_handler("/synth/1/*", |msg| println!("synth 1: {}", msg)).unwrap();
+/// let msg = OscMessage::new("/synth/1/freq", vec![]).unwrap();
+/// assert_eq!(dispatcher.dispatch(&msg), 2);
+/// ```
+#[derive(Default)]
+pub struct OscDispatcher {
+    /// Indexed by `HandlerId`. Removed handlers leave a `None`,
+    /// so that the ids of the others remain valid.
+    handlers: Vec<Option<(OscAddressPattern, DispatchHandler)>>,
+}
+
+impl OscDispatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Call `handler` for messages whose address matches `pattern`.
+    /// Fails with `OscError::InvalidAddress` if the pattern is malformed.
+    pub fn add_handler<F>(&mut self, pattern: &str, handler: F) -> ResultE<HandlerId>
+        where F: FnMut(&OscMessage) + 'static
+    {
+        let pattern = OscAddressPattern::compile(pattern)?;
+        self.handlers.push(Some((pattern, Box::new(handler))));
+        Ok(HandlerId(self.handlers.len() - 1))
+    }
+    /// Stop calling the handler identified by `id`.
+    /// Removing an already-removed handler does nothing.
+    pub fn remove_handler(&mut self, id: HandlerId) {
+        if let Some(handler) = self.handlers.get_mut(id.0) {
+            *handler = None;
+        }
+    }
+    /// Pass `msg` to each handler whose pattern it matches, in the order they
+    /// were added. Returns the number of handlers called.
+    pub fn dispatch(&mut self, msg: &OscMessage) -> usize {
+        let mut count = 0;
+        for &mut (ref pattern, ref mut handler) in self.handlers.iter_mut().flatten() {
+            if pattern.matches(msg.address()) {
+                handler(msg);
+                count += 1;
+            }
+        }
+        count
+    }
+    /// Dispatch `packet` if it's a message, or else each message within
+    /// the bundle (including within nested bundles), in order.
+    /// Timetags are ignored: see `BundleDispatcher` to honor them.
+    pub fn dispatch_packet(&mut self, packet: &OscPacket) {
+        match *packet {
+            OscPacket::Message(ref msg) => {
+                self.dispatch(msg);
+            },
+            OscPacket::Bundle(ref bundle) => for element in bundle.elements() {
+                self.dispatch_packet(element);
+            },
+        }
+    }
+}
+
+impl fmt::Debug for OscDispatcher {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Handlers can't be printed, so just list the patterns.
+        let patterns: Vec<_> = self.handlers.iter().flatten().map(|handler| &handler.0).collect();
+        f.debug_struct("OscDispatcher")
+            .field("handlers", &patterns)
+            .finish()
+    }
+}
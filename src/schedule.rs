@@ -0,0 +1,105 @@
+//! Delivery of the messages within bundles at the times their timetags specify.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::fmt;
+
+use packet::{OscBundle, OscMessage};
+use types::OscTimeTag;
+
+/// Holds onto the messages of scheduled bundles until their timetag is reached,
+/// then passes them to a handler.
+///
+/// Each message is due at the timetag of the bundle immediately enclosing it,
+/// so a bundle nested within another is scheduled by its own timetag rather
+/// than its parent's. Messages due at the same time are handled in the
+/// order they were scheduled.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use serde_osc::{BundleDispatcher, OscBundle, OscMessage, OscTimeTag};
+/// let mut dispatcher = BundleDispatcher::new(|msg: &OscMessage| println!("{}", msg));
+/// let later = OscTimeTag::IMMEDIATE + Duration::from_secs(1);
+/// let msg = OscMessage::new("/note/on", vec![]).unwrap();
+/// dispatcher.schedule(OscBundle::new(later, vec![msg.into()]));
+/// assert_eq!(dispatcher.poll(OscTimeTag::IMMEDIATE), 0);
+/// assert_eq!(dispatcher.poll(later), 1);
+/// ```
+pub struct BundleDispatcher<H: Fn(&OscMessage)> {
+    handler: H,
+    pending: BinaryHeap<Scheduled>,
+    /// Number of messages scheduled so far, used to order those due at the same time.
+    scheduled: u64,
+}
+
+/// A message awaiting delivery, ordered such that the `BinaryHeap`
+/// (a max-heap) yields the earliest first.
+struct Scheduled {
+    timetag: OscTimeTag,
+    seq: u64,
+    msg: OscMessage,
+}
+
+impl<H: Fn(&OscMessage)> BundleDispatcher<H> {
+    pub fn new(handler: H) -> Self {
+        Self {
+            handler,
+            pending: BinaryHeap::new(),
+            scheduled: 0,
+        }
+    }
+    /// Hold onto every message within `bundle` (including within nested bundles)
+    /// until its timetag is reached.
+    pub fn schedule(&mut self, bundle: OscBundle) {
+        for (timetag, msg) in bundle.flatten() {
+            self.pending.push(Scheduled{ timetag, seq: self.scheduled, msg });
+            self.scheduled += 1;
+        }
+    }
+    /// Pass every message due at or before `now` to the handler,
+    /// earliest first. Returns the number of messages handled.
+    pub fn poll(&mut self, now: OscTimeTag) -> usize {
+        let mut count = 0;
+        while self.pending.peek().is_some_and(|next| next.timetag <= now) {
+            let next = self.pending.pop().unwrap();
+            (self.handler)(&next.msg);
+            count += 1;
+        }
+        count
+    }
+    /// When the earliest pending message is due, if there are any.
+    pub fn next_timetag(&self) -> Option<OscTimeTag> {
+        self.pending.peek().map(|next| next.timetag)
+    }
+}
+
+impl<H: Fn(&OscMessage)> fmt::Debug for BundleDispatcher<H> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // The handler can't be printed.
+        f.debug_struct("BundleDispatcher")
+            .field("pending", &self.pending.len())
+            .field("next_timetag", &self.next_timetag())
+            .finish()
+    }
+}
+
+impl PartialEq for Scheduled {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Scheduled {}
+
+impl PartialOrd for Scheduled {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Scheduled {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed, so that the earliest is greatest.
+        (other.timetag, other.seq).cmp(&(self.timetag, self.seq))
+    }
+}
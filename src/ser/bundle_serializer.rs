@@ -2,51 +2,105 @@ use std::convert::TryInto;
 use std::io::{Cursor, Write};
 use serde::ser::{Impossible, Serialize, Serializer, SerializeSeq, SerializeStruct, SerializeTuple};
 
-use error::{Error, ResultE};
+use error::{OscError, ResultE};
+use framing::Framing;
 use super::osc_writer::OscWriter;
 use super::pkt_serializer::PktSerializer;
 
+/// Serializes the elements of a bundle into `contents`.
+/// This is either a buffer, from which the bundle is written once complete,
+/// or (if the bundle's length needn't be known in advance) the output itself.
 #[derive(Debug)]
-pub struct BundleSerializer {
-    contents: Cursor<Vec<u8>>,
+pub struct BundleSerializer<W: Write> {
+    contents: W,
 }
 #[derive(Debug)]
-pub struct BundleElemSerializer<'a> {
-    bundle: &'a mut BundleSerializer,
+pub struct BundleElemSerializer<'a, W: Write + 'a> {
+    bundle: &'a mut BundleSerializer<W>,
 }
 
-impl BundleSerializer {
-    pub fn new(contents: Cursor<Vec<u8>>) -> Self {
+impl<W: Write> BundleSerializer<W> {
+    pub fn new(contents: W) -> Self {
         Self {
             contents
         }
     }
-    pub fn write_into<W: Write>(self, output: &mut W) -> ResultE<()> {
+    /// Serialize `value` as the next element.
+    pub fn push_element<T>(&mut self, value: &T) -> ResultE<()>
+        where T: ?Sized + Serialize
+    {
+        // each bundle element is itself a packet, and is always length-prefixed.
+        // Elements may themselves be bundles, so the writer's type is erased
+        // to avoid instantiating PktSerializer<&mut &mut ... W> without end.
+        let contents: &mut dyn Write = &mut self.contents;
+        let mut ser = PktSerializer::new(contents);
+        value.serialize(&mut ser)
+    }
+    /// Append an element that's already been serialized (without framing),
+    /// preceded by its length. See `check_raw_element` for what's validated.
+    pub fn push_raw_bytes(&mut self, bytes: &[u8]) -> ResultE<()> {
+        check_raw_element(bytes)?;
+        self.contents.osc_write_i32(bytes.len().try_into()?)?;
+        Ok(self.contents.write_all(bytes)?)
+    }
+}
+
+/// Check that `bytes` could be an unframed packet, i.e. that it begins with
+/// the '/' of a message's address or the '#' of "#bundle", and is a multiple
+/// of 4 bytes long. The rest of the packet is assumed to be well-formed.
+pub(crate) fn check_raw_element(bytes: &[u8]) -> ResultE<()> {
+    match bytes.first() {
+        Some(&b'/') | Some(&b'#') => (),
+        _ => return Err(OscError::BadFormat{ offset: 0, context: "bundle element must begin with '/' or '#'" }),
+    }
+    if !bytes.len().is_multiple_of(4) {
+        return Err(OscError::BadFormat{ offset: bytes.len(), context: "bundle element not a multiple of 4 bytes" });
+    }
+    Ok(())
+}
+
+impl BundleSerializer<Cursor<Vec<u8>>> {
+    /// Buffer the bundle's contents, with room for `elements_bytes_hint` bytes
+    /// of timetag and elements before reallocating.
+    pub fn with_capacity(elements_bytes_hint: usize) -> Self {
+        Self::new(Cursor::new(Vec::with_capacity(elements_bytes_hint)))
+    }
+    /// Write the bundle's timetag, which must precede its elements.
+    pub(crate) fn write_timetag(&mut self, timetag: (u32, u32)) -> ResultE<()> {
+        self.contents.osc_write_timetag(timetag)
+    }
+    pub fn write_into<W: Write>(self, output: &mut W, framing: Framing) -> ResultE<()> {
         let payload = self.contents.into_inner();
         // Add 8 because we have yet to write the #bundle address
         let payload_size = 8 + payload.len();
         if payload_size % 4 != 0 {
             // Sanity check; OSC requires packets to be a multiple of 4 bytes.
-            return Err(Error::BadFormat);
+            return Err(OscError::BadFormat{ offset: 0, context: "bundle not a multiple of 4 bytes" });
         }
         // Write the packet length
-        output.osc_write_i32(payload_size.try_into()?)?;
+        if framing == Framing::Framed {
+            output.osc_write_i32(payload_size.try_into()?)?;
+        }
         // Write the packet payload
         output.osc_write_str("#bundle")?;
         Ok(output.write_all(&payload)?)
     }
 }
 
-impl<'a> Serializer for &'a mut BundleSerializer {
+impl<'a, W: Write> Serializer for &'a mut BundleSerializer<W> {
     type Ok = ();
-    type Error = Error;
-    type SerializeSeq = BundleElemSerializer<'a>;
+    type Error = OscError;
+    type SerializeSeq = BundleElemSerializer<'a, W>;
     type SerializeTuple = Self::SerializeSeq;
     type SerializeStruct = Self::SerializeSeq;
-    type SerializeTupleStruct = Impossible<Self::Ok, Error>;
-    type SerializeTupleVariant = Impossible<Self::Ok, Error>;
-    type SerializeMap = Impossible<Self::Ok, Error>;
-    type SerializeStructVariant = Impossible<Self::Ok, Error>;
+    type SerializeTupleStruct = Impossible<Self::Ok, OscError>;
+    type SerializeTupleVariant = Impossible<Self::Ok, OscError>;
+    type SerializeMap = Impossible<Self::Ok, OscError>;
+    type SerializeStructVariant = Impossible<Self::Ok, OscError>;
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
     fn serialize_seq(
         self, 
         _size: Option<usize>
@@ -76,25 +130,23 @@ impl<'a> Serializer for &'a mut BundleSerializer {
 }
 
 
-impl<'a> SerializeSeq for BundleElemSerializer<'a> {
+impl<'a, W: Write> SerializeSeq for BundleElemSerializer<'a, W> {
     type Ok = ();
-    type Error = Error;
+    type Error = OscError;
 
     fn serialize_element<'b, T: ?Sized>(&'b mut self, value: &T) -> ResultE<()>
         where T: Serialize
     {
-        // each bundle element is itself a packet.
-        let mut ser = PktSerializer::new(self.bundle.contents.by_ref());
-        value.serialize(&mut ser)
+        self.bundle.push_element(value)
     }
     fn end(self) -> ResultE<()> {
         Ok(())
     }
 }
 
-impl<'a> SerializeStruct for BundleElemSerializer<'a> {
+impl<'a, W: Write> SerializeStruct for BundleElemSerializer<'a, W> {
     type Ok = ();
-    type Error = Error;
+    type Error = OscError;
 
     fn serialize_field<T: ?Sized>(&mut self, _key: &'static str, value: &T) -> ResultE<()>
         where T: Serialize
@@ -107,9 +159,9 @@ impl<'a> SerializeStruct for BundleElemSerializer<'a> {
     }
 }
 
-impl<'a> SerializeTuple for BundleElemSerializer<'a> {
+impl<'a, W: Write> SerializeTuple for BundleElemSerializer<'a, W> {
     type Ok = ();
-    type Error = Error;
+    type Error = OscError;
 
     fn serialize_element<T: ?Sized>(&mut self, value: &T) -> ResultE<()>
         where T: Serialize
@@ -0,0 +1,82 @@
+use std::convert::TryInto;
+use serde::ser::{Impossible, Serialize, Serializer, SerializeTuple};
+
+use error::{OscError, ResultE};
+
+/// Captures the contents of a `[u8; 4]`, i.e. a tuple of exactly four `u8`s.
+/// Used for OSC arguments that consist of a raw 4-byte word, like colors
+/// and MIDI messages.
+#[derive(Debug)]
+pub struct ByteArraySer {
+    n_parsed: u8,
+    parsed: [u8; 4],
+}
+
+impl ByteArraySer {
+    pub fn new() -> Self {
+        ByteArraySer {
+            n_parsed: 0,
+            parsed: [0; 4],
+        }
+    }
+}
+
+impl TryInto<[u8; 4]> for ByteArraySer {
+    type Error = OscError;
+    fn try_into(self) -> ResultE<[u8; 4]> {
+        if self.n_parsed != 4 {
+            return Err(OscError::BadFormat{ offset: 0, context: "expected 4 bytes" });
+        }
+        Ok(self.parsed)
+    }
+}
+
+impl Serializer for &mut ByteArraySer {
+    type Ok = ();
+    type Error = OscError;
+    type SerializeSeq = Impossible<Self::Ok, OscError>;
+    type SerializeTuple = Self;
+    type SerializeStruct = Impossible<Self::Ok, OscError>;
+    type SerializeTupleStruct = Impossible<Self::Ok, OscError>;
+    type SerializeTupleVariant = Impossible<Self::Ok, OscError>;
+    type SerializeMap = Impossible<Self::Ok, OscError>;
+    type SerializeStructVariant = Impossible<Self::Ok, OscError>;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn serialize_u8(self, value: u8) -> ResultE<Self::Ok> {
+        match self.parsed.get_mut(self.n_parsed as usize) {
+            // Already serialized all 4 bytes!
+            None => Err(OscError::BadFormat{ offset: 0, context: "expected 4 bytes" }),
+            Some(byte) => {
+                *byte = value;
+                self.n_parsed += 1;
+                Ok(())
+            }
+        }
+    }
+    fn serialize_tuple(self, _size: usize) -> ResultE<Self::SerializeTuple> {
+        Ok(self)
+    }
+
+    default_ser!{bool i8 i16 i32 i64 u16 u32 u64 f32 f64 char
+        str bytes none some unit unit_struct unit_variant newtype_struct newtype_variant
+        seq tuple_struct tuple_variant map struct struct_variant}
+}
+
+impl SerializeTuple for &mut ByteArraySer {
+    type Ok = ();
+    type Error = OscError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> ResultE<()>
+        where T: Serialize + ?Sized
+    {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> ResultE<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+use std::cmp;
+use std::io;
+use std::io::{ErrorKind, Write};
+
+/// Writer into a fixed-size buffer (e.g. one on the stack), so that the
+/// serialized packet needn't be allocated. Writing beyond the end of the
+/// buffer fails with `ErrorKind::WriteZero`.
+///
+/// Note that the serializer still allocates while assembling each message.
+#[derive(Debug)]
+pub struct FixedBufWriter<'a> {
+    buf: &'a mut [u8],
+    pos: usize,
+}
+
+impl<'a> FixedBufWriter<'a> {
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self{ buf, pos: 0 }
+    }
+    /// The bytes written so far.
+    pub fn written(&self) -> &[u8] {
+        &self.buf[..self.pos]
+    }
+}
+
+impl<'a> Write for FixedBufWriter<'a> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        let remaining = &mut self.buf[self.pos..];
+        if remaining.is_empty() && !data.is_empty() {
+            return Err(io::Error::new(ErrorKind::WriteZero, "buffer full"));
+        }
+        let len = cmp::min(remaining.len(), data.len());
+        remaining[..len].copy_from_slice(&data[..len]);
+        self.pos += len;
+        Ok(len)
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
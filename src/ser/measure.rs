@@ -0,0 +1,47 @@
+use std::io;
+use std::io::Write;
+use serde::ser::Serialize;
+
+use error::ResultE;
+use framing::Framing;
+
+/// Writer that discards everything written to it, counting the bytes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MeasureWriter {
+    count: usize,
+}
+
+impl MeasureWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Number of bytes written so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl Write for MeasureWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.count += buf.len();
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Number of bytes `value` serializes to, including any framing,
+/// e.g. to allocate a buffer of the right size up front.
+pub fn measure_packet_size<T>(value: &T, framing: Framing) -> ResultE<usize>
+    where T: Serialize + ?Sized
+{
+    let mut writer = MeasureWriter::new();
+    if framing == Framing::Framed {
+        // Don't measure the packet twice over; the length prefix is always 4 bytes.
+        super::to_write(&mut writer, value, Framing::Unframed)?;
+        return Ok(4 + writer.count());
+    }
+    super::to_write(&mut writer, value, framing)?;
+    Ok(writer.count())
+}
@@ -1,35 +1,125 @@
+use std::convert::TryInto;
 use std::io::{Cursor, Write};
 use serde;
-use error::ResultE;
+use serde::de::IgnoredAny;
+use pattern::validate_pattern;
+use config::OscConfig;
+use de;
+use error::{OscError, ResultE};
+use framing::Framing;
 
 #[macro_use]
 mod serializer_defaults;
 
-mod bundle_serializer;
+pub(crate) mod bundle_serializer;
+mod byte_array_ser;
+mod fixed_buf;
+mod measure;
 mod pkt_serializer;
 mod pkt_type_decoder;
-mod osc_writer;
-mod msg_serializer;
+pub(crate) mod osc_writer;
+pub(crate) mod msg_serializer;
+mod str_ser;
 mod timetag_ser;
+#[cfg(feature = "tokio")]
+mod write_async;
 
 pub use self::pkt_serializer::PktSerializer as Serializer;
+pub use error::OscError as Error;
+pub use self::fixed_buf::FixedBufWriter;
+pub use self::measure::{measure_packet_size, MeasureWriter};
+use self::osc_writer::OscWriter;
+#[cfg(feature = "tokio")]
+pub use self::write_async::{to_write_async, WritePacket};
 
 /// Serialize `value` into an OSC packet, and write the contents into `write`.
 /// Note that serialization of structs is done only based on the ordering
 /// of fields; their names are not preserved in the output.
-pub fn to_write<S: ?Sized, W: Write>(write: &mut W, value: &S) -> ResultE<()>
-    where W: Write, S: serde::ser::Serialize
+///
+/// `framing` determines whether the packet is prefixed by its length;
+/// see [`Framing`] for which to use.
+///
+/// The elements of a bundle are written out as they're serialized, rather than
+/// the whole packet being held in memory. To achieve this with `Framing::Framed`,
+/// `value` is serialized twice: first to measure its length, then to write it.
+/// Without framing, an error partway through a bundle leaves its earlier elements written.
+///
+/// [`Framing`]: ../framing/enum.Framing.html
+pub fn to_write<S, W>(write: &mut W, value: &S, framing: Framing) -> ResultE<()>
+    where W: Write, S: serde::ser::Serialize + ?Sized
 {
-    let mut ser = Serializer::new(write.by_ref());
+    #[cfg(feature = "tracing")]
+    let _span = tracing::trace_span!("to_write", ?framing).entered();
+    let framing = if framing == Framing::Framed {
+        // Measure the packet first, so that its length can be written ahead of it
+        // without holding the whole packet in memory.
+        let size = measure_packet_size(value, Framing::Unframed)?;
+        #[cfg(feature = "tracing")]
+        tracing::trace!(packet_size_bytes = size, "measured packet");
+        write.osc_write_i32(size.try_into()?)?;
+        Framing::Unframed
+    } else {
+        framing
+    };
+    let mut ser = Serializer::with_framing(write.by_ref(), framing);
     value.serialize(&mut ser)
 }
 
+/// Serialize `value` into an OSC packet, and write the contents into `write`,
+/// provided the packet is within the limits in `config`.
+/// Otherwise, nothing is written and `OscError::BadFormat` is returned.
+///
+/// This guarantees that the packet will be accepted by a receiver using the same config.
+pub fn to_write_with_config<S, W>(
+    write: &mut W,
+    value: &S,
+    framing: Framing,
+    config: &OscConfig
+) -> ResultE<()>
+    where W: Write, S: serde::ser::Serialize + ?Sized
+{
+    let packet = to_vec(value, framing)?;
+    // Check the limits exactly as the receiver would.
+    de::from_read_with_config::<IgnoredAny, _>(Cursor::new(&packet), framing, config)?;
+    Ok(write.write_all(&packet)?)
+}
+
+/// Write an unframed message whose arguments are already encoded, e.g. to
+/// forward a received message without parsing its arguments.
+///
+/// `type_tag` must begin with ',', and `data` must be the arguments it describes,
+/// each padded to a multiple of 4 bytes. Only the address (which may be any
+/// well-formed address pattern) and the type tag's leading ',' are checked,
+/// along with the length of `data`; otherwise this fails with
+/// `OscError::InvalidAddress` or `OscError::BadFormat` respectively.
+pub fn write_message_raw<W: Write>(write: &mut W, address: &str, type_tag: &str, data: &[u8]) -> ResultE<()> {
+    validate_pattern(address)?;
+    if !type_tag.starts_with(',') {
+        return Err(OscError::BadFormat{ offset: 0, context: "type tag must begin with ','" });
+    }
+    if !data.len().is_multiple_of(4) {
+        return Err(OscError::BadFormat{ offset: 0, context: "argument data must be a multiple of 4 bytes" });
+    }
+    write.osc_write_str(address)?;
+    write.osc_write_str(type_tag)?;
+    Ok(write.write_all(data)?)
+}
+
 /// Serializes `value` into a `Vec<u8>` type.
 /// This is a wrapper around the `to_write` function.
-pub fn to_vec<T: ?Sized>(value: &T) -> ResultE<Vec<u8>>
-    where T: serde::ser::Serialize
+pub fn to_vec<T>(value: &T, framing: Framing) -> ResultE<Vec<u8>>
+    where T: serde::ser::Serialize + ?Sized
 {
-    let mut output = Cursor::new(Vec::new());
-    to_write(&mut output, value)?;
-    Ok(output.into_inner())
+    let mut output = Vec::new();
+    if framing == Framing::Framed {
+        // Unlike to_write, we can go back and fill in the length once the
+        // packet's been written, rather than measuring it beforehand.
+        output.extend_from_slice(&[0; 4]);
+        to_write(&mut output, value, Framing::Unframed)?;
+        let size: i32 = (output.len() - 4).try_into()?;
+        output[..4].copy_from_slice(&size.to_be_bytes());
+        return Ok(output);
+    }
+    to_write(&mut output, value, framing)?;
+    Ok(output)
 }
@@ -3,8 +3,14 @@ use std::io::{Cursor, Write};
 use byteorder::WriteBytesExt;
 use serde::ser::{Impossible, Serialize, Serializer, SerializeSeq, SerializeStruct, SerializeTuple};
 
-use error::{Error, ResultE};
+use error::{OscError, ResultE};
+use framing::Framing;
+use pattern::validate_pattern;
+use types::{COLOR_NAME, IMPULSE_NAME, MIDI_NAME, SYMBOL_NAME};
+use super::byte_array_ser::ByteArraySer;
 use super::osc_writer::OscWriter;
+use super::str_ser::StrSer;
+use super::timetag_ser::TimetagSer;
 
 /// Once we know we're serializing a message, we do so through this struct.
 #[derive(Debug)]
@@ -13,11 +19,26 @@ pub struct MsgSerializer {
     addr_typetag: Cursor<Vec<u8>>,
     /// Binary-formatted argument data
     args: Cursor<Vec<u8>>,
+    /// Index of the argument currently being serialized, for error reporting.
+    arg_index: usize,
 }
 
+/// Serializes a single argument (or, via `SerializeSeq`, each of a sequence of arguments)
+/// into the message it borrows for `'msg`.
 #[derive(Debug)]
-pub struct ArgSerializer<'a> {
+pub struct ArgSerializer<'msg> {
+    msg: &'msg mut MsgSerializer,
+}
+
+/// Serializes a nested sequence of arguments as an OSC array,
+/// i.e. surrounded by '[' and ']' in the type tag.
+/// As with bundles, a sequence of exactly two u32s is instead treated as a timetag.
+#[derive(Debug)]
+pub struct ArraySerializer<'a> {
     msg: &'a mut MsgSerializer,
+    /// Until we know whether the sequence is a timetag,
+    /// its leading u32s are captured here rather than written.
+    timetag: Option<TimetagSer>,
 }
 
 impl MsgSerializer {
@@ -27,20 +48,49 @@ impl MsgSerializer {
         Ok(Self {
             addr_typetag: address,
             args: Cursor::new(Vec::new()),
+            arg_index: 0,
         })
     }
-    pub fn write_into<W: Write>(self, output: &mut W) -> ResultE<()> {
+    /// A message with no address yet, with room for `addr_typetag_capacity` bytes
+    /// of (padded) address and type tag, and `args_capacity` bytes of argument data.
+    /// Its address must be given by `write_address` before any arguments are serialized.
+    pub fn with_capacity(addr_typetag_capacity: usize, args_capacity: usize) -> Self {
+        Self {
+            addr_typetag: Cursor::new(Vec::with_capacity(addr_typetag_capacity)),
+            args: Cursor::new(Vec::with_capacity(args_capacity)),
+            arg_index: 0,
+        }
+    }
+    /// Write the address of a message created by `with_capacity`,
+    /// checking that it's a well-formed address pattern.
+    pub(crate) fn write_address(&mut self, address: &str) -> ResultE<()> {
+        validate_pattern(address)?;
+        self.addr_typetag.osc_write_str(address)?;
+        Ok(self.addr_typetag.write_u8(b',')?)
+    }
+    /// Make room for `count` more arguments, to avoid reallocating as they're added.
+    /// Each has a 1-byte type tag, and most have 4 or more bytes of data.
+    fn reserve_args(&mut self, count: usize) {
+        self.addr_typetag.get_mut().reserve(count);
+        self.args.get_mut().reserve(4 * count);
+    }
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all, fields(framing = ?framing)))]
+    pub fn write_into<W: Write>(self, output: &mut W, framing: Framing) -> ResultE<()> {
         let typetag = self.addr_typetag.into_inner();
         let args = self.args.into_inner();
         let tag_pad = 4 - (typetag.len() % 4);
         let payload_size = typetag.len() + tag_pad + args.len();
         if payload_size % 4 != 0 {
             // Sanity check; OSC requires packets to be a multiple of 4 bytes.
-            return Err(Error::BadFormat);
+            return Err(OscError::BadFormat{ offset: 0, context: "message not a multiple of 4 bytes" });
         }
+        #[cfg(feature = "tracing")]
+        trace_message(&typetag, payload_size);
 
         // Write the packet length
-        output.osc_write_i32(payload_size.try_into()?)?;
+        if framing == Framing::Framed {
+            output.osc_write_i32(payload_size.try_into()?)?;
+        }
         // Write the address and type tag
         output.write_all(&typetag)?;
         let zeros = b"\0\0\0\0";
@@ -50,22 +100,42 @@ impl MsgSerializer {
     }
 }
 
+/// Log the address and type tag held in `addr_typetag`.
+#[cfg(feature = "tracing")]
+fn trace_message(addr_typetag: &[u8], packet_size_bytes: usize) {
+    let address_len = addr_typetag.iter().position(|&b| b == 0).unwrap_or(addr_typetag.len());
+    // The type tag follows the address's terminator and padding.
+    let type_string = &addr_typetag[(address_len / 4 + 1) * 4..];
+    tracing::trace!(
+        address = %String::from_utf8_lossy(&addr_typetag[..address_len]),
+        type_string = %String::from_utf8_lossy(type_string),
+        packet_size_bytes,
+        "writing message");
+}
+
 impl<'a> Serializer for &'a mut MsgSerializer {
     type Ok = ();
-    type Error = Error;
+    type Error = OscError;
     type SerializeSeq = ArgSerializer<'a>;
     type SerializeTuple = Self::SerializeSeq;
     type SerializeStruct = Self::SerializeSeq;
-    type SerializeTupleStruct = Impossible<Self::Ok, Error>;
-    type SerializeTupleVariant = Impossible<Self::Ok, Error>;
-    type SerializeMap = Impossible<Self::Ok, Error>;
-    type SerializeStructVariant = Impossible<Self::Ok, Error>;
+    type SerializeTupleStruct = Impossible<Self::Ok, OscError>;
+    type SerializeTupleVariant = Impossible<Self::Ok, OscError>;
+    type SerializeMap = Impossible<Self::Ok, OscError>;
+    type SerializeStructVariant = Impossible<Self::Ok, OscError>;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
 
     fn serialize_seq(
         self, 
-        _size: Option<usize>
+        size: Option<usize>
     ) -> ResultE<Self::SerializeSeq>
     {
+        if let Some(count) = size {
+            self.reserve_args(count);
+        }
         Ok(ArgSerializer{ msg: self })
     }
     fn serialize_tuple(
@@ -100,59 +170,156 @@ impl<'a> Serializer for &'a mut MsgSerializer {
         tuple_struct tuple_variant map struct_variant}
 }
 
-impl<'a> Serializer for &'a mut ArgSerializer<'a> {
+// The serializer need only be borrowed for as long as the array serializer
+// it may create; that needn't be as long as the message is borrowed.
+impl<'a, 'msg> Serializer for &'a mut ArgSerializer<'msg> {
     type Ok = ();
-    type Error = Error;
-    type SerializeSeq = Impossible<Self::Ok, Error>;
+    type Error = OscError;
+    type SerializeSeq = ArraySerializer<'a>;
     type SerializeTuple = Self::SerializeSeq;
-    type SerializeStruct = Self::SerializeSeq;
-    type SerializeTupleStruct = Impossible<Self::Ok, Error>;
-    type SerializeTupleVariant = Impossible<Self::Ok, Error>;
-    type SerializeMap = Impossible<Self::Ok, Error>;
-    type SerializeStructVariant = Impossible<Self::Ok, Error>;
+    type SerializeStruct = Impossible<Self::Ok, OscError>;
+    type SerializeTupleStruct = Impossible<Self::Ok, OscError>;
+    type SerializeTupleVariant = Impossible<Self::Ok, OscError>;
+    type SerializeMap = Impossible<Self::Ok, OscError>;
+    type SerializeStructVariant = Impossible<Self::Ok, OscError>;
 
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn serialize_bool(self, value: bool) -> ResultE<Self::Ok> {
+        self.msg.addr_typetag.write_bool_tag(value)
+    }
     fn serialize_i32(self, value: i32) -> ResultE<Self::Ok> {
         self.msg.addr_typetag.write_i32_tag()?;
-        Ok(self.msg.args.osc_write_i32(value)?)
+        self.msg.args.osc_write_i32(value)
+    }
+    fn serialize_u32(self, value: u32) -> ResultE<Self::Ok> {
+        self.msg.addr_typetag.write_u32_tag()?;
+        self.msg.args.osc_write_u32(value)
+    }
+    fn serialize_i64(self, value: i64) -> ResultE<Self::Ok> {
+        self.msg.addr_typetag.write_i64_tag()?;
+        self.msg.args.osc_write_i64(value)
     }
     fn serialize_f32(self, value: f32) -> ResultE<Self::Ok> {
         self.msg.addr_typetag.write_f32_tag()?;
-        Ok(self.msg.args.osc_write_f32(value)?)
+        self.msg.args.osc_write_f32(value)
+    }
+    fn serialize_f64(self, value: f64) -> ResultE<Self::Ok> {
+        self.msg.addr_typetag.write_f64_tag()?;
+        self.msg.args.osc_write_f64(value)
     }
     fn serialize_str(self, value: &str) -> ResultE<Self::Ok> {
         self.msg.addr_typetag.write_str_tag()?;
-        Ok(self.msg.args.osc_write_str(value)?)
+        self.msg.args.osc_write_str(value)
+    }
+    fn serialize_char(self, value: char) -> ResultE<Self::Ok> {
+        // OSC chars are ASCII only.
+        if !value.is_ascii() {
+            return Err(OscError::UnsupportedType{ type_code: b'c', arg_index: self.msg.arg_index });
+        }
+        self.msg.addr_typetag.write_char_tag()?;
+        self.msg.args.osc_write_char(value)
     }
     fn serialize_bytes(self, value: &[u8]) -> ResultE<Self::Ok> {
         self.msg.addr_typetag.write_blob_tag()?;
-        Ok(self.msg.args.osc_write_blob(value)?)
+        self.msg.args.osc_write_blob(value)
+    }
+    // Both None and () are sent as Nil.
+    fn serialize_none(self) -> ResultE<Self::Ok> {
+        self.msg.addr_typetag.write_nil_tag()
+    }
+    fn serialize_some<T>(self, value: &T) -> ResultE<Self::Ok>
+        where T: Serialize + ?Sized
+    {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> ResultE<Self::Ok> {
+        self.msg.addr_typetag.write_nil_tag()
+    }
+    // OSC types that have no serde equivalent are represented by newtypes,
+    // which we identify by name.
+    fn serialize_newtype_struct<T>(
+        self,
+        name: &'static str,
+        value: &T
+    ) -> ResultE<Self::Ok>
+        where T: Serialize + ?Sized
+    {
+        match name {
+            COLOR_NAME => {
+                let mut rgba = ByteArraySer::new();
+                value.serialize(&mut rgba)?;
+                self.msg.addr_typetag.write_color_tag()?;
+                Ok(self.msg.args.osc_write_color(rgba.try_into()?)?)
+            },
+            MIDI_NAME => {
+                let mut midi = ByteArraySer::new();
+                value.serialize(&mut midi)?;
+                self.msg.addr_typetag.write_midi_tag()?;
+                Ok(self.msg.args.osc_write_midi(midi.try_into()?)?)
+            },
+            SYMBOL_NAME => {
+                let mut symbol = StrSer::new();
+                value.serialize(&mut symbol)?;
+                let symbol: String = symbol.try_into()?;
+                self.msg.addr_typetag.write_symbol_tag()?;
+                Ok(self.msg.args.osc_write_str(&symbol)?)
+            },
+            _ => Err(OscError::UnsupportedValue),
+        }
+    }
+    // Data-less OSC types are represented by unit structs.
+    fn serialize_unit_struct(self, name: &'static str) -> ResultE<Self::Ok> {
+        match name {
+            IMPULSE_NAME => self.msg.addr_typetag.write_impulse_tag(),
+            _ => Err(OscError::UnsupportedValue),
+        }
+    }
+    // Nested sequences become OSC arrays.
+    fn serialize_seq(
+        self,
+        _size: Option<usize>
+    ) -> ResultE<Self::SerializeSeq>
+    {
+        Ok(ArraySerializer{ msg: &mut *self.msg, timetag: Some(TimetagSer::new()) })
+    }
+    fn serialize_tuple(
+        self,
+        size: usize
+    ) -> ResultE<Self::SerializeTuple>
+    {
+        self.serialize_seq(Some(size))
     }
-    default_ser!{bool i8 i16 i64 u8 u16 u32 u64 f64 char
-        none some unit unit_struct unit_variant newtype_struct newtype_variant
-        seq tuple tuple_struct tuple_variant map struct struct_variant}
+    default_ser!{i8 i16 u8 u16 u64
+        unit_variant newtype_variant
+        tuple_struct tuple_variant map struct struct_variant}
 }
 
-impl<'a> SerializeSeq for ArgSerializer<'a> {
+impl<'msg> SerializeSeq for ArgSerializer<'msg> {
     type Ok = ();
-    type Error = Error;
+    type Error = OscError;
 
-    fn serialize_element<'b, T: ?Sized>(&'b mut self, value: &T) -> ResultE<()>
-        where T: Serialize
+    fn serialize_element<'b, T>(&'b mut self, value: &T) -> ResultE<()>
+        where T: Serialize + ?Sized
     {
         // each element is an OSC arg: i32, f32, etc.
-        value.serialize(&mut ArgSerializer{ msg: self.msg })
+        value.serialize(&mut ArgSerializer{ msg: self.msg })?;
+        self.msg.arg_index += 1;
+        Ok(())
     }
     fn end(self) -> ResultE<()> {
         Ok(())
     }
 }
 
-impl<'a> SerializeStruct for ArgSerializer<'a> {
+impl<'msg> SerializeStruct for ArgSerializer<'msg> {
     type Ok = ();
-    type Error = Error;
+    type Error = OscError;
 
-    fn serialize_field<T: ?Sized>(&mut self, _key: &'static str, value: &T) -> ResultE<()>
-        where T: Serialize
+    fn serialize_field<T>(&mut self, _key: &'static str, value: &T) -> ResultE<()>
+        where T: Serialize + ?Sized
     {
         SerializeSeq::serialize_element(self, value)
     }
@@ -162,12 +329,74 @@ impl<'a> SerializeStruct for ArgSerializer<'a> {
     }
 }
 
-impl<'a> SerializeTuple for ArgSerializer<'a> {
+impl<'msg> SerializeTuple for ArgSerializer<'msg> {
+    type Ok = ();
+    type Error = OscError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> ResultE<()>
+        where T: Serialize + ?Sized
+    {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> ResultE<()> {
+        SerializeSeq::end(self)
+    }
+}
+
+impl<'a> ArraySerializer<'a> {
+    /// Commit to serializing an array rather than a timetag:
+    /// write the start of the array, followed by any elements captured so far.
+    fn begin_array(&mut self) -> ResultE<()> {
+        if let Some(timetag) = self.timetag.take() {
+            self.msg.addr_typetag.write_array_start_tag()?;
+            let mut arg = ArgSerializer{ msg: self.msg };
+            for part in timetag.parsed() {
+                part.serialize(&mut arg)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> SerializeSeq for ArraySerializer<'a> {
+    type Ok = ();
+    type Error = OscError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> ResultE<()>
+        where T: Serialize + ?Sized
+    {
+        if let Some(ref mut timetag) = self.timetag {
+            // TimetagSer only accepts u32s, and rejects anything else
+            // without capturing any part of it.
+            if value.serialize(timetag).is_ok() {
+                return Ok(());
+            }
+        }
+        self.begin_array()?;
+        // each element is an OSC arg, which may itself be an array.
+        value.serialize(&mut ArgSerializer{ msg: self.msg })
+    }
+    fn end(mut self) -> ResultE<()> {
+        match self.timetag.clone().map(TryInto::try_into) {
+            Some(Ok(timetag)) => {
+                self.msg.addr_typetag.write_timetag_tag()?;
+                Ok(self.msg.args.osc_write_timetag(timetag)?)
+            },
+            _ => {
+                self.begin_array()?;
+                self.msg.addr_typetag.write_array_end_tag()
+            },
+        }
+    }
+}
+
+impl<'a> SerializeTuple for ArraySerializer<'a> {
     type Ok = ();
-    type Error = Error;
+    type Error = OscError;
 
-    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> ResultE<()>
-        where T: Serialize
+    fn serialize_element<T>(&mut self, value: &T) -> ResultE<()>
+        where T: Serialize + ?Sized
     {
         SerializeSeq::serialize_element(self, value)
     }
@@ -2,7 +2,7 @@ use std::convert::TryInto;
 use std::io::Write;
 use byteorder::{BigEndian, WriteBytesExt};
 
-use error::ResultE;
+use error::{OscError, ResultE};
 
 /// auto-implemented trait to write OSC data to a Write object.
 pub trait OscWriter: Write {
@@ -12,12 +12,30 @@ pub trait OscWriter: Write {
     fn write_i32_tag(&mut self) -> ResultE<()> {
         Ok(self.write_u8(b'i')?)
     }
+    fn osc_write_u32(&mut self, value: u32) -> ResultE<()> {
+        Ok(self.write_u32::<BigEndian>(value)?)
+    }
+    fn write_u32_tag(&mut self) -> ResultE<()> {
+        Ok(self.write_u8(b'u')?)
+    }
+    fn osc_write_i64(&mut self, value: i64) -> ResultE<()> {
+        Ok(self.write_i64::<BigEndian>(value)?)
+    }
+    fn write_i64_tag(&mut self) -> ResultE<()> {
+        Ok(self.write_u8(b'h')?)
+    }
     fn osc_write_f32(&mut self, value: f32) -> ResultE<()> {
         Ok(self.write_f32::<BigEndian>(value)?)
     }
     fn write_f32_tag(&mut self) -> ResultE<()> {
         Ok(self.write_u8(b'f')?)
     }
+    fn osc_write_f64(&mut self, value: f64) -> ResultE<()> {
+        Ok(self.write_f64::<BigEndian>(value)?)
+    }
+    fn write_f64_tag(&mut self) -> ResultE<()> {
+        Ok(self.write_u8(b'd')?)
+    }
     fn osc_write_str(&mut self, value: &str) -> ResultE<()> {
         self.write_all(value.as_bytes())?;
         // pad to 4-byte boundary, PLUS ensure we have at least one null terminator.
@@ -28,6 +46,10 @@ pub trait OscWriter: Write {
     fn write_str_tag(&mut self) -> ResultE<()> {
         Ok(self.write_u8(b's')?)
     }
+    /// Symbols are encoded just like strings.
+    fn write_symbol_tag(&mut self) -> ResultE<()> {
+        Ok(self.write_u8(b'S')?)
+    }
     fn osc_write_blob(&mut self, value: &[u8]) -> ResultE<()> {
         // write the blob length (yes, as an i32)
         self.write_i32::<BigEndian>(value.len().try_into()?)?;
@@ -39,6 +61,50 @@ pub trait OscWriter: Write {
     fn write_blob_tag(&mut self) -> ResultE<()> {
         Ok(self.write_u8(b'b')?)
     }
+    /// OSC chars are ASCII only, stored in the low byte of a 32-bit word.
+    /// The argument's index isn't known here, so is reported as 0.
+    fn osc_write_char(&mut self, value: char) -> ResultE<()> {
+        if !value.is_ascii() {
+            return Err(OscError::UnsupportedType{ type_code: b'c', arg_index: 0 });
+        }
+        Ok(self.write_u32::<BigEndian>(value as u32)?)
+    }
+    fn write_char_tag(&mut self) -> ResultE<()> {
+        Ok(self.write_u8(b'c')?)
+    }
+    /// Colors are 4 bytes (RGBA) and hence need no padding.
+    fn osc_write_color(&mut self, rgba: [u8; 4]) -> ResultE<()> {
+        Ok(self.write_all(&rgba)?)
+    }
+    fn write_color_tag(&mut self) -> ResultE<()> {
+        Ok(self.write_u8(b'r')?)
+    }
+    /// MIDI messages are always 4 bytes, so unlike blobs they need no length prefix or padding.
+    fn osc_write_midi(&mut self, msg: [u8; 4]) -> ResultE<()> {
+        Ok(self.write_all(&msg)?)
+    }
+    fn write_midi_tag(&mut self) -> ResultE<()> {
+        Ok(self.write_u8(b'm')?)
+    }
+    /// Booleans are encoded entirely in their type tag ('T' or 'F'); they carry no data.
+    fn write_bool_tag(&mut self, value: bool) -> ResultE<()> {
+        Ok(self.write_u8(if value { b'T' } else { b'F' })?)
+    }
+    /// Nil carries no data; the type tag alone indicates an absent value.
+    fn write_nil_tag(&mut self) -> ResultE<()> {
+        Ok(self.write_u8(b'N')?)
+    }
+    /// Impulses are, like Nil, just a type tag.
+    fn write_impulse_tag(&mut self) -> ResultE<()> {
+        Ok(self.write_u8(b'I')?)
+    }
+    /// Arrays are delimited by '[' and ']' in the type tag; the delimiters have no data.
+    fn write_array_start_tag(&mut self) -> ResultE<()> {
+        Ok(self.write_u8(b'[')?)
+    }
+    fn write_array_end_tag(&mut self) -> ResultE<()> {
+        Ok(self.write_u8(b']')?)
+    }
     /// Write the OSC timetag, characterized by a (u32, u32) pair.
     /// The first u32 is the seconds, second is fraction of seconds.
     fn osc_write_timetag(&mut self, tag: (u32, u32)) -> ResultE<()> {
@@ -46,6 +112,9 @@ pub trait OscWriter: Write {
         self.write_u32::<BigEndian>(tag.1)?;
         Ok(())
     }
+    fn write_timetag_tag(&mut self) -> ResultE<()> {
+        Ok(self.write_u8(b't')?)
+    }
 }
 
 /// Provide OSC writing functions to all types implementing Write
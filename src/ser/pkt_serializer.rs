@@ -1,9 +1,11 @@
-use std::io::Write;
+use std::io::{Cursor, Write};
 use serde::ser::{Impossible, Serialize, Serializer, SerializeSeq, SerializeStruct, SerializeTuple};
 
-use error::{Error, ResultE};
+use error::{OscError, ResultE};
+use framing::{slip, Framing};
 use super::bundle_serializer::BundleSerializer;
 use super::msg_serializer::MsgSerializer;
+use super::osc_writer::OscWriter;
 use super::pkt_type_decoder::{PktType, PktTypeDecoder};
 
 /// Serializes an entire OSC packet, which contains either one message or one
@@ -67,6 +69,7 @@ use super::pkt_type_decoder::{PktType, PktTypeDecoder};
 #[derive(Debug)]
 pub struct PktSerializer<W: Write> {
     output: W,
+    framing: Framing,
 }
 
 /// After the State receives a serialize_seq call,
@@ -84,30 +87,67 @@ pub struct PktContents<'a, W: Write + 'a> {
 enum State {
     UnknownType,
     Msg(MsgSerializer),
-    Bundle(BundleSerializer),
+    Bundle(BundleSerializer<Cursor<Vec<u8>>>),
+    /// A bundle whose header has already been written to the output, followed
+    /// by each element as it's serialized. This is only possible without framing:
+    /// otherwise the bundle's length must be known before anything is written.
+    StreamedBundle,
 }
 
 
+impl State {
+    /// Write the packet header & data to the output.
+    fn write_into<W: Write>(self, output: &mut W, framing: Framing) -> ResultE<()> {
+        match self {
+            State::UnknownType => Err(OscError::BadFormat{ offset: 0, context: "packet is neither a message nor a bundle" }),
+            State::Msg(msg) => msg.write_into(output, framing),
+            State::Bundle(bundle) => bundle.write_into(output, framing),
+            State::StreamedBundle => Ok(()),
+        }
+    }
+}
+
 impl<W: Write> PktSerializer<W> {
+    /// Create a serializer that prefixes the packet with its length.
     pub fn new(output: W) -> Self {
-        Self{ output }
+        Self::with_framing(output, Framing::Framed)
+    }
+    pub fn with_framing(output: W, framing: Framing) -> Self {
+        Self{ output, framing }
+    }
+    /// Get a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.output
+    }
+    /// Get a mutable reference to the underlying writer.
+    /// Writing to it directly will interleave with any packets serialized later.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.output
+    }
+    /// Unwrap this serializer, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.output
     }
 }
 
 impl<'a, W: Write> Serializer for &'a mut PktSerializer<W> {
     type Ok = ();
-    type Error = Error;
+    type Error = OscError;
     type SerializeSeq = PktContents<'a, W>;
     type SerializeTuple = Self::SerializeSeq;
     type SerializeStruct = Self::SerializeSeq;
-    type SerializeTupleStruct = Impossible<Self::Ok, Error>;
-    type SerializeTupleVariant = Impossible<Self::Ok, Error>;
-    type SerializeMap = Impossible<Self::Ok, Error>;
-    type SerializeStructVariant = Impossible<Self::Ok, Error>;
+    type SerializeTupleStruct = Impossible<Self::Ok, OscError>;
+    type SerializeTupleVariant = Impossible<Self::Ok, OscError>;
+    type SerializeMap = Impossible<Self::Ok, OscError>;
+    type SerializeStructVariant = Impossible<Self::Ok, OscError>;
 
     default_ser!{bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char
         str bytes none some unit unit_struct unit_variant newtype_struct newtype_variant
         tuple_struct tuple_variant map struct_variant}
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
     fn serialize_seq(
         self, 
         _size: Option<usize>
@@ -135,7 +175,7 @@ impl<'a, W: Write> Serializer for &'a mut PktSerializer<W> {
 
 impl<'a, W: Write + 'a> SerializeSeq for PktContents<'a, W> {
     type Ok = ();
-    type Error = Error;
+    type Error = OscError;
 
     fn serialize_element<'b, T: ?Sized>(&'b mut self, value: &T) -> ResultE<()>
         where T: Serialize
@@ -151,13 +191,20 @@ impl<'a, W: Write + 'a> SerializeSeq for PktContents<'a, W> {
                 value.serialize(&mut decoder)?;
 
                 match decoder.pkt_type() {
-                    PktType::Unknown => Err(Error::BadFormat),
+                    PktType::Unknown => Err(OscError::BadFormat{ offset: 0, context: "packet is neither a message nor a bundle" }),
                     PktType::Msg => {
                         self.state = State::Msg(MsgSerializer::new(
                             decoder.data()
                         )?);
                         Ok(())
                     },
+                    PktType::Bundle if self.output.framing == Framing::Unframed => {
+                        // The decoder holds the timetag.
+                        self.output.output.osc_write_str("#bundle")?;
+                        self.output.output.write_all(&decoder.data().into_inner())?;
+                        self.state = State::StreamedBundle;
+                        Ok(())
+                    },
                     PktType::Bundle => {
                         self.state = State::Bundle(BundleSerializer::new(
                             decoder.data()
@@ -172,28 +219,33 @@ impl<'a, W: Write + 'a> SerializeSeq for PktContents<'a, W> {
             State::Bundle(ref mut bundle) => {
                 value.serialize(bundle)
             },
+            State::StreamedBundle => {
+                // Erasing the writer's type means nested bundles (which are buffered)
+                // don't instantiate a new BundleSerializer per level of nesting.
+                let output: &mut dyn Write = &mut self.output.output;
+                value.serialize(&mut BundleSerializer::new(output))
+            },
         }
     }
 
     fn end(self) -> ResultE<()> {
         match self.state {
             // Packet has no contents!
-            State::UnknownType => Err(Error::BadFormat),
-            // Write the message header & data to the output
-            State::Msg(msg) => {
-                msg.write_into(&mut self.output.output)
+            State::UnknownType => Err(OscError::BadFormat{ offset: 0, context: "packet is neither a message nor a bundle" }),
+            // SLIP escapes the packet as a whole, so it must be encoded in full first.
+            state if self.output.framing == Framing::Slip => {
+                let mut packet = Vec::new();
+                state.write_into(&mut packet, Framing::Unframed)?;
+                Ok(self.output.output.write_all(&slip::encode(&packet))?)
             },
-            // Write the bundle header & data to the output
-            State::Bundle(bundle) => {
-                bundle.write_into(&mut self.output.output)
-            }
+            state => state.write_into(&mut self.output.output, self.output.framing),
         }
     }
 }
 
 impl<'a, W: Write + 'a> SerializeStruct for PktContents<'a, W> {
     type Ok = ();
-    type Error = Error;
+    type Error = OscError;
 
     fn serialize_field<T: ?Sized>(&mut self, _key: &'static str, value: &T) -> ResultE<()>
         where T: Serialize
@@ -208,7 +260,7 @@ impl<'a, W: Write + 'a> SerializeStruct for PktContents<'a, W> {
 
 impl<'a, W: Write + 'a> SerializeTuple for PktContents<'a, W> {
     type Ok = ();
-    type Error = Error;
+    type Error = OscError;
 
     fn serialize_element<T: ?Sized>(&mut self, value: &T) -> ResultE<()>
         where T: Serialize
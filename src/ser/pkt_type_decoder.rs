@@ -2,7 +2,8 @@ use std::convert::TryInto;
 use std::io::Cursor;
 use serde::ser::{Impossible, Serialize, Serializer, SerializeSeq, SerializeStruct, SerializeTuple};
 
-use error::{Error, ResultE};
+use pattern::validate_pattern;
+use error::{OscError, ResultE};
 use super::osc_writer::OscWriter;
 use super::timetag_ser::TimetagSer;
 
@@ -45,16 +46,21 @@ impl PktTypeDecoder {
 
 impl<'a> Serializer for &'a mut PktTypeDecoder {
     type Ok = ();
-    type Error = Error;
+    type Error = OscError;
     type SerializeSeq = TimetagSeqSer<'a>;
     type SerializeTuple = Self::SerializeSeq;
     type SerializeStruct = Self::SerializeSeq;
-    type SerializeTupleStruct = Impossible<Self::Ok, Error>;
-    type SerializeTupleVariant = Impossible<Self::Ok, Error>;
-    type SerializeMap = Impossible<Self::Ok, Error>;
-    type SerializeStructVariant = Impossible<Self::Ok, Error>;
+    type SerializeTupleStruct = Impossible<Self::Ok, OscError>;
+    type SerializeTupleVariant = Impossible<Self::Ok, OscError>;
+    type SerializeMap = Impossible<Self::Ok, OscError>;
+    type SerializeStructVariant = Impossible<Self::Ok, OscError>;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
 
     fn serialize_str(self, value: &str) -> ResultE<Self::Ok> {
+        validate_pattern(value)?;
         self.output.osc_write_str(value)?;
         self.pkt_type = PktType::Msg;
         Ok(())
@@ -96,7 +102,7 @@ pub struct TimetagSeqSer<'a> {
 
 impl<'a> SerializeSeq for TimetagSeqSer<'a> {
     type Ok = ();
-    type Error = Error;
+    type Error = OscError;
 
     fn serialize_element<T: ?Sized>(&mut self, value: &T) -> ResultE<()>
         where T: Serialize
@@ -113,7 +119,7 @@ impl<'a> SerializeSeq for TimetagSeqSer<'a> {
 
 impl<'a> SerializeStruct for TimetagSeqSer<'a> {
     type Ok = ();
-    type Error = Error;
+    type Error = OscError;
 
     fn serialize_field<T: ?Sized>(&mut self, _key: &'static str, value: &T) -> ResultE<()>
         where T: Serialize
@@ -128,7 +134,7 @@ impl<'a> SerializeStruct for TimetagSeqSer<'a> {
 
 impl<'a> SerializeTuple for TimetagSeqSer<'a> {
     type Ok = ();
-    type Error = Error;
+    type Error = OscError;
 
     fn serialize_element<T: ?Sized>(&mut self, value: &T) -> ResultE<()>
         where T: Serialize
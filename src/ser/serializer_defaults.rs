@@ -5,7 +5,7 @@
 macro_rules! default_ser_one {
     ($result:ident, $func:ident($($arg:ty),*)) => {
         fn $func(self, $(_: $arg,)*) -> ResultE<Self::$result> {
-            Err(Error::UnsupportedType)
+            Err(OscError::UnsupportedValue)
         }
     };
 }
@@ -15,7 +15,7 @@ macro_rules! default_ser_one {
 macro_rules! default_ser_one_sized {
     ($func:ident($($arg:ty),*)) => {
         fn $func<T: ?Sized + Serialize>(self, $(_: $arg,)*) -> ResultE<Self::Ok> {
-            Err(Error::UnsupportedType)
+            Err(OscError::UnsupportedValue)
         }
     };
 }
@@ -0,0 +1,58 @@
+use std::convert::TryInto;
+use serde::ser::{Impossible, Serialize, Serializer};
+
+use error::{OscError, ResultE};
+
+/// Captures the contents of a string.
+/// Used for OSC arguments that are encoded exactly like strings,
+/// but carry a different type tag (i.e. symbols).
+#[derive(Debug)]
+pub struct StrSer {
+    parsed: Option<String>,
+}
+
+impl StrSer {
+    pub fn new() -> Self {
+        StrSer {
+            parsed: None,
+        }
+    }
+}
+
+impl TryInto<String> for StrSer {
+    type Error = OscError;
+    fn try_into(self) -> ResultE<String> {
+        self.parsed.ok_or(OscError::BadFormat{ offset: 0, context: "expected a string" })
+    }
+}
+
+impl Serializer for &mut StrSer {
+    type Ok = ();
+    type Error = OscError;
+    type SerializeSeq = Impossible<Self::Ok, OscError>;
+    type SerializeTuple = Impossible<Self::Ok, OscError>;
+    type SerializeStruct = Impossible<Self::Ok, OscError>;
+    type SerializeTupleStruct = Impossible<Self::Ok, OscError>;
+    type SerializeTupleVariant = Impossible<Self::Ok, OscError>;
+    type SerializeMap = Impossible<Self::Ok, OscError>;
+    type SerializeStructVariant = Impossible<Self::Ok, OscError>;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn serialize_str(self, value: &str) -> ResultE<Self::Ok> {
+        match self.parsed {
+            // Already serialized a string!
+            Some(_) => Err(OscError::BadFormat{ offset: 0, context: "expected a single string" }),
+            None => {
+                self.parsed = Some(value.to_owned());
+                Ok(())
+            }
+        }
+    }
+
+    default_ser!{bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char
+        bytes none some unit unit_struct unit_variant newtype_struct newtype_variant
+        seq tuple tuple_struct tuple_variant map struct struct_variant}
+}
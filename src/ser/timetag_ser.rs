@@ -1,9 +1,9 @@
 use std::convert::TryInto;
 use serde::ser::{Impossible, Serialize, Serializer};
 
-use error::{Error, ResultE};
+use error::{OscError, ResultE};
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct TimetagSer {
     n_parsed: u8,
     parsed: [u32; 2],
@@ -16,13 +16,17 @@ impl TimetagSer {
             parsed: [0, 0],
         }
     }
+    /// The values captured so far.
+    pub fn parsed(&self) -> &[u32] {
+        &self.parsed[..self.n_parsed as usize]
+    }
 }
 
 impl TryInto<(u32, u32)> for TimetagSer {
-    type Error = Error;
+    type Error = OscError;
     fn try_into(self) -> ResultE<(u32, u32)> {
         if self.n_parsed != 2 {
-            return Err(Error::BadFormat);
+            return Err(OscError::BadFormat{ offset: 0, context: "expected a (u32, u32) time tag" });
         }
         Ok((self.parsed[0], self.parsed[1]))
     }
@@ -30,19 +34,23 @@ impl TryInto<(u32, u32)> for TimetagSer {
 
 impl<'a> Serializer for &'a mut TimetagSer {
     type Ok = ();
-    type Error = Error;
-    type SerializeSeq = Impossible<Self::Ok, Error>;
+    type Error = OscError;
+    type SerializeSeq = Impossible<Self::Ok, OscError>;
     type SerializeTuple = Self::SerializeSeq;
     type SerializeStruct = Self::SerializeSeq;
-    type SerializeTupleStruct = Impossible<Self::Ok, Error>;
-    type SerializeTupleVariant = Impossible<Self::Ok, Error>;
-    type SerializeMap = Impossible<Self::Ok, Error>;
-    type SerializeStructVariant = Impossible<Self::Ok, Error>;
+    type SerializeTupleStruct = Impossible<Self::Ok, OscError>;
+    type SerializeTupleVariant = Impossible<Self::Ok, OscError>;
+    type SerializeMap = Impossible<Self::Ok, OscError>;
+    type SerializeStructVariant = Impossible<Self::Ok, OscError>;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
 
     fn serialize_u32(self, value: u32) -> ResultE<Self::Ok> {
         match self.parsed.get_mut(self.n_parsed as usize) {
             // Already serialized all the values needed for a timetag!
-            None => Err(Error::BadFormat),
+            None => Err(OscError::BadFormat{ offset: 0, context: "expected a (u32, u32) time tag" }),
             Some(mut part) => {
                 *part = value;
                 self.n_parsed += 1;
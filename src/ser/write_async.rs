@@ -0,0 +1,65 @@
+use std::future::Future;
+use std::io;
+use std::io::ErrorKind;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use serde::ser::Serialize;
+use tokio::io::AsyncWrite;
+
+use error::{OscError, ResultE};
+use framing::Framing;
+
+/// Serialize `value` into an OSC packet, and write it to some asynchronously
+/// writable device. This is the async equivalent of [`to_write`].
+///
+/// Serialization is CPU-bound, so it's done synchronously into a buffer,
+/// before this function returns; only the writing of that buffer is asynchronous.
+///
+/// [`to_write`]: fn.to_write.html
+pub fn to_write_async<'a, S, W>(writer: &'a mut W, value: &S, framing: Framing) -> WritePacket<'a, W>
+    where W: AsyncWrite + Unpin + ?Sized, S: Serialize + ?Sized
+{
+    let (packet, error) = match super::to_vec(value, framing) {
+        Ok(packet) => (packet, None),
+        Err(error) => (Vec::new(), Some(error)),
+    };
+    WritePacket {
+        writer,
+        packet,
+        written: 0,
+        error,
+    }
+}
+
+/// Future returned by [`to_write_async`].
+///
+/// [`to_write_async`]: fn.to_write_async.html
+#[derive(Debug)]
+pub struct WritePacket<'a, W: 'a + ?Sized> {
+    writer: &'a mut W,
+    packet: Vec<u8>,
+    written: usize,
+    /// Error encountered while serializing the packet, to be reported when polled.
+    error: Option<OscError>,
+}
+
+impl<'a, W> Future for WritePacket<'a, W>
+    where W: AsyncWrite + Unpin + ?Sized
+{
+    type Output = ResultE<()>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<ResultE<()>> {
+        let this = self.get_mut();
+        if let Some(error) = this.error.take() {
+            return Poll::Ready(Err(error));
+        }
+        while this.written < this.packet.len() {
+            let len = ready!(Pin::new(&mut *this.writer).poll_write(cx, &this.packet[this.written..]))?;
+            if len == 0 {
+                return Poll::Ready(Err(OscError::Io(io::Error::from(ErrorKind::WriteZero))));
+            }
+            this.written += len;
+        }
+        ready!(Pin::new(&mut *this.writer).poll_flush(cx))?;
+        Poll::Ready(Ok(()))
+    }
+}
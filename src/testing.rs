@@ -0,0 +1,119 @@
+//! Implementations of `arbitrary::Arbitrary`, for generating packets in fuzzers
+//! and property tests.
+//!
+//! Everything generated can be serialized, and deserializes back into an
+//! equivalent value (floats aside, as NaN isn't equal to itself).
+//! So addresses contain none of the characters forbidden in them,
+//! strings contain no null characters, and chars are ASCII.
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use address::OscAddress;
+use packet::{OscBundle, OscMessage, OscPacket};
+use types::{OscArg, OscColor, OscMidi, OscTimeTag};
+
+/// Arrays within arrays, and bundles within bundles, are nested at most this deep.
+/// Without a limit, generating a value could recurse until the input runs out.
+const MAX_DEPTH: usize = 4;
+
+/// Remove the characters that wouldn't survive serialization.
+fn sanitize(s: String, forbidden: &[char]) -> String {
+    s.chars().filter(|c| *c != '\0' && !forbidden.contains(c)).collect()
+}
+
+/// Generate elements with `f` for as long as the input says to continue,
+/// and there's input left.
+fn arbitrary_vec<'a, T, F>(u: &mut Unstructured<'a>, mut f: F) -> Result<Vec<T>>
+    where F: FnMut(&mut Unstructured<'a>) -> Result<T>
+{
+    let mut elements = Vec::new();
+    while !u.is_empty() && bool::arbitrary(u)? {
+        elements.push(f(u)?);
+    }
+    Ok(elements)
+}
+
+impl<'a> Arbitrary<'a> for OscAddress {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let forbidden = ['/', ' ', '#', '*', ',', '?', '[', ']', '{', '}'];
+        let mut address = String::new();
+        for part in Vec::<String>::arbitrary(u)? {
+            address.push('/');
+            address.push_str(&sanitize(part, &forbidden));
+        }
+        if address.is_empty() {
+            address.push('/');
+        }
+        Ok(OscAddress::from_validated(&address))
+    }
+}
+
+impl<'a> Arbitrary<'a> for OscTimeTag {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(OscTimeTag{ seconds: u.arbitrary()?, fraction: u.arbitrary()? })
+    }
+}
+
+impl<'a> Arbitrary<'a> for OscArg {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        arbitrary_arg(u, 0)
+    }
+}
+
+fn arbitrary_arg(u: &mut Unstructured, depth: usize) -> Result<OscArg> {
+    // Arrays are the last variant, so excluding them just shortens the range.
+    let last = if depth < MAX_DEPTH { 15 } else { 14 };
+    Ok(match u.int_in_range(0..=last)? {
+        0 => OscArg::I32(u.arbitrary()?),
+        1 => OscArg::F32(u.arbitrary()?),
+        2 => OscArg::String(sanitize(u.arbitrary()?, &[])),
+        3 => OscArg::Blob(u.arbitrary()?),
+        4 => OscArg::I64(u.arbitrary()?),
+        5 => OscArg::F64(u.arbitrary()?),
+        6 => OscArg::Char(char::from(u.int_in_range(0..=0x7f)?)),
+        7 => OscArg::Color(OscColor(u.arbitrary()?)),
+        8 => OscArg::Midi(OscMidi(u.arbitrary()?)),
+        9 => OscArg::Bool(u.arbitrary()?),
+        10 => OscArg::Nil,
+        11 => OscArg::Impulse,
+        12 => OscArg::Symbol(sanitize(u.arbitrary()?, &[])),
+        13 => OscArg::TimeTag(u.arbitrary()?),
+        14 => OscArg::U32(u.arbitrary()?),
+        _ => OscArg::Array(arbitrary_vec(u, |u| arbitrary_arg(u, depth + 1))?),
+    })
+}
+
+impl<'a> Arbitrary<'a> for OscMessage {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(OscMessage {
+            address: OscAddress::arbitrary(u)?.to_string(),
+            args: Vec::<OscArg>::arbitrary(u)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for OscBundle {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        arbitrary_bundle(u, 0)
+    }
+}
+
+impl<'a> Arbitrary<'a> for OscPacket {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        arbitrary_packet(u, 0)
+    }
+}
+
+fn arbitrary_bundle(u: &mut Unstructured, depth: usize) -> Result<OscBundle> {
+    let timetag = OscTimeTag::arbitrary(u)?;
+    let elements = arbitrary_vec(u, |u| arbitrary_packet(u, depth + 1))?;
+    Ok(OscBundle::new(timetag, elements))
+}
+
+fn arbitrary_packet(u: &mut Unstructured, depth: usize) -> Result<OscPacket> {
+    if depth < MAX_DEPTH && u.arbitrary()? {
+        arbitrary_bundle(u, depth).map(OscPacket::Bundle)
+    } else {
+        OscMessage::arbitrary(u).map(OscPacket::Message)
+    }
+}
@@ -0,0 +1,799 @@
+//! OSC 1.1 defines several argument types that don't map onto any type in
+//! the serde data model. Each of them is represented by a newtype in this
+//! module (or a unit struct, for types that carry no data); the serializer and deserializer recognize these by their name, so
+//! they can be used as message arguments like any other type.
+//!
+//! [`OscArg`] can hold an argument of any of these types, for when the types
+//! of a message's arguments aren't known at compile time.
+//!
+//! [`OscArg`]: enum.OscArg.html
+
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::mem;
+use std::ops::{Add, Sub};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use serde::de::{Deserialize, Deserializer, EnumAccess, SeqAccess, Unexpected, VariantAccess,
+    Visitor};
+use serde::de;
+use serde::ser::{Serialize, Serializer};
+#[cfg(feature = "bytes")]
+use bytes::Bytes;
+
+#[cfg(feature = "json")]
+use json;
+
+/// Name that `OscArg` passes when deserializing, so that symbols aren't read as strings.
+pub(crate) const ARG_NAME: &str = "OscArg";
+/// Name that serde_derive gives `OscColor` when (de)serializing it.
+pub(crate) const COLOR_NAME: &str = "OscColor";
+/// Name that serde_derive gives `OscMidi` when (de)serializing it.
+pub(crate) const MIDI_NAME: &str = "OscMidi";
+/// Name that serde_derive gives `OscSymbol` when (de)serializing it.
+pub(crate) const SYMBOL_NAME: &str = "OscSymbol";
+/// Name that serde_derive gives `OscImpulse` when (de)serializing it.
+pub(crate) const IMPULSE_NAME: &str = "OscImpulse";
+
+/// 32-bit RGBA color, sent with the 'r' type tag.
+/// Fields are ordered red, green, blue, alpha.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct OscColor(pub [u8; 4]);
+
+/// 4-byte MIDI message, sent with the 'm' type tag.
+/// Bytes from MSB to LSB are: port id, status byte, data1, data2.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct OscMidi(pub [u8; 4]);
+
+/// Symbol, sent with the 'S' type tag.
+/// Encoded exactly like a string, but some OSC implementations treat symbols
+/// differently (e.g. as interned names).
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct OscSymbol(pub String);
+
+/// Impulse (also known as "Infinitum" or "Bang"), sent with the 'I' type tag.
+/// Carries no data; it's used to trigger an event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct OscImpulse;
+
+/// Seconds between the NTP epoch (1900) used by timetags and the Unix epoch (1970).
+const NTP_UNIX_OFFSET: u64 = 2_208_988_800;
+
+/// Point in time, as used by bundles and 't' arguments.
+///
+/// This is a 64-bit fixed-point number of seconds since midnight,
+/// January 1, 1900 (the NTP epoch). It serializes as a `(u32, u32)`
+/// of `(seconds, fraction)`, so it can be used anywhere a raw timetag can.
+///
+/// The seconds field overflows in 2036. When converting to a `SystemTime`,
+/// timetags with the most significant bit of `seconds` clear are assumed to
+/// lie after this overflow, as per RFC 4330.
+///
+/// Timetags are ordered by `seconds`, then `fraction`, which is chronological
+/// order (for timetags on the same side of the overflow).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OscTimeTag {
+    pub seconds: u32,
+    /// Fractions of a second, in units of 2^-32 seconds.
+    pub fraction: u32,
+}
+
+impl OscTimeTag {
+    /// The special timetag that means "execute immediately".
+    pub const IMMEDIATE: OscTimeTag = OscTimeTag { seconds: 0, fraction: 1 };
+
+    /// The current time, according to the system clock.
+    pub fn now() -> Self {
+        SystemTime::now().into()
+    }
+    /// Whether this is the special timetag that means "execute immediately".
+    pub fn is_immediate(&self) -> bool {
+        *self == Self::IMMEDIATE
+    }
+    /// The timetag as a single 64-bit fixed-point value.
+    fn to_bits(self) -> u64 {
+        (u64::from(self.seconds) << 32) | u64::from(self.fraction)
+    }
+    fn from_bits(bits: u64) -> Self {
+        OscTimeTag {
+            seconds: (bits >> 32) as u32,
+            fraction: bits as u32,
+        }
+    }
+}
+
+/// Convert a duration to 64-bit fixed-point seconds.
+/// Nanoseconds are rounded up, so that converting back gives the same duration.
+fn duration_to_bits(duration: Duration) -> u64 {
+    let fraction = (u64::from(duration.subsec_nanos()) << 32).div_ceil(1_000_000_000);
+    (duration.as_secs() << 32).wrapping_add(fraction)
+}
+
+/// Convert 64-bit fixed-point seconds to a duration, rounding down to the nearest nanosecond.
+fn bits_to_duration(bits: u64) -> Duration {
+    let nanos = ((bits & 0xffff_ffff) * 1_000_000_000) >> 32;
+    Duration::new(bits >> 32, nanos as u32)
+}
+
+/// Seconds since the NTP epoch, to the nanosecond (e.g. "3913056000.500000000"),
+/// or "immediate" for `OscTimeTag::IMMEDIATE`.
+impl fmt::Display for OscTimeTag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_immediate() {
+            return f.write_str("immediate");
+        }
+        let since_epoch = bits_to_duration(self.to_bits());
+        write!(f, "{}.{:09}", since_epoch.as_secs(), since_epoch.subsec_nanos())
+    }
+}
+
+impl From<(u32, u32)> for OscTimeTag {
+    fn from((seconds, fraction): (u32, u32)) -> Self {
+        OscTimeTag { seconds, fraction }
+    }
+}
+
+impl From<OscTimeTag> for (u32, u32) {
+    fn from(tag: OscTimeTag) -> Self {
+        (tag.seconds, tag.fraction)
+    }
+}
+
+/// Fractions of a second carry into the seconds field.
+impl Add<Duration> for OscTimeTag {
+    type Output = OscTimeTag;
+    fn add(self, duration: Duration) -> OscTimeTag {
+        OscTimeTag::from_bits(self.to_bits().wrapping_add(duration_to_bits(duration)))
+    }
+}
+
+/// The time elapsed between two timetags, or `None` if `other` is later than `self`.
+impl Sub<OscTimeTag> for OscTimeTag {
+    type Output = Option<Duration>;
+    fn sub(self, other: OscTimeTag) -> Option<Duration> {
+        self.to_bits().checked_sub(other.to_bits()).map(bits_to_duration)
+    }
+}
+
+impl From<SystemTime> for OscTimeTag {
+    fn from(time: SystemTime) -> Self {
+        let epoch = NTP_UNIX_OFFSET << 32;
+        let bits = match time.duration_since(UNIX_EPOCH) {
+            Ok(since) => epoch.wrapping_add(duration_to_bits(since)),
+            Err(before) => epoch.wrapping_sub(duration_to_bits(before.duration())),
+        };
+        OscTimeTag::from_bits(bits)
+    }
+}
+
+impl From<OscTimeTag> for SystemTime {
+    fn from(tag: OscTimeTag) -> Self {
+        let mut since_ntp_epoch = bits_to_duration(tag.to_bits());
+        if tag.seconds & 0x8000_0000 == 0 {
+            // Timetags from 1900 to 1968 are indistinguishable from those
+            // after the 2036 overflow; assume the latter.
+            since_ntp_epoch += Duration::from_secs(1 << 32);
+        }
+        let offset = Duration::from_secs(NTP_UNIX_OFFSET);
+        match since_ntp_epoch.checked_sub(offset) {
+            Some(since_unix_epoch) => UNIX_EPOCH + since_unix_epoch,
+            None => UNIX_EPOCH - (offset - since_ntp_epoch),
+        }
+    }
+}
+
+impl Serialize for OscTimeTag {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        (self.seconds, self.fraction).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for OscTimeTag {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        <(u32, u32)>::deserialize(deserializer).map(OscTimeTag::from)
+    }
+}
+
+/// A single character of a message's type tag, identifying the type of an argument
+/// (or, for `ArrayStart` and `ArrayEnd`, delimiting an array of arguments).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OscTypeTag {
+    I32,
+    F32,
+    Str,
+    Blob,
+    I64,
+    F64,
+    Char,
+    Color,
+    Midi,
+    True,
+    False,
+    Nil,
+    Impulse,
+    Symbol,
+    TimeTag,
+    U32,
+    ArrayStart,
+    ArrayEnd,
+}
+
+impl OscTypeTag {
+    /// The types defined by OSC 1.0; all others are optional.
+    pub fn all_v10() -> &'static [OscTypeTag] {
+        &[OscTypeTag::I32, OscTypeTag::F32, OscTypeTag::Str, OscTypeTag::Blob]
+    }
+    pub fn from_char(c: char) -> Option<Self> {
+        Some(match c {
+            'i' => OscTypeTag::I32,
+            'f' => OscTypeTag::F32,
+            's' => OscTypeTag::Str,
+            'b' => OscTypeTag::Blob,
+            'h' => OscTypeTag::I64,
+            'd' => OscTypeTag::F64,
+            'c' => OscTypeTag::Char,
+            'r' => OscTypeTag::Color,
+            'm' => OscTypeTag::Midi,
+            'T' => OscTypeTag::True,
+            'F' => OscTypeTag::False,
+            'N' => OscTypeTag::Nil,
+            'I' => OscTypeTag::Impulse,
+            'S' => OscTypeTag::Symbol,
+            't' => OscTypeTag::TimeTag,
+            'u' => OscTypeTag::U32,
+            '[' => OscTypeTag::ArrayStart,
+            ']' => OscTypeTag::ArrayEnd,
+            _ => return None,
+        })
+    }
+    pub fn to_char(&self) -> char {
+        match *self {
+            OscTypeTag::I32 => 'i',
+            OscTypeTag::F32 => 'f',
+            OscTypeTag::Str => 's',
+            OscTypeTag::Blob => 'b',
+            OscTypeTag::I64 => 'h',
+            OscTypeTag::F64 => 'd',
+            OscTypeTag::Char => 'c',
+            OscTypeTag::Color => 'r',
+            OscTypeTag::Midi => 'm',
+            OscTypeTag::True => 'T',
+            OscTypeTag::False => 'F',
+            OscTypeTag::Nil => 'N',
+            OscTypeTag::Impulse => 'I',
+            OscTypeTag::Symbol => 'S',
+            OscTypeTag::TimeTag => 't',
+            OscTypeTag::U32 => 'u',
+            OscTypeTag::ArrayStart => '[',
+            OscTypeTag::ArrayEnd => ']',
+        }
+    }
+    /// Number of bytes of argument data this type always has,
+    /// or `None` for strings, symbols and blobs, whose size varies.
+    pub fn data_size(&self) -> Option<usize> {
+        match *self {
+            OscTypeTag::I32 | OscTypeTag::F32 | OscTypeTag::Char |
+            OscTypeTag::Color | OscTypeTag::Midi | OscTypeTag::U32 => Some(4),
+            OscTypeTag::I64 | OscTypeTag::F64 | OscTypeTag::TimeTag => Some(8),
+            OscTypeTag::Str | OscTypeTag::Symbol | OscTypeTag::Blob => None,
+            _ => Some(0),
+        }
+    }
+    /// Whether this type has no argument data, being conveyed entirely by its tag.
+    pub fn is_zero_width(&self) -> bool {
+        self.data_size() == Some(0)
+    }
+}
+
+/// A single OSC argument of any supported type.
+///
+/// Deserializing into an `OscArg` picks the variant based on the argument's
+/// type tag, so a message whose arguments aren't known at compile time
+/// can be read into a `Vec<OscArg>`.
+///
+/// With the `json` feature, human-readable formats such as JSON instead
+/// (de)serialize each argument tagged with its type, e.g. `{"type":"i","value":42}`.
+///
+/// With the `bytes` feature, a blob can also be held as a `BlobBytes`,
+/// which is cheap to clone. Deserializing always produces a `Blob`, which
+/// [`into_bytes`] converts without copying.
+///
+/// Note that, like any other sequence, an `Array` containing exactly two
+/// `U32`s will be serialized as a timetag.
+///
+/// Floats are compared by their bit patterns, as they'd appear on the wire,
+/// rather than as IEEE values: an `F32(NAN)` is equal to itself (if the NaN
+/// payloads match), and `F32(0.0)` isn't equal to `F32(-0.0)`. This makes
+/// `OscArg` `Eq`, so messages can be used as `HashMap` keys.
+///
+/// [`into_bytes`]: #method.into_bytes
+#[derive(Clone, Debug)]
+pub enum OscArg {
+    /// 'i'
+    I32(i32),
+    /// 'f'
+    F32(f32),
+    /// 's'
+    String(String),
+    /// 'b'
+    Blob(Vec<u8>),
+    /// 'h'
+    I64(i64),
+    /// 'd'
+    F64(f64),
+    /// 'c'
+    Char(char),
+    /// 'r'
+    Color(OscColor),
+    /// 'm'
+    Midi(OscMidi),
+    /// 'T' or 'F'
+    Bool(bool),
+    /// 'N'
+    Nil,
+    /// 'I'
+    Impulse,
+    /// 'S'
+    Symbol(String),
+    /// 't'
+    TimeTag(OscTimeTag),
+    /// 'u'
+    U32(u32),
+    /// '[' ... ']'
+    Array(Vec<OscArg>),
+    /// 'b', held in a reference-counted buffer.
+    /// Not equal to a `Blob` with the same contents.
+    #[cfg(feature = "bytes")]
+    BlobBytes(Bytes),
+}
+
+impl PartialEq for OscArg {
+    fn eq(&self, other: &OscArg) -> bool {
+        match (self, other) {
+            (OscArg::I32(a), OscArg::I32(b)) => a == b,
+            (OscArg::F32(a), OscArg::F32(b)) => a.to_bits() == b.to_bits(),
+            (OscArg::String(a), OscArg::String(b)) => a == b,
+            (OscArg::Blob(a), OscArg::Blob(b)) => a == b,
+            (OscArg::I64(a), OscArg::I64(b)) => a == b,
+            (OscArg::F64(a), OscArg::F64(b)) => a.to_bits() == b.to_bits(),
+            (OscArg::Char(a), OscArg::Char(b)) => a == b,
+            (OscArg::Color(a), OscArg::Color(b)) => a == b,
+            (OscArg::Midi(a), OscArg::Midi(b)) => a == b,
+            (OscArg::Bool(a), OscArg::Bool(b)) => a == b,
+            (OscArg::Nil, OscArg::Nil) | (OscArg::Impulse, OscArg::Impulse) => true,
+            (OscArg::Symbol(a), OscArg::Symbol(b)) => a == b,
+            (OscArg::TimeTag(a), OscArg::TimeTag(b)) => a == b,
+            (OscArg::U32(a), OscArg::U32(b)) => a == b,
+            (OscArg::Array(a), OscArg::Array(b)) => a == b,
+            #[cfg(feature = "bytes")]
+            (OscArg::BlobBytes(a), OscArg::BlobBytes(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for OscArg {}
+
+impl Hash for OscArg {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        match *self {
+            OscArg::I32(i) => i.hash(state),
+            OscArg::F32(f) => f.to_bits().hash(state),
+            OscArg::String(ref s) | OscArg::Symbol(ref s) => s.hash(state),
+            OscArg::Blob(ref b) => b.hash(state),
+            OscArg::I64(h) => h.hash(state),
+            OscArg::F64(d) => d.to_bits().hash(state),
+            OscArg::Char(c) => c.hash(state),
+            OscArg::Color(ref r) => r.hash(state),
+            OscArg::Midi(ref m) => m.hash(state),
+            OscArg::Bool(b) => b.hash(state),
+            OscArg::Nil | OscArg::Impulse => {},
+            OscArg::TimeTag(ref t) => t.hash(state),
+            OscArg::U32(u) => u.hash(state),
+            OscArg::Array(ref a) => a.hash(state),
+            #[cfg(feature = "bytes")]
+            OscArg::BlobBytes(ref b) => b.hash(state),
+        }
+    }
+}
+
+/// Numbers are suffixed with their type tag (e.g. "42i", "3.14f"),
+/// and strings are quoted.
+impl fmt::Display for OscArg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_value(f)?;
+        match *self {
+            OscArg::I32(_) => f.write_str("i"),
+            OscArg::F32(_) => f.write_str("f"),
+            OscArg::I64(_) => f.write_str("h"),
+            OscArg::F64(_) => f.write_str("d"),
+            OscArg::U32(_) => f.write_str("u"),
+            OscArg::Symbol(_) => f.write_str("S"),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl OscArg {
+    /// The value of an `I32` argument.
+    pub fn as_i32(&self) -> Option<i32> {
+        match *self {
+            OscArg::I32(i) => Some(i),
+            _ => None,
+        }
+    }
+    /// The value of an `F32` argument.
+    pub fn as_f32(&self) -> Option<f32> {
+        match *self {
+            OscArg::F32(f) => Some(f),
+            _ => None,
+        }
+    }
+    /// The value of a `String` argument (but not a `Symbol`).
+    pub fn as_str(&self) -> Option<&str> {
+        match *self {
+            OscArg::String(ref s) => Some(s),
+            _ => None,
+        }
+    }
+    /// The contents of a `Blob` (or `BlobBytes`) argument.
+    pub fn as_blob(&self) -> Option<&[u8]> {
+        match *self {
+            OscArg::Blob(ref b) => Some(b),
+            #[cfg(feature = "bytes")]
+            OscArg::BlobBytes(ref b) => Some(b),
+            _ => None,
+        }
+    }
+    pub fn into_i32(self) -> Option<i32> {
+        self.as_i32()
+    }
+    pub fn into_f32(self) -> Option<f32> {
+        self.as_f32()
+    }
+    pub fn into_string(self) -> Option<String> {
+        match self {
+            OscArg::String(s) => Some(s),
+            _ => None,
+        }
+    }
+    pub fn into_blob(self) -> Option<Vec<u8>> {
+        match self {
+            OscArg::Blob(b) => Some(b),
+            #[cfg(feature = "bytes")]
+            OscArg::BlobBytes(b) => Some(b.to_vec()),
+            _ => None,
+        }
+    }
+    /// The contents of a `Blob` or `BlobBytes` argument, as `Bytes`.
+    /// This takes ownership of a `Blob`'s buffer rather than copying it.
+    #[cfg(feature = "bytes")]
+    pub fn into_bytes(self) -> Option<Bytes> {
+        match self {
+            OscArg::Blob(b) => Some(Bytes::from(b)),
+            OscArg::BlobBytes(b) => Some(b),
+            _ => None,
+        }
+    }
+    /// The value of any numeric argument (`I32`, `F32`, `I64`, `F64` or `U32`)
+    /// as an `f64`, e.g. for a parameter that senders may give as either an int or a float.
+    /// This is exact, except for `I64` values beyond ±2^53, which are rounded.
+    pub fn coerce_to_f64(&self) -> Option<f64> {
+        match *self {
+            OscArg::I32(i) => Some(i.into()),
+            OscArg::F32(f) => Some(f.into()),
+            OscArg::I64(h) => Some(h as f64),
+            OscArg::F64(d) => Some(d),
+            OscArg::U32(u) => Some(u.into()),
+            _ => None,
+        }
+    }
+    /// The value of any numeric argument as an `f32`,
+    /// rounded if it can't be represented exactly.
+    pub fn coerce_to_f32(&self) -> Option<f32> {
+        match *self {
+            OscArg::F32(f) => Some(f),
+            // Converted directly, to avoid rounding twice.
+            OscArg::I64(h) => Some(h as f32),
+            _ => self.coerce_to_f64().map(|d| d as f32),
+        }
+    }
+    /// The value of any numeric argument as an `i64`. Floats are truncated
+    /// toward zero, saturating at the bounds of `i64`; NaN and infinities give `None`.
+    pub fn coerce_to_i64(&self) -> Option<i64> {
+        match *self {
+            OscArg::I32(i) => Some(i.into()),
+            OscArg::I64(h) => Some(h),
+            OscArg::U32(u) => Some(u.into()),
+            OscArg::F32(_) | OscArg::F64(_) => self.coerce_to_f64()
+                .filter(|d| d.is_finite())
+                .map(|d| d as i64),
+            _ => None,
+        }
+    }
+    /// Format the argument's value without any indication of its type,
+    /// for when that's given elsewhere (e.g. in a message's type tag).
+    pub(crate) fn fmt_value(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            OscArg::I32(i) => write!(f, "{}", i),
+            OscArg::F32(v) => write!(f, "{}", v),
+            OscArg::String(ref s) | OscArg::Symbol(ref s) => write!(f, "{:?}", s),
+            OscArg::Blob(ref b) => write!(f, "<{} bytes blob>", b.len()),
+            #[cfg(feature = "bytes")]
+            OscArg::BlobBytes(ref b) => write!(f, "<{} bytes blob>", b.len()),
+            OscArg::I64(h) => write!(f, "{}", h),
+            OscArg::F64(d) => write!(f, "{}", d),
+            OscArg::Char(c) => write!(f, "{:?}", c),
+            OscArg::Color(OscColor([r, g, b, a])) => write!(f, "<color #{:02x}{:02x}{:02x}{:02x}>", r, g, b, a),
+            OscArg::Midi(OscMidi([port, status, data1, data2])) =>
+                write!(f, "<midi {:02x} {:02x} {:02x} {:02x}>", port, status, data1, data2),
+            OscArg::Bool(b) => write!(f, "{}", b),
+            OscArg::Nil => f.write_str("nil"),
+            OscArg::Impulse => f.write_str("impulse"),
+            OscArg::TimeTag(ref t) => write!(f, "@{}", t),
+            OscArg::U32(u) => write!(f, "{}", u),
+            OscArg::Array(ref a) => {
+                f.write_str("[")?;
+                for (i, arg) in a.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(" ")?;
+                    }
+                    write!(f, "{}", arg)?;
+                }
+                f.write_str("]")
+            },
+        }
+    }
+    /// The type tag of this argument. For an array, that's the `ArrayStart`
+    /// which begins its type tag.
+    pub fn type_tag(&self) -> OscTypeTag {
+        match *self {
+            OscArg::I32(_) => OscTypeTag::I32,
+            OscArg::F32(_) => OscTypeTag::F32,
+            OscArg::String(_) => OscTypeTag::Str,
+            OscArg::Blob(_) => OscTypeTag::Blob,
+            OscArg::I64(_) => OscTypeTag::I64,
+            OscArg::F64(_) => OscTypeTag::F64,
+            OscArg::Char(_) => OscTypeTag::Char,
+            OscArg::Color(_) => OscTypeTag::Color,
+            OscArg::Midi(_) => OscTypeTag::Midi,
+            OscArg::Bool(true) => OscTypeTag::True,
+            OscArg::Bool(false) => OscTypeTag::False,
+            OscArg::Nil => OscTypeTag::Nil,
+            OscArg::Impulse => OscTypeTag::Impulse,
+            OscArg::Symbol(_) => OscTypeTag::Symbol,
+            OscArg::TimeTag(_) => OscTypeTag::TimeTag,
+            OscArg::U32(_) => OscTypeTag::U32,
+            // Serialized as a timetag; see above.
+            OscArg::Array(ref a) if is_timetag(a) => OscTypeTag::TimeTag,
+            OscArg::Array(_) => OscTypeTag::ArrayStart,
+            #[cfg(feature = "bytes")]
+            OscArg::BlobBytes(_) => OscTypeTag::Blob,
+        }
+    }
+    /// Append the characters representing this argument to a type tag.
+    pub(crate) fn push_tag(&self, tag: &mut String) {
+        tag.push(self.type_tag().to_char());
+        if let OscArg::Array(ref a) = *self {
+            if !is_timetag(a) {
+                for arg in a {
+                    arg.push_tag(tag);
+                }
+                tag.push(OscTypeTag::ArrayEnd.to_char());
+            }
+        }
+    }
+    /// Number of characters this argument contributes to the type tag.
+    pub(crate) fn tag_len(&self) -> usize {
+        match *self {
+            // Serialized as a timetag; see above.
+            OscArg::Array(ref a) if is_timetag(a) => 1,
+            OscArg::Array(ref a) => 2 + a.iter().map(OscArg::tag_len).sum::<usize>(),
+            _ => 1,
+        }
+    }
+    /// Number of bytes of argument data this argument serializes to,
+    /// excluding its type tag.
+    pub fn wire_size(&self) -> usize {
+        match *self {
+            OscArg::I32(_) | OscArg::F32(_) | OscArg::Char(_) |
+            OscArg::Color(_) | OscArg::Midi(_) | OscArg::U32(_) => 4,
+            OscArg::I64(_) | OscArg::F64(_) | OscArg::TimeTag(_) => 8,
+            OscArg::String(ref s) | OscArg::Symbol(ref s) => padded_str_len(s),
+            // Length, then the data padded to a multiple of 4 bytes.
+            OscArg::Blob(ref b) => 4 + b.len() + (4 - b.len() % 4) % 4,
+            OscArg::Bool(_) | OscArg::Nil | OscArg::Impulse => 0,
+            OscArg::Array(ref a) => a.iter().map(OscArg::wire_size).sum(),
+            #[cfg(feature = "bytes")]
+            OscArg::BlobBytes(ref b) => 4 + b.len() + (4 - b.len() % 4) % 4,
+        }
+    }
+}
+
+/// Conversions into the variant that each type serializes as, e.g. for `osc_message!`.
+macro_rules! impl_from_for_osc_arg {
+    ($($ty:ty => $variant:ident),*) => {
+        $(
+            impl From<$ty> for OscArg {
+                fn from(value: $ty) -> Self {
+                    OscArg::$variant(value)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_for_osc_arg!(i32 => I32, f32 => F32, String => String, Vec<u8> => Blob,
+    i64 => I64, f64 => F64, char => Char, OscColor => Color, OscMidi => Midi,
+    bool => Bool, OscTimeTag => TimeTag, u32 => U32);
+
+impl<'a> From<&'a str> for OscArg {
+    fn from(value: &'a str) -> Self {
+        OscArg::String(value.to_owned())
+    }
+}
+
+impl<'a> From<&'a [u8]> for OscArg {
+    fn from(value: &'a [u8]) -> Self {
+        OscArg::Blob(value.to_vec())
+    }
+}
+
+/// Allows byte string literals, e.g. `OscArg::from(b"\xde\xad")`.
+impl<'a, const N: usize> From<&'a [u8; N]> for OscArg {
+    fn from(value: &'a [u8; N]) -> Self {
+        OscArg::Blob(value.to_vec())
+    }
+}
+
+impl From<OscSymbol> for OscArg {
+    fn from(value: OscSymbol) -> Self {
+        OscArg::Symbol(value.0)
+    }
+}
+
+impl From<OscImpulse> for OscArg {
+    fn from(_: OscImpulse) -> Self {
+        OscArg::Impulse
+    }
+}
+
+fn is_timetag(args: &[OscArg]) -> bool {
+    matches!(*args, [OscArg::U32(_), OscArg::U32(_)])
+}
+
+/// Length of a string once encoded: it's null-terminated, then padded to a multiple of 4 bytes.
+pub(crate) fn padded_str_len(s: &str) -> usize {
+    s.len() + 4 - s.len() % 4
+}
+
+impl Serialize for OscArg {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        #[cfg(feature = "json")]
+        {
+            if serializer.is_human_readable() {
+                return json::serialize_arg(self, serializer);
+            }
+        }
+        match *self {
+            OscArg::I32(i) => serializer.serialize_i32(i),
+            OscArg::F32(f) => serializer.serialize_f32(f),
+            OscArg::String(ref s) => serializer.serialize_str(s),
+            OscArg::Blob(ref b) => serializer.serialize_bytes(b),
+            #[cfg(feature = "bytes")]
+            OscArg::BlobBytes(ref b) => serializer.serialize_bytes(b),
+            OscArg::I64(h) => serializer.serialize_i64(h),
+            OscArg::F64(d) => serializer.serialize_f64(d),
+            OscArg::Char(c) => serializer.serialize_char(c),
+            OscArg::Color(ref r) => r.serialize(serializer),
+            OscArg::Midi(ref m) => m.serialize(serializer),
+            OscArg::Bool(b) => serializer.serialize_bool(b),
+            OscArg::Nil => serializer.serialize_none(),
+            OscArg::Impulse => OscImpulse.serialize(serializer),
+            OscArg::Symbol(ref s) => serializer.serialize_newtype_struct(SYMBOL_NAME, s),
+            OscArg::TimeTag(ref t) => t.serialize(serializer),
+            OscArg::U32(u) => serializer.serialize_u32(u),
+            OscArg::Array(ref a) => a.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for OscArg {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        #[cfg(feature = "json")]
+        {
+            if deserializer.is_human_readable() {
+                return json::deserialize_arg(deserializer);
+            }
+        }
+        // The argument's type is determined by its type tag, not by us.
+        // The name only serves to tell symbols apart from strings.
+        deserializer.deserialize_newtype_struct(ARG_NAME, OscArgVisitor)
+    }
+}
+
+struct OscArgVisitor;
+
+impl<'de> Visitor<'de> for OscArgVisitor {
+    type Value = OscArg;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an OSC argument")
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<OscArg, E> {
+        Ok(OscArg::Bool(value))
+    }
+    fn visit_i32<E>(self, value: i32) -> Result<OscArg, E> {
+        Ok(OscArg::I32(value))
+    }
+    fn visit_i64<E>(self, value: i64) -> Result<OscArg, E> {
+        Ok(OscArg::I64(value))
+    }
+    fn visit_u32<E>(self, value: u32) -> Result<OscArg, E> {
+        Ok(OscArg::U32(value))
+    }
+    fn visit_f32<E>(self, value: f32) -> Result<OscArg, E> {
+        Ok(OscArg::F32(value))
+    }
+    fn visit_f64<E>(self, value: f64) -> Result<OscArg, E> {
+        Ok(OscArg::F64(value))
+    }
+    fn visit_char<E>(self, value: char) -> Result<OscArg, E> {
+        Ok(OscArg::Char(value))
+    }
+    fn visit_str<E>(self, value: &str) -> Result<OscArg, E> {
+        Ok(OscArg::String(value.to_owned()))
+    }
+    fn visit_string<E>(self, value: String) -> Result<OscArg, E> {
+        Ok(OscArg::String(value))
+    }
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<OscArg, E> {
+        Ok(OscArg::Blob(value.to_owned()))
+    }
+    fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<OscArg, E> {
+        Ok(OscArg::Blob(value))
+    }
+    fn visit_none<E>(self) -> Result<OscArg, E> {
+        Ok(OscArg::Nil)
+    }
+    fn visit_unit<E>(self) -> Result<OscArg, E> {
+        Ok(OscArg::Impulse)
+    }
+    fn visit_seq<A>(self, mut seq: A) -> Result<OscArg, A::Error>
+        where A: SeqAccess<'de>
+    {
+        let mut elements = Vec::new();
+        while let Some(element) = seq.next_element()? {
+            elements.push(element);
+        }
+        Ok(OscArg::Array(elements))
+    }
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<OscArg, D::Error>
+        where D: Deserializer<'de>
+    {
+        deserializer.deserialize_any(self)
+    }
+    /// Types that can't be told apart by their contents are
+    /// presented as an enum, with the variant named after the type tag.
+    fn visit_enum<A>(self, data: A) -> Result<OscArg, A::Error>
+        where A: EnumAccess<'de>
+    {
+        let (tag, variant) = data.variant()?;
+        match tag {
+            'r' => variant.newtype_variant().map(OscArg::Color),
+            'm' => variant.newtype_variant().map(OscArg::Midi),
+            'S' => variant.newtype_variant().map(OscArg::Symbol),
+            't' => variant.newtype_variant().map(OscArg::TimeTag),
+            other => Err(de::Error::invalid_value(Unexpected::Char(other), &"an OSC type tag")),
+        }
+    }
+}
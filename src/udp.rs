@@ -0,0 +1,76 @@
+//! Sending and receiving packets over UDP, one packet per datagram.
+
+use std::io;
+use std::net::{SocketAddr, UdpSocket};
+use serde::de::DeserializeOwned;
+use serde::ser::Serialize;
+
+use de::from_slice;
+use error::ResultE;
+use framing::Framing;
+use ser::to_vec;
+
+/// The largest payload a UDP datagram can carry.
+const MAX_DATAGRAM_SIZE: usize = 65_507;
+
+/// Sends each packet as a single datagram to one remote address.
+///
+/// ```no_run
+/// # use serde_osc::udp::UdpOscSender;
+/// let sender = UdpOscSender::connect("127.0.0.1:9000".parse().unwrap()).unwrap();
+/// sender.send(&("/synth/freq", (440.0f32,))).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct UdpOscSender(UdpSocket);
+
+/// Receives packets, each of which must arrive in a single datagram.
+#[derive(Debug)]
+pub struct UdpOscReceiver(UdpSocket);
+
+impl UdpOscSender {
+    /// Bind to an arbitrary local port, and send to `addr`.
+    pub fn connect(addr: SocketAddr) -> io::Result<Self> {
+        let local: SocketAddr = if addr.is_ipv4() {
+            ([0, 0, 0, 0], 0).into()
+        } else {
+            ([0u16; 8], 0).into()
+        };
+        let socket = UdpSocket::bind(local)?;
+        socket.connect(addr)?;
+        Ok(UdpOscSender(socket))
+    }
+    /// Serialize `value` and send it as a single datagram.
+    pub fn send<T>(&self, value: &T) -> ResultE<()>
+        where T: ?Sized + Serialize
+    {
+        let packet = to_vec(value, Framing::Unframed)?;
+        self.0.send(&packet)?;
+        Ok(())
+    }
+    /// The underlying socket, e.g. for setting a write timeout.
+    pub fn socket(&self) -> &UdpSocket {
+        &self.0
+    }
+}
+
+impl UdpOscReceiver {
+    /// Listen for datagrams sent to `addr`.
+    pub fn bind(addr: SocketAddr) -> io::Result<Self> {
+        UdpSocket::bind(addr).map(UdpOscReceiver)
+    }
+    /// Block until a datagram arrives, then deserialize it.
+    pub fn recv<T: DeserializeOwned>(&self) -> ResultE<T> {
+        let mut buf = vec![0; MAX_DATAGRAM_SIZE];
+        let len = self.0.recv(&mut buf)?;
+        from_slice(&buf[..len], Framing::Unframed)
+    }
+    /// The address being listened on, e.g. to learn which port was
+    /// assigned when binding to port 0.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.0.local_addr()
+    }
+    /// The underlying socket, e.g. for setting a read timeout.
+    pub fn socket(&self) -> &UdpSocket {
+        &self.0
+    }
+}
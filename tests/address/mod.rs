@@ -0,0 +1,99 @@
+use serde_osc::{de, ser, Framing, OscAddress, OscMessage};
+use serde_osc::error::Error;
+
+#[test]
+fn valid_address() {
+    let address = OscAddress::new("/audio/synth-1/play").unwrap();
+    assert_eq!(&*address, "/audio/synth-1/play");
+    assert_eq!(address.to_string(), "/audio/synth-1/play");
+    assert_eq!(address.components().collect::<Vec<_>>(), ["audio", "synth-1", "play"]);
+}
+
+#[test]
+fn illegal_chars() {
+    for &c in &[' ', '#', '*', ',', '?', '[', ']', '{', '}'] {
+        let address = format!("/ok/b{}d", c);
+        match OscAddress::new(&address) {
+            Err(Error::InvalidAddress{ address: ref a, position: 5, invalid_char }) => {
+                assert_eq!(a, &address);
+                assert_eq!(invalid_char, c);
+            },
+            other => panic!("expected InvalidAddress for {:?}, got {:?}", c, other),
+        }
+    }
+}
+
+#[test]
+fn missing_slash() {
+    match OscAddress::new("audio") {
+        Err(Error::InvalidAddress{ position: 0, invalid_char: 'a', .. }) => (),
+        other => panic!("expected InvalidAddress, got {:?}", other),
+    }
+    match OscAddress::new("") {
+        Err(Error::InvalidAddress{ position: 0, invalid_char: '\0', .. }) => (),
+        other => panic!("expected InvalidAddress, got {:?}", other),
+    }
+}
+
+#[test]
+fn serde_roundtrip() {
+    let test_input = (OscAddress::new("/a/b").unwrap(), (1i32,));
+    let serialized = ser::to_vec(&test_input, Framing::Framed).unwrap();
+    assert_eq!(serialized, ser::to_vec(&("/a/b".to_owned(), (1i32,)), Framing::Framed).unwrap());
+    let deserialized: (OscAddress, (i32,)) = de::from_slice(&serialized, Framing::Framed).unwrap();
+    assert_eq!(deserialized, test_input);
+
+    // Addresses are validated on deserialization.
+    let pattern = ser::to_vec(&("/a/*".to_owned(), (1i32,)), Framing::Framed).unwrap();
+    assert!(de::from_slice::<(OscAddress, (i32,))>(&pattern, Framing::Framed).is_err());
+}
+
+#[test]
+fn validate_address() {
+    for address in &["/", "/a", "/audio/synth-1/play", "/a.b/c_d/~e", "/é"] {
+        serde_osc::validate_address(address).unwrap();
+    }
+    for &c in &[' ', '#', '*', ',', '?', '[', ']', '{', '}'] {
+        match serde_osc::validate_address(&format!("/ok/b{}d", c)) {
+            Err(Error::InvalidAddress{ position: 5, invalid_char, .. }) => assert_eq!(invalid_char, c),
+            other => panic!("expected InvalidAddress for {:?}, got {:?}", c, other),
+        }
+    }
+}
+
+#[test]
+fn serialize_invalid() {
+    // Message addresses may be patterns, but must be well-formed ones.
+    for address in &["/a/*", "/a/{b,c}", "/a/[!x-z]?"] {
+        ser::to_vec(&(address.to_string(), ()), Framing::Framed).unwrap();
+    }
+    for address in &["/a b", "/a#", "a", "", "/a,b", "/a/[b", "/a/{b/c}", "/a]"] {
+        match ser::to_vec(&(address.to_string(), ()), Framing::Framed) {
+            Err(Error::InvalidAddress{ .. }) => {},
+            other => panic!("expected InvalidAddress for {:?}, got {:?}", address, other),
+        }
+    }
+}
+
+#[test]
+fn from_parts() {
+    let address = OscAddress::from_parts(&["audio", "synth-1"]).unwrap();
+    assert_eq!(&*address, "/audio/synth-1");
+    assert_eq!(&*address.join("play").unwrap(), "/audio/synth-1/play");
+    assert_eq!(&*OscAddress::new("/").unwrap().join("a").unwrap(), "/a");
+    // Components round-trip.
+    let parts: Vec<_> = address.components().collect();
+    assert_eq!(OscAddress::from_parts(&parts).unwrap(), address);
+    let msg = OscMessage::new("/audio/synth-1", vec![]).unwrap();
+    assert_eq!(msg.address_components().collect::<Vec<_>>(), parts);
+
+    match OscAddress::from_parts(&["a", "b/c"]) {
+        Err(Error::InvalidAddress{ position: 4, invalid_char: '/', .. }) => (),
+        other => panic!("expected InvalidAddress, got {:?}", other),
+    }
+    match address.join("b*") {
+        Err(Error::InvalidAddress{ invalid_char: '*', .. }) => (),
+        other => panic!("expected InvalidAddress, got {:?}", other),
+    }
+    assert!(OscAddress::from_parts(&[]).is_err());
+}
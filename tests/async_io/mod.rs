@@ -0,0 +1,70 @@
+use tokio::io::duplex;
+use tokio_test::block_on;
+use serde_osc::{from_read_async, from_read_async_with_config, ser, to_write_async, Framing, OscArg, OscBundle, OscConfig, OscMessage, OscPacket};
+
+#[test]
+fn round_trip() {
+    let msg = OscMessage::new("/a", vec![OscArg::I32(1), OscArg::String("x".to_owned())]).unwrap();
+    let mut bundle = OscBundle::empty((0, 1));
+    bundle.push(msg.clone());
+
+    for &framing in &[Framing::Framed, Framing::Slip] {
+        let (mut client, mut server) = duplex(1024);
+        block_on(to_write_async(&mut client, &msg, framing)).unwrap();
+        block_on(to_write_async(&mut client, &bundle, framing)).unwrap();
+
+        let first: OscPacket = block_on(from_read_async(&mut server, framing)).unwrap();
+        let second: OscPacket = block_on(from_read_async(&mut server, framing)).unwrap();
+        assert_eq!(first.as_message().unwrap().address(), "/a");
+        assert_eq!(second.as_bundle().unwrap().len(), 1);
+    }
+}
+
+#[test]
+fn unframed() {
+    let test_input = ("/a".to_owned(), (1i32,));
+    let (mut client, mut server) = duplex(1024);
+    block_on(to_write_async(&mut client, &test_input, Framing::Unframed)).unwrap();
+    // Unframed packets extend to EOF.
+    drop(client);
+
+    let read: (String, (i32,)) = block_on(from_read_async(&mut server, Framing::Unframed)).unwrap();
+    assert_eq!(read, test_input);
+}
+
+#[test]
+fn matches_sync() {
+    let test_input = ("/a".to_owned(), (1i32,));
+    let (mut client, mut server) = duplex(1024);
+    block_on(to_write_async(&mut client, &test_input, Framing::Framed)).unwrap();
+    drop(client);
+
+    let mut written = Vec::new();
+    block_on(tokio::io::AsyncReadExt::read_to_end(&mut server, &mut written)).unwrap();
+    assert_eq!(written, ser::to_vec(&test_input, Framing::Framed).unwrap());
+}
+
+#[test]
+fn limits() {
+    let config = OscConfig::default().max_packet_size(16);
+    let msg = OscMessage::new("/a", vec![OscArg::Blob(vec![0; 32])]).unwrap();
+    for &framing in &[Framing::Framed, Framing::Unframed, Framing::Slip] {
+        let (mut client, mut server) = duplex(1024);
+        block_on(to_write_async(&mut client, &msg, framing)).unwrap();
+        drop(client);
+        let read: Result<OscPacket, _> = block_on(from_read_async_with_config(&mut server, framing, &config));
+        assert!(read.unwrap_err().is_format(), "{:?}", framing);
+    }
+
+    // Space isn't reserved for a packet until it arrives: a length
+    // prefix of i32::MAX followed by EOF fails without allocating 2 GiB.
+    let (mut client, mut server) = duplex(1024);
+    block_on(tokio::io::AsyncWriteExt::write_all(&mut client, b"\x7f\xff\xff\xff/a\0\0")).unwrap();
+    drop(client);
+    let read: Result<OscPacket, _> = block_on(from_read_async(&mut server, Framing::Framed));
+    assert!(read.unwrap_err().is_io());
+    let (mut client, mut server) = duplex(1024);
+    block_on(tokio::io::AsyncWriteExt::write_all(&mut client, b"\x7f\xff\xff\xff")).unwrap();
+    let read: Result<OscPacket, _> = block_on(from_read_async_with_config(&mut server, Framing::Framed, &OscConfig::default()));
+    assert!(read.unwrap_err().is_format());
+}
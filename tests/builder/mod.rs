@@ -0,0 +1,138 @@
+use serde_bytes::ByteBuf;
+use serde_osc::{de, ser, to_udp_payload, BundleBuilder, Framing, MessageBuilder, OscBundle, OscPacket, OscTimeTag};
+use serde_osc::error::Error;
+
+#[test]
+fn message_matches_derive() {
+    #[derive(Serialize)]
+    struct Msg {
+        address: String,
+        args: (i32, f32, String, ByteBuf),
+    }
+    let derived = Msg {
+        address: "/audio/play".to_owned(),
+        args: (1, 44100.0, "loop".to_owned(), ByteBuf::from(vec![0xde, 0xad, 0xbe, 0xef, 0x01])),
+    };
+    let expected = ser::to_vec(&derived, Framing::Framed).unwrap();
+
+    let mut builder = MessageBuilder::new("/audio/play").unwrap();
+    builder.i32(1).f32(44100.0).str("loop").blob(&[0xde, 0xad, 0xbe, 0xef, 0x01]);
+    assert_eq!(builder.build_vec().unwrap(), expected);
+
+    let msg = builder.build();
+    assert_eq!(msg.args_count(), 4);
+    assert_eq!(ser::to_vec(&msg, Framing::Framed).unwrap(), expected);
+
+    // Capacity makes no difference to the output.
+    let mut builder = MessageBuilder::with_capacity("/audio/play", 4).unwrap();
+    builder.i32(1).f32(44100.0).str("loop").blob(&[0xde, 0xad, 0xbe, 0xef, 0x01]);
+    assert_eq!(builder.build_vec().unwrap(), expected);
+}
+
+#[test]
+fn message_bad_address() {
+    match MessageBuilder::new("audio") {
+        Err(Error::InvalidAddress{ .. }) => (),
+        other => panic!("expected InvalidAddress, got {:?}", other),
+    }
+}
+
+#[test]
+fn bundle_matches_derive() {
+    // The same bundle as in `tests/ser/bundle.rs`, but to be executed immediately.
+    #[derive(Serialize)]
+    struct Msg1 {
+        address: String,
+        args: (i32,),
+    }
+    #[derive(Serialize)]
+    struct Msg2 {
+        address: String,
+        args: (f32,),
+    }
+    #[derive(Serialize)]
+    struct Bundle {
+        timestamp: (u32, u32),
+        messages: (Msg1, Msg2),
+    }
+    let derived = Bundle {
+        timestamp: (0, 1),
+        messages: (
+            Msg1 { address: "/m1".to_owned(), args: (0x5eeeeeed,) },
+            Msg2 { address: "/m2".to_owned(), args: (440.0,) },
+        ),
+    };
+    let msg1 = MessageBuilder::new("/m1").unwrap().i32(0x5eeeeeed).build();
+    let msg2 = MessageBuilder::new("/m2").unwrap().f32(440.0).build();
+
+    let built = BundleBuilder::immediate().push(msg1).push(msg2).build_vec().unwrap();
+    assert_eq!(built, ser::to_vec(&derived, Framing::Framed).unwrap());
+
+    // Elements larger than the builder expects are still written in full.
+    let big = MessageBuilder::new("/big").unwrap().blob(&[7; 300]).build();
+    let mut builder = BundleBuilder::with_capacity(OscTimeTag::IMMEDIATE, 1);
+    builder.push(big.clone()).push(big.clone());
+    let bundle = OscBundle::new(OscTimeTag::IMMEDIATE, vec![big.clone().into(), big.into()]);
+    assert_eq!(builder.build_vec().unwrap(), ser::to_vec(&bundle, Framing::Framed).unwrap());
+}
+
+#[test]
+fn nested_bundle() {
+    let inner = BundleBuilder::new(OscTimeTag{ seconds: 1, fraction: 0 })
+        .push(MessageBuilder::new("/inner").unwrap().build())
+        .build();
+    let outer = BundleBuilder::immediate()
+        .push(inner)
+        .push(MessageBuilder::new("/outer").unwrap().build())
+        .build();
+    assert_eq!(outer.len(), 2);
+    assert!(outer.elements()[0].is_bundle());
+    assert!(outer.elements()[1].is_message());
+}
+
+#[test]
+fn raw_elements() {
+    let msg = MessageBuilder::new("/raw").unwrap().i32(1).str("two").build();
+    let inner = BundleBuilder::new(OscTimeTag{ seconds: 1, fraction: 0 }).push(msg.clone()).build();
+    let typed = MessageBuilder::new("/typed").unwrap().f32(3.0).build();
+
+    let mut builder = BundleBuilder::immediate();
+    builder.push_raw(&to_udp_payload(&msg).unwrap()).unwrap()
+        .push(typed.clone())
+        .push_raw(&to_udp_payload(&inner).unwrap()).unwrap();
+    let expected = OscBundle::new(OscTimeTag::IMMEDIATE, vec![msg.into(), typed.into(), inner.into()]);
+    assert_eq!(builder.build(), expected);
+    let built = builder.build_vec().unwrap();
+    assert_eq!(built, ser::to_vec(&expected, Framing::Framed).unwrap());
+    assert_eq!(de::from_slice::<OscPacket>(&built, Framing::Framed).unwrap(), OscPacket::Bundle(expected));
+
+    assert!(builder.push_raw(b",i\0\0").unwrap_err().is_format());
+    assert!(builder.push_raw(b"").unwrap_err().is_format());
+    // Elements are checked in full when added, so that build() can't fail.
+    assert!(builder.push_raw(b"/a\0\0,i\0\0").is_err());
+    assert!(builder.push_raw(b"/a\0\0,x\0\0").is_err());
+    assert!(builder.push_raw(b"#bundle\0\0\0\0\0").is_err());
+    assert_eq!(builder.build().len(), 3);
+}
+
+#[test]
+fn message_and_packet_bytes() {
+    let serialized = MessageBuilder::new("/serialized").unwrap().i32(1).build();
+    let built = MessageBuilder::new("/built").unwrap().str("two").build();
+    let inner = BundleBuilder::new(OscTimeTag{ seconds: 1, fraction: 0 }).push(built.clone()).build();
+
+    let mut builder = BundleBuilder::immediate();
+    builder.with_message_bytes(&ser::to_vec(&serialized, Framing::Unframed).unwrap()).unwrap()
+        .with_message_bytes(&to_udp_payload(&built).unwrap()).unwrap()
+        .with_packet_bytes(&to_udp_payload(&inner).unwrap()).unwrap();
+    let expected = OscBundle::new(OscTimeTag::IMMEDIATE, vec![serialized.into(), built.into(), inner.clone().into()]);
+    let bytes = builder.build_vec().unwrap();
+    assert_eq!(de::from_slice::<OscPacket>(&bytes, Framing::Framed).unwrap(), OscPacket::Bundle(expected));
+
+    // Only with_packet_bytes accepts bundles.
+    assert!(builder.with_message_bytes(&to_udp_payload(&inner).unwrap()).unwrap_err().is_format());
+    // Misaligned elements are rejected.
+    assert!(builder.with_message_bytes(b"/abc\0").unwrap_err().is_format());
+    assert!(builder.with_packet_bytes(b"/a\0\0,\0").unwrap_err().is_format());
+    assert_eq!(builder.build().len(), 3);
+}
@@ -0,0 +1,56 @@
+use tokio_stream::StreamExt;
+use tokio_test::block_on;
+use tokio_test::io::Builder;
+use tokio_util::codec::{Decoder, Encoder, FramedRead};
+use bytes::BytesMut;
+use serde_osc::{ser, Framing, OscArg, OscBundle, OscCodec, OscConfig, OscMessage, OscPacket};
+
+#[test]
+fn encode() {
+    let msg: OscPacket = OscMessage::new("/a", vec![OscArg::I32(1)]).unwrap().into();
+    let mut dst = BytesMut::new();
+    OscCodec::new().encode(msg.clone(), &mut dst).unwrap();
+    assert_eq!(&dst[..], &ser::to_vec(&msg, Framing::Framed).unwrap()[..]);
+}
+
+#[test]
+fn decode_split_reads() {
+    let msg = OscMessage::new("/a", vec![OscArg::I32(1)]).unwrap();
+    let mut bundle = OscBundle::empty((0, 1));
+    bundle.push(msg.clone());
+    let first = ser::to_vec(&msg, Framing::Framed).unwrap();
+    let second = ser::to_vec(&bundle, Framing::Framed).unwrap();
+
+    // The length prefix and the payload arrive separately,
+    // and the second packet is itself split mid-way.
+    let stream = Builder::new()
+        .read(&first[..4])
+        .read(&first[4..])
+        .read(&second[..6])
+        .read(&second[6..])
+        .build();
+    let mut framed = FramedRead::new(stream, OscCodec::new());
+
+    let read_first = block_on(framed.next()).unwrap().unwrap();
+    assert_eq!(read_first.as_message().unwrap().address(), "/a");
+    let read_second = block_on(framed.next()).unwrap().unwrap();
+    assert_eq!(read_second.as_bundle().unwrap().len(), 1);
+    assert!(block_on(framed.next()).is_none());
+}
+
+#[test]
+fn decode_oversized() {
+    // A length prefix of i32::MAX mustn't have the codec reserve space for it.
+    let mut src = BytesMut::from(&b"\x7f\xff\xff\xff/a\0\0"[..]);
+    assert!(OscCodec::new().decode(&mut src).unwrap_err().is_format());
+    assert!(src.capacity() < 1024);
+
+    // A packet within the default limits, but not within those configured.
+    let msg: OscPacket = OscMessage::new("/a", vec![OscArg::Blob(vec![0; 64])]).unwrap().into();
+    let bytes = ser::to_vec(&msg, Framing::Framed).unwrap();
+    assert_eq!(OscCodec::new().decode(&mut BytesMut::from(&bytes[..])).unwrap(), Some(msg));
+    let mut codec = OscCodec::with_config(OscConfig::default().max_packet_size(32));
+    assert!(codec.decode(&mut BytesMut::from(&bytes[..])).unwrap_err().is_format());
+    let mut codec = OscCodec::with_config(OscConfig::default().max_blob_size(32));
+    assert!(codec.decode(&mut BytesMut::from(&bytes[..])).unwrap_err().is_format());
+}
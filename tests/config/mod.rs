@@ -0,0 +1,172 @@
+use std::io::Cursor;
+use serde_bytes::ByteBuf;
+use serde_osc::{de, ser, Framing, OscArg, OscBundle, OscConfig, OscMessage, OscPacket};
+use serde_osc::error::Error;
+
+fn assert_bad_format<T: ::std::fmt::Debug>(result: Result<T, Error>) {
+    match result {
+        Err(Error::BadFormat{ .. }) => {},
+        other => panic!("expected BadFormat, got {:?}", other),
+    }
+}
+
+#[test]
+fn huge_length_prefix() {
+    // A 4 GB length, which doesn't fit in the i32 that holds it.
+    let test_input = b"\xff\xff\xff\xff/a\0\0,\0\0\0";
+    assert_bad_format(de::from_read_with_config::<OscPacket, _>(
+        Cursor::new(&test_input[..]), Framing::Framed, &OscConfig::default()));
+    // The largest possible length, of which only a few bytes are present.
+    let test_input = b"\x7f\xff\xff\xff/a\0\0,\0\0\0";
+    assert_bad_format(de::from_read_with_config::<OscPacket, _>(
+        Cursor::new(&test_input[..]), Framing::Framed, &OscConfig::default()));
+}
+
+#[test]
+fn huge_blob_length() {
+    let test_input = b"\x00\x00\x00\x10/a\0\0,b\0\0\x7f\xff\xff\xff\0\0\0\0";
+    assert_bad_format(de::from_read_with_config::<OscPacket, _>(
+        Cursor::new(&test_input[..]), Framing::Framed, &OscConfig::default()));
+    // Even without limits, a blob can't be longer than the rest of the packet,
+    // so this is rejected before space is allocated for it.
+    assert_bad_format(de::from_slice::<OscPacket>(&test_input[..], Framing::Framed));
+    assert_bad_format(de::from_slice::<OscPacket>(b"/a\0\0,b\0\0\x7f\xff\xff\xfc", Framing::Unframed));
+    // The packet's length prefix can't be trusted either: space for the blob
+    // is only allocated as its data arrives, and here it runs out.
+    let test_input = b"\x7f\xff\xff\xff/a\0\0,b\0\0\x7f\xff\xff\xf0\0\0\0\0";
+    assert!(de::from_slice::<OscPacket>(&test_input[..], Framing::Framed).unwrap_err().is_io());
+
+    // The same cases are seeded into the fuzz corpus.
+    assert_bad_format(de::from_slice::<OscPacket>(
+        include_bytes!("../../fuzz/corpus/fuzz_framed/huge_blob_length"), Framing::Framed));
+    assert_bad_format(de::from_slice::<OscPacket>(
+        include_bytes!("../../fuzz/corpus/fuzz_unframed/huge_blob_length"), Framing::Unframed));
+}
+
+#[test]
+fn packet_size() {
+    let msg = OscMessage::new("/a", vec![]).unwrap();
+    let serialized = ser::to_vec(&msg, Framing::Unframed).unwrap();
+    let config = OscConfig::default().max_packet_size(serialized.len());
+    let too_small = OscConfig::default().max_packet_size(serialized.len() - 1);
+
+    for &framing in &[Framing::Framed, Framing::Unframed, Framing::Slip] {
+        let serialized = ser::to_vec(&msg, framing).unwrap();
+        de::from_read_with_config::<OscMessage, _>(Cursor::new(&serialized), framing, &config).unwrap();
+        assert_bad_format(de::from_read_with_config::<OscMessage, _>(
+            Cursor::new(&serialized), framing, &too_small));
+    }
+}
+
+#[test]
+fn blob_and_string_size() {
+    let test_input = ("/a".to_owned(), (ByteBuf::from(vec![0; 8]), "12345678".to_owned()));
+    let serialized = ser::to_vec(&test_input, Framing::Framed).unwrap();
+    let config = OscConfig::default().max_blob_size(8).max_string_len(8);
+    de::from_read_with_config::<OscMessage, _>(Cursor::new(&serialized), Framing::Framed, &config).unwrap();
+
+    assert_bad_format(de::from_read_with_config::<OscMessage, _>(
+        Cursor::new(&serialized), Framing::Framed, &config.max_blob_size(7)));
+    assert_bad_format(de::from_read_with_config::<OscMessage, _>(
+        Cursor::new(&serialized), Framing::Framed, &config.max_string_len(7)));
+}
+
+#[test]
+fn bundle_depth() {
+    let mut nested = OscBundle::empty((0, 1));
+    nested.push(OscMessage::new("/a", vec![]).unwrap());
+    let mut bundle = OscBundle::empty((0, 1));
+    bundle.push(nested);
+    let serialized = ser::to_vec(&bundle, Framing::Framed).unwrap();
+
+    let config = OscConfig::default().max_bundle_depth(2);
+    de::from_read_with_config::<OscBundle, _>(Cursor::new(&serialized), Framing::Framed, &config).unwrap();
+    // The error arises within the outer bundle's first element.
+    match de::from_read_with_config::<OscBundle, _>(
+        Cursor::new(&serialized), Framing::Framed, &config.max_bundle_depth(1)) {
+        Err(Error::InBundle{ element_index: 0, inner }) => assert_bad_format::<()>(Err(*inner)),
+        other => panic!("expected InBundle, got {:?}", other),
+    }
+}
+
+#[test]
+fn write_with_config() {
+    let msg = OscMessage::new("/too/long", vec![]).unwrap();
+    let config = OscConfig::default().max_string_len(4);
+
+    let mut output = Vec::new();
+    assert_bad_format(ser::to_write_with_config(&mut output, &msg, Framing::Framed, &config));
+    // Nothing is written when the limits are exceeded.
+    assert!(output.is_empty());
+
+    let msg = OscMessage::new("/ok", vec![]).unwrap();
+    ser::to_write_with_config(&mut output, &msg, Framing::Framed, &config).unwrap();
+    assert_eq!(output, ser::to_vec(&msg, Framing::Framed).unwrap());
+}
+
+#[test]
+fn strict_and_lenient() {
+    let parse = |input: &[u8], config: &OscConfig| {
+        de::from_read_with_config::<OscMessage, _>(Cursor::new(input), Framing::Unframed, config)
+    };
+    // Missing type tag
+    let test_input = b"/a\0\0";
+    assert_bad_format(parse(test_input, &OscConfig::strict()));
+    assert_eq!(parse(test_input, &OscConfig::lenient()).unwrap().args().len(), 0);
+    // Type tag without the leading comma
+    let test_input = b"/a\0\0i\0\0\0\0\0\0\x07";
+    assert_bad_format(parse(test_input, &OscConfig::strict()));
+    assert_eq!(parse(test_input, &OscConfig::lenient()).unwrap().args().len(), 1);
+    // Non-zero padding
+    let test_input = b"/a\0x,s\0\0ab\0\0";
+    match parse(test_input, &OscConfig::strict()) {
+        Err(Error::BadPadding{ .. }) => {},
+        other => panic!("expected BadPadding, got {:?}", other),
+    }
+    assert_eq!(parse(test_input, &OscConfig::lenient()).unwrap().args().len(), 1);
+    // Unknown type tag
+    let test_input = b"/a\0\0,zi\0\0\0\0\x07";
+    match parse(test_input, &OscConfig::strict()) {
+        Err(Error::UnsupportedType{ type_code: b'z', arg_index: 0 }) => {},
+        other => panic!("expected UnsupportedType, got {:?}", other),
+    }
+    assert_eq!(parse(test_input, &OscConfig::lenient()).unwrap().args().len(), 1);
+}
+
+#[test]
+fn strict_padding() {
+    // The blob's final padding byte is non-zero.
+    let test_input = b"/a\0\0,b\0\0\x00\x00\x00\x02\x01\x02\0\x07";
+    let parse = |config: &OscConfig| {
+        de::from_read_with_config::<OscMessage, _>(Cursor::new(&test_input[..]), Framing::Unframed, config)
+    };
+    for config in &[OscConfig::default(), OscConfig::default().strict_padding(true)] {
+        match parse(config) {
+            Err(Error::BadPadding{ offset: 15 }) => {},
+            other => panic!("expected BadPadding, got {:?}", other),
+        }
+    }
+    let msg = parse(&OscConfig::default().strict_padding(false)).unwrap();
+    match msg.args() {
+        [OscArg::Blob(ref blob)] => assert_eq!(blob, &[1, 2]),
+        other => panic!("expected a blob, got {:?}", other),
+    }
+}
+
+#[test]
+fn strict_arg_count() {
+    #[derive(Debug, Deserialize)]
+    struct Msg {
+        address: String,
+        args: (i32, i32, i32),
+    }
+    let test_input = ("/a".to_owned(), (1i32, 2i32, 3i32, 4i32, 5i32));
+    let serialized = ser::to_vec(&test_input, Framing::Unframed).unwrap();
+    let parse = |config: &OscConfig| {
+        de::from_read_with_config::<Msg, _>(Cursor::new(&serialized), Framing::Unframed, config)
+    };
+    let msg = parse(&OscConfig::default()).unwrap();
+    assert_eq!(msg.address, "/a");
+    assert_eq!(msg.args, (1, 2, 3));
+    assert_bad_format(parse(&OscConfig::default().strict_arg_count(true)));
+}
@@ -1,5 +1,6 @@
+use std::io::Cursor;
 use serde_bytes::ByteBuf;
-use serde_osc::de;
+use serde_osc::{de, ser, Framing, OscArg, OscMessage};
 
 
 #[test]
@@ -22,7 +23,7 @@ fn auto_de() {
     // Note: 0x43dc0000 is 440.0 in f32.
     let test_input = b"\x00\x00\x00\x2C/example/path\0\0\0,ifb\0\0\0\0\x01\x02\x03\x04\x43\xdc\0\0\0\0\0\x05\xde\xad\xbe\xef\xff\x00\x00\x00";
 
-    let deserialized: Deserialized = de::from_slice(test_input).unwrap();
+    let deserialized: Deserialized = de::from_slice(test_input, Framing::Framed).unwrap();
     assert_eq!(deserialized, expected);
 }
 
@@ -30,7 +31,7 @@ fn auto_de() {
 fn to_tuple() {
     let expected = ("/ts".to_owned(), ());
     let test_input = b"\x00\x00\x00\x08/ts\0,\0\0\0";
-    let deserialized: (String, ()) = de::from_slice(test_input).unwrap();
+    let deserialized: (String, ()) = de::from_slice(test_input, Framing::Framed).unwrap();
     assert_eq!(deserialized, expected);
 }
 
@@ -38,7 +39,7 @@ fn to_tuple() {
 fn to_array() {
     let expected: (String, [i32; 0]) = ("/ts".to_owned(), []);
     let test_input = b"\x00\x00\x00\x08/ts\0,\0\0\0";
-    let deserialized: (String, [i32; 0]) = de::from_slice(test_input).unwrap();
+    let deserialized: (String, [i32; 0]) = de::from_slice(test_input, Framing::Framed).unwrap();
     assert_eq!(deserialized, expected);
 }
 
@@ -49,7 +50,29 @@ fn to_unit_struct() {
 
     let expected: (String, Unit) = ("/ts".to_owned(), Unit);
     let test_input = b"\x00\x00\x00\x08/ts\0,\0\0\0";
-    let deserialized: (String, Unit) = de::from_slice(test_input).unwrap();
+    let deserialized: (String, Unit) = de::from_slice(test_input, Framing::Framed).unwrap();
     assert_eq!(deserialized, expected);
 }
 
+
+#[test]
+fn address_only() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct AddressOnly {
+        address: String,
+    }
+    let msg = OscMessage::new("/test", vec![
+        OscArg::I32(1), OscArg::F32(2.0), OscArg::String("three".to_owned()),
+        OscArg::Blob(vec![4]), OscArg::I64(5),
+    ]).unwrap();
+    let next = OscMessage::new("/next", vec![OscArg::I32(6)]).unwrap();
+    let mut stream = ser::to_vec(&msg, Framing::Framed).unwrap();
+    stream.extend(ser::to_vec(&next, Framing::Framed).unwrap());
+
+    // The arguments are skipped, leaving the reader at the following packet.
+    let mut reader = Cursor::new(stream);
+    let parsed: AddressOnly = de::from_read(&mut reader, Framing::Framed).unwrap();
+    assert_eq!(parsed, AddressOnly{ address: "/test".to_owned() });
+    assert_eq!(reader.position() as usize, 4 + msg.byte_len());
+    assert_eq!(de::from_read::<OscMessage, _>(&mut reader, Framing::Framed).unwrap(), next);
+}
@@ -0,0 +1,147 @@
+use std::io::Cursor;
+use serde_osc::{de, ser, Framing, OscArg, OscConfig, OscMessage, OscPacket};
+use serde_osc::error::Error;
+
+#[test]
+fn padding_offset() {
+    // The 'x' at byte 15 should have been padding.
+    let test_input = b"\x00\x00\x00\x0c/a\0\0,s\0\0ab\0x";
+    match de::from_read::<OscPacket, _>(Cursor::new(&test_input[..]), Framing::Framed) {
+        Err(Error::BadPadding{ offset: 15 }) => {},
+        other => panic!("expected BadPadding at 15, got {:?}", other),
+    }
+    // Same packet, but without the length prefix.
+    match de::from_slice::<OscPacket>(&test_input[4..], Framing::Unframed) {
+        Err(Error::BadPadding{ offset: 11 }) => {},
+        other => panic!("expected BadPadding at 11, got {:?}", other),
+    }
+}
+
+#[test]
+fn offset_within_bundle() {
+    // The blob's padding (byte 38) is within a message, within a bundle.
+    let test_input = b"\x00\x00\x00\x24#bundle\0\0\0\0\0\0\0\0\x01\
+        \x00\x00\x00\x10/a\0\0,b\0\0\x00\x00\x00\x01\x01\0\x07\0";
+    match de::from_read::<OscPacket, _>(Cursor::new(&test_input[..]), Framing::Framed) {
+        Err(Error::InBundle{ element_index: 0, inner }) => match *inner {
+            Error::BadPadding{ offset: 38 } => {},
+            other => panic!("expected BadPadding at 38, got {:?}", other),
+        },
+        other => panic!("expected InBundle, got {:?}", other),
+    }
+}
+
+#[test]
+fn format_offset() {
+    // The char argument at byte 12 isn't ASCII.
+    let test_input = b"\x00\x00\x00\x0c/a\0\0,c\0\0\x00\x00\x00\xe9";
+    match de::from_read::<OscPacket, _>(Cursor::new(&test_input[..]), Framing::Framed) {
+        Err(Error::BadFormat{ offset: 12, .. }) => {},
+        other => panic!("expected BadFormat at 12, got {:?}", other),
+    }
+}
+
+#[test]
+fn unsupported_type_index() {
+    let test_input = b"/a\0\0,ix\0\0\0\0\x07";
+    match de::from_slice::<OscPacket>(test_input, Framing::Unframed) {
+        Err(Error::UnsupportedType{ type_code: b'x', arg_index: 1 }) => {},
+        other => panic!("expected UnsupportedType for argument 1, got {:?}", other),
+    }
+}
+
+#[test]
+fn arg_miscount() {
+    let test_input = b"/a\0\0,i\0\0\0\0\0\x07";
+    match de::from_slice::<(String, (i32, i32))>(test_input, Framing::Unframed) {
+        Err(Error::ArgMiscount{ expected: 2, found: 1 }) => {},
+        other => panic!("expected ArgMiscount, got {:?}", other),
+    }
+}
+
+#[test]
+fn error_in_bundle() {
+    // The second element has an unknown type tag.
+    let test_input = b"#bundle\0\0\0\0\0\0\0\0\x01\
+        \x00\x00\x00\x08/a\0\0,\0\0\0\
+        \x00\x00\x00\x08/b\0\0,x\0\0";
+    match de::from_slice::<OscPacket>(test_input, Framing::Unframed) {
+        Err(Error::InBundle{ element_index: 1, inner }) => match *inner {
+            Error::UnsupportedType{ type_code: b'x', arg_index: 0 } => {},
+            other => panic!("expected UnsupportedType, got {:?}", other),
+        },
+        other => panic!("expected InBundle, got {:?}", other),
+    }
+}
+
+#[test]
+fn unified_error_type() {
+    // Serialization and deserialization share one error type.
+    let error: serde_osc::de::Error = serde_osc::ser::Error::UnsupportedValue;
+    let _: serde_osc::OscError = error;
+}
+
+/// A message whose only argument is an empty array nested `depth` levels deep.
+fn nested_arrays(depth: usize) -> Vec<u8> {
+    let mut tag = ",".to_owned();
+    tag.extend((0..depth).map(|_| '['));
+    tag.extend((0..depth).map(|_| ']'));
+    let mut packet = b"/a\0\0".to_vec();
+    packet.extend(tag.as_bytes());
+    packet.extend(vec![0; 4 - tag.len() % 4]);
+    packet
+}
+
+/// Empty bundles, nested `depth` levels deep.
+fn nested_bundles(depth: usize) -> Vec<u8> {
+    let mut packet = b"#bundle\0\0\0\0\0\0\0\0\x01".to_vec();
+    for _ in 1..depth {
+        let mut outer = b"#bundle\0\0\0\0\0\0\0\0\x01".to_vec();
+        outer.extend(&(packet.len() as u32).to_be_bytes());
+        outer.extend(packet);
+        packet = outer;
+    }
+    packet
+}
+
+#[test]
+fn nesting_limit() {
+    // Deserializing deeper packets than these could overflow the stack,
+    // so they're rejected even without a config.
+    assert!(de::from_slice::<OscPacket>(&nested_arrays(64), Framing::Unframed).is_ok());
+    match de::from_slice::<OscPacket>(&nested_arrays(65), Framing::Unframed) {
+        Err(Error::BadFormat{ context: "arrays nested too deeply", .. }) => (),
+        other => panic!("got {:?}", other),
+    }
+    assert!(de::from_slice::<OscPacket>(&nested_arrays(100_000), Framing::Unframed).unwrap_err().is_format());
+
+    assert!(de::from_slice::<OscPacket>(&nested_bundles(64), Framing::Unframed).is_ok());
+    assert!(de::from_slice::<OscPacket>(&nested_bundles(65), Framing::Unframed).unwrap_err().is_format());
+    assert!(de::from_slice::<OscPacket>(&nested_bundles(1000), Framing::Unframed).unwrap_err().is_format());
+}
+
+#[test]
+fn default_bundle_depth() {
+    // The default config is far stricter than the hard limit.
+    let config = OscConfig::default();
+    let packet = nested_bundles(8);
+    assert!(de::from_read_with_config::<OscPacket, _>(Cursor::new(&packet), Framing::Unframed, &config).is_ok());
+    let packet = nested_bundles(100);
+    let err = de::from_read_with_config::<OscPacket, _>(Cursor::new(&packet), Framing::Unframed, &config).unwrap_err();
+    // Reported from within the 8 enclosing bundles.
+    assert!(err.is_format());
+}
+
+#[test]
+fn trailing_data() {
+    let msg = OscMessage::new("/a", vec![OscArg::I32(1)]).unwrap();
+    for &framing in &[Framing::Framed, Framing::Unframed] {
+        let mut data = ser::to_vec(&msg, framing).unwrap();
+        assert_eq!(de::from_read_exact::<OscMessage, _>(&data[..], framing).unwrap(), msg);
+        data.push(0);
+        match de::from_read_exact::<OscMessage, _>(&data[..], framing) {
+            Err(Error::BadFormat{ offset, .. }) => assert_eq!(offset, data.len() - 1),
+            other => panic!("expected BadFormat, got {:?}", other),
+        }
+    }
+}
@@ -1,4 +1,9 @@
 mod auto_derive;
 mod bundle;
+mod errors;
 mod manual;
+mod packet_iter;
+mod peekable;
+mod read_args;
+mod skip;
 
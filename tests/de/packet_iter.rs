@@ -0,0 +1,74 @@
+use std::io::Cursor;
+use serde_osc::{de, ser, Framing, OscArg, OscBundle, OscMessage, OscPacket};
+use serde_osc::error::Error;
+
+fn three_packets(framing: Framing) -> Vec<u8> {
+    let msg = OscMessage::new("/m", vec![OscArg::I32(1)]).unwrap();
+    let mut bundle = OscBundle::empty((0, 1));
+    bundle.push(msg.clone());
+
+    let mut stream = ser::to_vec(&msg, framing).unwrap();
+    stream.extend(ser::to_vec(&bundle, framing).unwrap());
+    stream.extend(ser::to_vec(&("/typed".to_owned(), (2i32,)), framing).unwrap());
+    stream
+}
+
+#[test]
+fn three_packets_framed() {
+    for &framing in &[Framing::Framed, Framing::Slip] {
+        let stream = three_packets(framing);
+        let packets: Vec<OscPacket> = de::packets_from_read(Cursor::new(stream), framing)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(packets.len(), 3);
+        assert!(packets[0].is_message());
+        assert!(packets[1].is_bundle());
+        assert_eq!(packets[2].as_message().unwrap().address(), "/typed");
+    }
+}
+
+#[test]
+fn empty_stream() {
+    let mut iter = de::packets_from_read::<OscPacket, _>(Cursor::new(Vec::new()), Framing::Framed);
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn truncated() {
+    let mut stream = three_packets(Framing::Framed);
+    let len = stream.len();
+    stream.truncate(len - 2);
+
+    let mut iter = de::packets_from_read::<OscPacket, _>(Cursor::new(stream), Framing::Framed);
+    assert!(iter.next().unwrap().is_ok());
+    assert!(iter.next().unwrap().is_ok());
+    match iter.next() {
+        Some(Err(Error::Io(_))) => {},
+        other => panic!("expected an I/O error, got {:?}", other),
+    }
+    // Nothing more is read after an error.
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn read_all() {
+    let mut stream = Vec::new();
+    for i in 0..5 {
+        ser::to_write(&mut stream, &OscMessage::new("/n", vec![OscArg::I32(i)]).unwrap(), Framing::Framed).unwrap();
+    }
+    let packets: Vec<OscMessage> = de::from_read_all(Cursor::new(&stream), Framing::Framed).unwrap();
+    assert_eq!(packets.len(), 5);
+    for (i, msg) in packets.iter().enumerate() {
+        assert_eq!(msg.args(), &[OscArg::I32(i as i32)]);
+    }
+    let alias: Vec<OscMessage> = de::packets_until_eof(Cursor::new(&stream), Framing::Framed).unwrap();
+    assert_eq!(alias, packets);
+
+    // A clean EOF after zero packets is fine, but a truncated one isn't.
+    assert!(de::from_read_all::<OscMessage, _>(Cursor::new(Vec::new()), Framing::Framed).unwrap().is_empty());
+    stream.pop();
+    match de::from_read_all::<OscMessage, _>(Cursor::new(&stream), Framing::Framed) {
+        Err(Error::Io(_)) => {},
+        other => panic!("expected an I/O error, got {:?}", other),
+    }
+}
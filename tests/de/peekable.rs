@@ -0,0 +1,85 @@
+use std::io::Cursor;
+use serde_osc::{de, ser, Framing, OscArg, OscBundle, OscConfig, OscMessage, OscPacket};
+use serde_osc::de::PeekablePacketReader;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Volume {
+    address: String,
+    args: (f32, i32),
+}
+
+fn stream(framing: Framing) -> Vec<u8> {
+    let mut bundle = OscBundle::empty((0, 1));
+    bundle.push(OscMessage::new("/m", vec![]).unwrap());
+    let mut stream = ser::to_vec(&("/volume".to_owned(), (0.5f32, 3i32)), framing).unwrap();
+    stream.extend(ser::to_vec(&bundle, framing).unwrap());
+    stream
+}
+
+#[test]
+fn peek_then_deserialize() {
+    for &framing in &[Framing::Framed, Framing::Slip] {
+        let stream = stream(framing);
+        let direct: Volume = de::from_read(Cursor::new(&stream), framing).unwrap();
+
+        let mut reader = PeekablePacketReader::new(Cursor::new(&stream), framing);
+        assert_eq!(reader.peek_address().unwrap(), "/volume");
+        // Peeking again doesn't advance.
+        assert!(!reader.peek_is_bundle().unwrap());
+        assert_eq!(reader.peek_address().unwrap(), "/volume");
+        let peeked: Volume = de::from_read(&mut reader, framing).unwrap();
+        assert_eq!(peeked, direct);
+
+        // The next packet is left intact.
+        assert!(reader.peek_is_bundle().unwrap());
+        assert_eq!(reader.peek_address().unwrap(), "#bundle");
+        let bundle: OscBundle = de::from_read(&mut reader, framing).unwrap();
+        assert_eq!(bundle.len(), 1);
+    }
+}
+
+#[test]
+fn without_peeking() {
+    let stream = stream(Framing::Framed);
+    let mut reader = PeekablePacketReader::new(Cursor::new(&stream), Framing::Framed);
+    let packets: Vec<OscPacket> = de::packets_from_read(&mut reader, Framing::Framed)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(packets.len(), 2);
+    assert_eq!(packets[0].as_message().unwrap().args(), &[OscArg::F32(0.5), OscArg::I32(3)]);
+}
+
+#[test]
+fn unframed() {
+    let packet = ser::to_vec(&("/only".to_owned(), (1i32,)), Framing::Unframed).unwrap();
+    let mut reader = PeekablePacketReader::new(&packet[..], Framing::Unframed);
+    assert_eq!(reader.peek_address().unwrap(), "/only");
+    let msg: OscMessage = de::from_read(&mut reader, Framing::Unframed).unwrap();
+    assert_eq!(msg.args(), &[OscArg::I32(1)]);
+}
+
+#[test]
+fn truncated() {
+    let mut stream = stream(Framing::Framed);
+    stream.truncate(6);
+    let mut reader = PeekablePacketReader::new(Cursor::new(stream), Framing::Framed);
+    assert!(reader.peek_address().unwrap_err().is_io());
+}
+
+#[test]
+fn too_long() {
+    let config = OscConfig::default().max_packet_size(16);
+    for &framing in &[Framing::Framed, Framing::Unframed, Framing::Slip] {
+        let packet = ser::to_vec(&("/too/long/for/the/limit".to_owned(), (1i32,)), framing).unwrap();
+        let mut reader = PeekablePacketReader::with_config(&packet[..], framing, config);
+        assert!(reader.peek_address().unwrap_err().is_format());
+
+        let packet = ser::to_vec(&("/short".to_owned(), ()), framing).unwrap();
+        let mut reader = PeekablePacketReader::with_config(&packet[..], framing, config);
+        assert_eq!(reader.peek_address().unwrap(), "/short");
+    }
+
+    // Without a config, a huge length prefix fails without being allocated.
+    let mut reader = PeekablePacketReader::new(&b"\x7f\xff\xff\xfc/a\0\0"[..], Framing::Framed);
+    assert!(reader.peek_address().unwrap_err().is_format());
+}
@@ -0,0 +1,79 @@
+use std::io::Cursor;
+use serde_osc::{de, ser, to_udp_payload, OscArg, OscMessage, OscTimeTag};
+use serde_osc::error::Error;
+
+#[test]
+fn read_all_args() {
+    let data = b",isb[ft]\0\0\0\0\
+        \x00\x00\x00\x2a\
+        hi\0\0\
+        \x00\x00\x00\x03\x01\x02\x03\0\
+        \x3f\x80\x00\x00\
+        \x00\x00\x00\x02\x80\x00\x00\x00";
+    let mut reader = Cursor::new(&data[..]);
+    let args = de::read_all_args(&mut reader).unwrap();
+    assert_eq!(args, [
+        OscArg::I32(42),
+        OscArg::String("hi".to_owned()),
+        OscArg::Blob(vec![1, 2, 3]),
+        OscArg::Array(vec![OscArg::F32(1.0), OscArg::TimeTag(OscTimeTag{ seconds: 2, fraction: 0x8000_0000 })]),
+    ]);
+    // Exactly the message's bytes were consumed.
+    assert_eq!(reader.position() as usize, data.len());
+}
+
+#[test]
+fn read_address_and_args() {
+    let mut reader = Cursor::new(&b"/a/b\0\0\0\0,TN\0"[..]);
+    let (address, args) = de::read_address_and_args(&mut reader).unwrap();
+    assert_eq!(address, "/a/b");
+    assert_eq!(args, [OscArg::Bool(true), OscArg::Nil]);
+}
+
+#[test]
+fn read_args_errors() {
+    // Truncated data.
+    let mut reader = Cursor::new(&b",i\0\0\x00\x00"[..]);
+    assert!(de::read_all_args(&mut reader).unwrap_err().is_io());
+    // Unknown type.
+    let mut reader = Cursor::new(&b",iZ\0\x00\x00\x00\x01"[..]);
+    match de::read_all_args(&mut reader) {
+        Err(Error::UnsupportedType{ type_code: b'Z', arg_index: 1 }) => (),
+        other => panic!("expected UnsupportedType, got {:?}", other),
+    }
+}
+
+#[test]
+fn raw_message_roundtrip() {
+    let msg = OscMessage::new("/synth/*/freq", vec![OscArg::F32(440.0), OscArg::String("sine".to_owned())]).unwrap();
+    let data = b"\x43\xdc\x00\x00sine\0\0\0\0";
+    let mut raw = Vec::new();
+    ser::write_message_raw(&mut raw, "/synth/*/freq", ",fs", data).unwrap();
+    assert_eq!(raw, to_udp_payload(&msg).unwrap());
+
+    let (address, type_tag, args) = de::read_message_raw(&mut Cursor::new(&raw)).unwrap();
+    assert_eq!(address, "/synth/*/freq");
+    assert_eq!(type_tag, ",fs");
+    assert_eq!(args, data);
+
+    // Any well-formed pattern can be forwarded.
+    let mut raw = Vec::new();
+    ser::write_message_raw(&mut raw, "/{left,right}/[0-9]?/*", ",", b"").unwrap();
+    assert_eq!(de::read_message_raw(&mut Cursor::new(&raw)).unwrap().0, "/{left,right}/[0-9]?/*");
+}
+
+#[test]
+fn raw_message_errors() {
+    let mut raw = Vec::new();
+    assert!(matches!(ser::write_message_raw(&mut raw, "synth", ",", b""), Err(Error::InvalidAddress{ .. })));
+    // Addresses are checked with the same rules as address patterns.
+    assert!(ser::write_message_raw(&mut raw, "/synth/[0-9", ",", b"").is_err());
+    assert!(ser::write_message_raw(&mut raw, "/synth/{a,b", ",", b"").is_err());
+    assert!(ser::write_message_raw(&mut raw, "/synth name", ",", b"").is_err());
+    assert!(ser::write_message_raw(&mut raw, "/synth", "f", b"\0\0\0\0").unwrap_err().is_format());
+    assert!(ser::write_message_raw(&mut raw, "/synth", ",f", b"\0\0").unwrap_err().is_format());
+    assert!(raw.is_empty());
+
+    let bundle = b"#bundle\0\x00\x00\x00\x00\x00\x00\x00\x01";
+    assert!(de::read_message_raw(&mut Cursor::new(&bundle[..])).unwrap_err().is_format());
+}
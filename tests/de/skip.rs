@@ -0,0 +1,46 @@
+use std::io::{Cursor, Read};
+use serde_bytes::ByteBuf;
+use serde_osc::{de, ser, Framing, OscArg, OscMessage};
+use serde_osc::error::Error;
+
+#[test]
+fn skip_packet() {
+    let first = OscMessage::new("/first", vec![OscArg::String("skip me".to_owned())]).unwrap();
+    let second = OscMessage::new("/second", vec![OscArg::I32(2)]).unwrap();
+    let mut stream = ser::to_vec(&first, Framing::Framed).unwrap();
+    let first_len = stream.len();
+    stream.extend(ser::to_vec(&second, Framing::Framed).unwrap());
+
+    let mut reader = Cursor::new(stream);
+    de::skip_packet(&mut reader).unwrap();
+    assert_eq!(reader.position() as usize, first_len);
+    let msg: OscMessage = de::from_read(&mut reader, Framing::Framed).unwrap();
+    assert_eq!(msg, second);
+    // Skipping past the end fails.
+    assert!(de::skip_packet(&mut reader).unwrap_err().is_io());
+}
+
+#[test]
+fn skip_truncated_packet() {
+    let mut reader = Cursor::new(b"\x00\x00\x00\x08/a\0\0".to_vec());
+    assert!(de::skip_packet(&mut reader).unwrap_err().is_io());
+}
+
+#[test]
+fn skip_arg() {
+    let args = (1i32, "four".to_owned(), ByteBuf::from(vec![1u8, 2, 3, 4, 5]), 2.0f64, true, "end".to_owned());
+    let packet = ser::to_vec(&("/a".to_owned(), args), Framing::Unframed).unwrap();
+    // Skip the address (4 bytes) and type tag ",isbdTs" (8 bytes).
+    let mut reader = Cursor::new(&packet[12..]);
+    for &tag in b"isbdT" {
+        de::skip_arg(&mut reader, tag).unwrap();
+    }
+    let mut rest = Vec::new();
+    reader.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, b"end\0");
+
+    match de::skip_arg(&mut Cursor::new(&packet[..]), b'x') {
+        Err(Error::UnsupportedType{ type_code: b'x', .. }) => (),
+        other => panic!("got {:?}", other),
+    }
+}
@@ -0,0 +1,65 @@
+use serde_osc::{de, ser, osc_address, osc_addressed, Framing, OscAddress, OscArg, OscDispatch, OscMessage};
+
+#[derive(Debug, PartialEq, OscDispatch)]
+enum Command {
+    #[osc_path = "/synth/*/freq"]
+    Freq(f32),
+    #[osc_path = "/synth/note"]
+    Note { pitch: i32, velocity: f32 },
+    #[osc_path = "/stop"]
+    Stop,
+}
+
+fn msg(address: &str, args: Vec<OscArg>) -> OscMessage {
+    OscMessage::new(address, args).unwrap()
+}
+
+#[test]
+fn dispatch() {
+    assert_eq!(Command::dispatch(&msg("/synth/1/freq", vec![OscArg::F32(440.0)])).unwrap().unwrap(),
+        Command::Freq(440.0));
+    assert_eq!(Command::dispatch(&msg("/synth/note", vec![OscArg::I32(60), OscArg::F32(0.5)])).unwrap().unwrap(),
+        Command::Note{ pitch: 60, velocity: 0.5 });
+    assert_eq!(Command::dispatch(&msg("/stop", vec![])).unwrap().unwrap(), Command::Stop);
+}
+
+#[test]
+fn no_match() {
+    assert!(Command::dispatch(&msg("/synth/1/gain", vec![OscArg::F32(1.0)])).is_none());
+}
+
+#[test]
+fn wrong_args() {
+    match Command::dispatch(&msg("/synth/note", vec![OscArg::String("C4".to_owned())])) {
+        Some(Err(_)) => (),
+        other => panic!("got {:?}", other),
+    }
+}
+
+#[osc_addressed]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Gain {
+    #[osc_address = "/synth/gain"]
+    address: (),
+    args: (f32,),
+}
+
+#[test]
+fn fixed_address() {
+    let gain = Gain{ address: (), args: (0.5,) };
+    let bytes = ser::to_vec(&gain, Framing::Unframed).unwrap();
+    let expected: Vec<u8> = ser::to_vec(&("/synth/gain", (0.5f32,)), Framing::Unframed).unwrap();
+    assert_eq!(bytes, expected);
+    assert_eq!(de::from_slice::<Gain>(&bytes, Framing::Unframed).unwrap(), gain);
+}
+
+#[test]
+fn wrong_fixed_address() {
+    let bytes = ser::to_vec(&("/synth/freq", (0.5f32,)), Framing::Unframed).unwrap();
+    assert!(de::from_slice::<Gain>(&bytes, Framing::Unframed).is_err());
+}
+
+#[test]
+fn address_macro() {
+    assert_eq!(osc_address!("/synth/gain"), OscAddress::new("/synth/gain").unwrap());
+}
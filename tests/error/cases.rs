@@ -0,0 +1,73 @@
+//! One malformed packet for each way in which deserialization can fail,
+//! checking that it's reported as the specific error variant.
+
+use std::io;
+use serde_osc::{de, Framing, OscError, OscMessage, OscPacket};
+
+fn parse(data: &[u8]) -> OscError {
+    de::from_slice::<OscPacket>(data, Framing::Unframed).unwrap_err()
+}
+
+#[test]
+fn test_bad_padding_string() {
+    match parse(b"/a\0\x01,\0\0\0") {
+        OscError::BadPadding{ offset: 3 } => (),
+        other => panic!("expected BadPadding, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_bad_padding_blob() {
+    match parse(b"/a\0\0,b\0\0\x00\x00\x00\x01\x07\0\x01\0") {
+        OscError::BadPadding{ offset: 14 } => (),
+        other => panic!("expected BadPadding, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_unsupported_type() {
+    match parse(b"/a\0\0,ix\0\x00\x00\x00\x01") {
+        OscError::UnsupportedType{ type_code: b'x', arg_index: 1 } => (),
+        other => panic!("expected UnsupportedType, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_bad_format_empty() {
+    match parse(b"") {
+        OscError::BadFormat{ offset: 0, .. } => (),
+        other => panic!("expected BadFormat, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_truncated_mid_address() {
+    match parse(b"/ab") {
+        OscError::Io(ref err) if err.kind() == io::ErrorKind::UnexpectedEof => (),
+        other => panic!("expected Io, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_truncated_mid_arg() {
+    match parse(b"/a\0\0,i\0\0\x00\x00") {
+        OscError::Io(ref err) if err.kind() == io::ErrorKind::UnexpectedEof => (),
+        other => panic!("expected Io, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_bad_cast_negative_blob_size() {
+    match parse(b"/a\0\0,b\0\0\xff\xff\xff\xff") {
+        OscError::BadCast(_) => (),
+        other => panic!("expected BadCast, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_str_parse_error() {
+    match de::from_slice::<OscMessage>(b"/\xff\xfe\0,\0\0\0", Framing::Unframed).unwrap_err() {
+        OscError::StrParseError(_) => (),
+        other => panic!("expected StrParseError, got {:?}", other),
+    }
+}
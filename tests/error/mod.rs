@@ -0,0 +1,56 @@
+mod cases;
+
+use std::io;
+use std::io::Cursor;
+use anyhow;
+use serde_osc::{de, Framing, OscError, OscPacket};
+
+#[test]
+fn io_source() {
+    // Truncated packet
+    let test_input = b"\x00\x00\x00\x08/a\0\0";
+    let error = de::from_read::<OscPacket, _>(Cursor::new(&test_input[..]), Framing::Framed).unwrap_err();
+    let error = anyhow::Error::from(error);
+    let chain = error.chain().collect::<Vec<_>>();
+    assert_eq!(chain.len(), 2);
+    assert_eq!(chain[1].downcast_ref::<io::Error>().unwrap().kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn nested_source() {
+    let error = OscError::InBundle {
+        element_index: 0,
+        inner: Box::new(OscError::Io(io::Error::from(io::ErrorKind::BrokenPipe))),
+    };
+    let chain = anyhow::Error::from(error).chain().count();
+    assert_eq!(chain, 3);
+}
+
+#[test]
+fn predicates() {
+    use std::convert::TryFrom;
+    let bad_cast = u8::try_from(256u32).unwrap_err();
+    let errors = vec![
+        OscError::Io(io::Error::from(io::ErrorKind::BrokenPipe)),
+        OscError::BadFormat{ offset: 0, context: "test" },
+        OscError::UnsupportedType{ type_code: b'x', arg_index: 0 },
+        OscError::BadPadding{ offset: 0 },
+        OscError::BadCast(bad_cast),
+    ];
+    let predicates: [fn(&OscError) -> bool; 5] = [
+        OscError::is_io,
+        OscError::is_format,
+        OscError::is_unsupported_type,
+        OscError::is_bad_padding,
+        OscError::is_bad_cast,
+    ];
+    for (i, error) in errors.into_iter().enumerate() {
+        for (j, predicate) in predicates.iter().enumerate() {
+            assert_eq!(predicate(&error), i == j, "{:?}", error);
+        }
+        // Predicates look through bundles.
+        let nested = OscError::InBundle{ element_index: 0, inner: Box::new(error) };
+        assert!(predicates[i](&nested));
+    }
+    assert!(predicates.iter().all(|predicate| !predicate(&OscError::UnsupportedValue)));
+}
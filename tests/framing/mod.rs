@@ -0,0 +1,64 @@
+mod slip;
+
+use std::io::Cursor;
+use serde_osc::{de, ser, Framing, OscArg, OscBundle, OscMessage, OscPacket};
+
+#[test]
+fn unframed_message() {
+    let test_input = ("/a".to_owned(), (1i32,));
+    let expected = b"/a\0\0,i\0\0\x00\x00\x00\x01".to_vec();
+
+    let serialized = ser::to_vec(&test_input, Framing::Unframed).unwrap();
+    assert_eq!(serialized, expected);
+    // Identical to the framed encoding, minus the length.
+    assert_eq!(ser::to_vec(&test_input, Framing::Framed).unwrap()[4..], expected[..]);
+
+    let deserialized: (String, (i32,)) = de::from_slice(&serialized, Framing::Unframed).unwrap();
+    assert_eq!(deserialized, test_input);
+}
+
+#[test]
+fn unframed_bundle() {
+    let msg = OscMessage::new("/m", vec![OscArg::I32(1)]).unwrap();
+    let mut bundle = OscBundle::empty((0, 1));
+    bundle.push(msg.clone());
+    bundle.push(msg);
+    // Only the outermost length is omitted; elements keep theirs.
+    let expected = b"#bundle\0\x00\x00\x00\x00\x00\x00\x00\x01\
+        \x00\x00\x00\x0c/m\0\0,i\0\0\x00\x00\x00\x01\
+        \x00\x00\x00\x0c/m\0\0,i\0\0\x00\x00\x00\x01".to_vec();
+
+    let serialized = ser::to_vec(&bundle, Framing::Unframed).unwrap();
+    assert_eq!(serialized, expected);
+
+    let deserialized: OscBundle = de::from_slice(&serialized, Framing::Unframed).unwrap();
+    assert_eq!(deserialized.len(), 2);
+}
+
+#[test]
+fn udp_payload() {
+    let msg = OscMessage::new("/udp", vec![OscArg::F32(0.5)]).unwrap();
+    let payload = serde_osc::to_udp_payload(&msg).unwrap();
+    assert_eq!(payload, ser::to_vec(&msg, Framing::Unframed).unwrap());
+
+    let packet: OscPacket = serde_osc::from_udp_payload(&payload).unwrap();
+    assert_eq!(packet.as_message().unwrap().address(), "/udp");
+}
+
+#[test]
+fn tcp_stream() {
+    let first = ("/a".to_owned(), (1i32,));
+    let second = ("/b".to_owned(), (2i32,));
+    let mut stream = Cursor::new(Vec::new());
+    serde_osc::to_tcp_stream(&mut stream, &first).unwrap();
+    serde_osc::to_tcp_stream(&mut stream, &second).unwrap();
+
+    let mut expected = ser::to_vec(&first, Framing::Framed).unwrap();
+    expected.extend(ser::to_vec(&second, Framing::Framed).unwrap());
+    assert_eq!(stream.get_ref(), &expected);
+
+    stream.set_position(0);
+    let read_first: (String, (i32,)) = serde_osc::from_tcp_stream(&mut stream).unwrap();
+    let read_second: (String, (i32,)) = serde_osc::from_tcp_stream(&mut stream).unwrap();
+    assert_eq!((read_first, read_second), (first, second));
+}
@@ -0,0 +1,76 @@
+use std::io::{Cursor, Read};
+use serde_osc::{de, ser, Framing, OscArg, OscMessage, OscPacket};
+use serde_osc::framing::slip::{self, SlipFramed};
+use serde_osc::error::Error;
+
+#[test]
+fn escapes() {
+    assert_eq!(slip::encode(b"\xc0"), b"\xc0\xdb\xdc\xc0".to_vec());
+    assert_eq!(slip::encode(b"\xdb"), b"\xc0\xdb\xdd\xc0".to_vec());
+    assert_eq!(slip::encode(b"a\xc0b\xdbc"), b"\xc0a\xdb\xdcb\xdb\xddc\xc0".to_vec());
+
+    assert_eq!(slip::decode(b"\xc0\xdb\xdc\xc0").unwrap(), b"\xc0".to_vec());
+    assert_eq!(slip::decode(b"\xc0\xdb\xdd\xc0").unwrap(), b"\xdb".to_vec());
+    // The END bytes are optional when decoding a single packet.
+    assert_eq!(slip::decode(b"a\xdb\xdcb").unwrap(), b"a\xc0b".to_vec());
+}
+
+#[test]
+fn bad_escapes() {
+    match slip::decode(b"\xc0\xdb\x01\xc0") {
+        Err(Error::BadFormat{ .. }) => {},
+        other => panic!("expected BadFormat, got {:?}", other),
+    }
+    match slip::decode(b"\xc0a\xdb") {
+        Err(Error::BadFormat{ .. }) => {},
+        other => panic!("expected BadFormat, got {:?}", other),
+    }
+    // Two packets where one was expected.
+    match slip::decode(b"\xc0a\xc0b\xc0") {
+        Err(Error::BadFormat{ .. }) => {},
+        other => panic!("expected BadFormat, got {:?}", other),
+    }
+}
+
+#[test]
+fn round_trip() {
+    let data: Vec<u8> = (0..=255).collect();
+    assert_eq!(slip::decode(&slip::encode(&data)).unwrap(), data);
+}
+
+#[test]
+fn packet_round_trip() {
+    // Blob containing both special bytes.
+    let msg = OscMessage::new("/serial", vec![OscArg::Blob(vec![0xc0, 0xdb, 0x00, 0x01])]).unwrap();
+    let serialized = ser::to_vec(&msg, Framing::Slip).unwrap();
+    assert_eq!(serialized, slip::encode(&ser::to_vec(&msg, Framing::Unframed).unwrap()));
+
+    // Consecutive packets can be read from one stream.
+    let mut stream = serialized.clone();
+    stream.extend(serialized);
+    let mut read = Cursor::new(stream);
+    let first: OscMessage = de::from_read(&mut read, Framing::Slip).unwrap();
+    let second: OscPacket = de::from_read(&mut read, Framing::Slip).unwrap();
+    match first.args()[0] {
+        OscArg::Blob(ref blob) => assert_eq!(blob, &[0xc0, 0xdb, 0x00, 0x01]),
+        ref other => panic!("expected a blob, got {:?}", other),
+    }
+    assert!(second.is_message());
+}
+
+#[test]
+fn slip_framed_reader() {
+    let mut encoded = slip::encode(b"hello");
+    encoded.extend(slip::encode(b"\xc0"));
+    let mut read = SlipFramed::new(Cursor::new(encoded));
+
+    // Data from only one packet is returned by each read, split if it doesn't fit.
+    let mut buf = [0; 3];
+    assert_eq!(read.read(&mut buf).unwrap(), 3);
+    assert_eq!(&buf, b"hel");
+    assert_eq!(read.read(&mut buf).unwrap(), 2);
+    assert_eq!(&buf[..2], b"lo");
+    assert_eq!(read.read(&mut buf).unwrap(), 1);
+    assert_eq!(buf[0], 0xc0);
+    assert_eq!(read.read(&mut buf).unwrap(), 0);
+}
@@ -1,8 +1,49 @@
+extern crate anyhow;
+extern crate proptest;
 #[macro_use]
 extern crate serde_derive;
 extern crate serde;
 extern crate serde_bytes;
 extern crate serde_osc;
+#[cfg(feature = "json")]
+extern crate serde_json;
+#[cfg(feature = "bytes")]
+extern crate bytes;
+#[cfg(feature = "tokio")]
+extern crate tokio;
+#[cfg(feature = "tokio")]
+extern crate tokio_stream;
+#[cfg(feature = "tokio")]
+extern crate tokio_test;
+#[cfg(feature = "tokio")]
+extern crate tokio_util;
+#[cfg(feature = "testing")]
+extern crate arbitrary;
+#[cfg(feature = "tracing")]
+extern crate tracing;
+#[cfg(feature = "tracing")]
+extern crate tracing_test;
 
+mod address;
+#[cfg(feature = "tokio")]
+mod async_io;
+mod builder;
+#[cfg(feature = "tokio")]
+mod codec;
+mod config;
 mod de;
+#[cfg(feature = "derive")]
+mod dispatch;
+mod error;
+mod framing;
+mod namespace;
+mod packet;
+mod pattern;
+mod router;
+mod schedule;
 mod ser;
+mod spec_conformance;
+#[cfg(feature = "tracing")]
+mod trace;
+mod types;
+mod udp;
@@ -0,0 +1,96 @@
+use serde_osc::{OscAddress, OscNamespace, OscNodeInfo};
+use serde_osc::namespace::{ACCESS_NONE, ACCESS_READ_WRITE};
+
+fn addr(address: &str) -> OscAddress {
+    OscAddress::new(address).unwrap()
+}
+
+fn info(description: &str, type_tag: Option<&str>, access: u8) -> OscNodeInfo {
+    OscNodeInfo {
+        description: description.to_owned(),
+        type_tag: type_tag.map(str::to_owned),
+        access,
+    }
+}
+
+fn synth() -> OscNamespace {
+    let mut namespace = OscNamespace::new();
+    namespace.insert(&addr("/synth/1/freq"), info("Frequency of voice 1", Some("f"), ACCESS_READ_WRITE));
+    namespace.insert(&addr("/synth/2/freq"), info("Frequency of voice 2", Some("f"), ACCESS_READ_WRITE));
+    namespace.insert(&addr("/synth/reset"), info("Silence all voices", None, ACCESS_NONE));
+    namespace
+}
+
+#[test]
+fn insert_get_remove() {
+    let mut namespace = synth();
+    assert_eq!(namespace.get(&addr("/synth/reset")), Some(&info("Silence all voices", None, ACCESS_NONE)));
+    // Prefixes of described addresses aren't described themselves.
+    assert_eq!(namespace.get(&addr("/synth")), None);
+    assert_eq!(namespace.get(&addr("/synth/3/freq")), None);
+
+    let replaced = namespace.insert(&addr("/synth/reset"), info("Reset", None, ACCESS_NONE));
+    assert_eq!(replaced, Some(info("Silence all voices", None, ACCESS_NONE)));
+
+    assert!(namespace.remove(&addr("/synth/1/freq")));
+    assert!(!namespace.remove(&addr("/synth/1/freq")));
+    assert!(!namespace.remove(&addr("/synth")));
+    assert_eq!(namespace.get(&addr("/synth/1/freq")), None);
+    assert!(namespace.get(&addr("/synth/2/freq")).is_some());
+
+    namespace.remove(&addr("/synth/2/freq"));
+    namespace.remove(&addr("/synth/reset"));
+    assert_eq!(namespace, OscNamespace::new());
+}
+
+#[test]
+fn query() {
+    let namespace = synth();
+    assert_eq!(namespace.query("/synth/*/freq"), vec![addr("/synth/1/freq"), addr("/synth/2/freq")]);
+    assert_eq!(namespace.query("/synth/{reset,mute}"), vec![addr("/synth/reset")]);
+    assert_eq!(namespace.query("/synth/*"), vec![addr("/synth/reset")]);
+    assert!(namespace.query("/synth/[1").is_empty());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn query_json() {
+    let expected = ::serde_json::json!({
+        "FULL_PATH": "/",
+        "CONTENTS": {
+            "synth": {
+                "FULL_PATH": "/synth",
+                "CONTENTS": {
+                    "1": {
+                        "FULL_PATH": "/synth/1",
+                        "CONTENTS": {
+                            "freq": {
+                                "FULL_PATH": "/synth/1/freq",
+                                "DESCRIPTION": "Frequency of voice 1",
+                                "TYPE": "f",
+                                "ACCESS": 3,
+                            },
+                        },
+                    },
+                    "2": {
+                        "FULL_PATH": "/synth/2",
+                        "CONTENTS": {
+                            "freq": {
+                                "FULL_PATH": "/synth/2/freq",
+                                "DESCRIPTION": "Frequency of voice 2",
+                                "TYPE": "f",
+                                "ACCESS": 3,
+                            },
+                        },
+                    },
+                    "reset": {
+                        "FULL_PATH": "/synth/reset",
+                        "DESCRIPTION": "Silence all voices",
+                        "ACCESS": 0,
+                    },
+                },
+            },
+        },
+    });
+    assert_eq!(synth().to_query_json(), expected);
+}
@@ -0,0 +1,37 @@
+use arbitrary::{Arbitrary, Unstructured};
+use serde_osc::{from_udp_payload, to_udp_payload, OscAddress, OscMessage, OscPacket};
+
+/// Deterministic pseudo-random bytes (xorshift), to feed `Unstructured`.
+fn random_bytes(seed: u64, len: usize) -> Vec<u8> {
+    let mut state = seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1;
+    (0..len).map(|_| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state as u8
+    }).collect()
+}
+
+#[test]
+fn arbitrary_messages_roundtrip() {
+    for seed in 0..1000 {
+        let data = random_bytes(seed, 512);
+        let msg = OscMessage::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        assert!(OscAddress::new(msg.address()).is_ok(), "invalid address {:?}", msg.address());
+        // Compare the serialized forms, as NaN arguments aren't equal to themselves.
+        let bytes = to_udp_payload(&msg).unwrap();
+        let parsed: OscMessage = from_udp_payload(&bytes).unwrap();
+        assert_eq!(to_udp_payload(&parsed).unwrap(), bytes);
+    }
+}
+
+#[test]
+fn arbitrary_packets_roundtrip() {
+    for seed in 0..1000 {
+        let data = random_bytes(seed, 1024);
+        let packet = OscPacket::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        let bytes = to_udp_payload(&packet).unwrap();
+        let parsed: OscPacket = from_udp_payload(&bytes).unwrap();
+        assert_eq!(to_udp_payload(&parsed).unwrap(), bytes);
+    }
+}
@@ -0,0 +1,161 @@
+use serde_osc::{de, ser, Framing, OscArg, OscBundle, OscMessage, OscPacket};
+
+/// The same bundle as in `tests/ser/bundle.rs`.
+fn two_messages() -> OscBundle {
+    let mut bundle = OscBundle::empty((0x01020304, 0x05060708));
+    bundle.push(OscMessage::new("/m1", vec![OscArg::I32(0x5eeeeeed)]).unwrap());
+    bundle.push(OscMessage::new("/m2", vec![OscArg::F32(440.0)]).unwrap());
+    bundle
+}
+
+#[test]
+fn bundle_wire_format() {
+    let bundle = two_messages();
+    let expected = b"\x00\x00\x00\x30#bundle\0\x01\x02\x03\x04\x05\x06\x07\x08\x00\x00\x00\x0C/m1\0,i\0\0\x5E\xEE\xEE\xED\x00\x00\x00\x0C/m2\0,f\0\0\x43\xdc\x00\x00".to_vec();
+    assert_eq!(bundle.len(), 2);
+    assert_eq!(4 + bundle.byte_len(), expected.len());
+    assert_eq!(ser::to_vec(&bundle, Framing::Framed).unwrap(), expected);
+
+    let deserialized: OscBundle = de::from_slice(&expected, Framing::Framed).unwrap();
+    assert_eq!(deserialized.timetag(), (0x01020304, 0x05060708).into());
+    let addresses: Vec<_> = deserialized.into_iter().map(|elem| match elem {
+        OscPacket::Message(msg) => msg.address().to_owned(),
+        other => panic!("got {:?}", other),
+    }).collect();
+    assert_eq!(addresses, ["/m1", "/m2"]);
+}
+
+#[test]
+fn empty_bundle() {
+    let bundle = OscBundle::empty((0, 1));
+    let expected = b"\x00\x00\x00\x10#bundle\0\x00\x00\x00\x00\x00\x00\x00\x01".to_vec();
+    assert!(bundle.is_empty());
+    assert_eq!(4 + bundle.byte_len(), expected.len());
+    assert_eq!(ser::to_vec(&bundle, Framing::Framed).unwrap(), expected);
+
+    let deserialized: OscBundle = de::from_slice(&expected, Framing::Framed).unwrap();
+    assert_eq!(deserialized.timetag(), (0, 1).into());
+    assert!(deserialized.is_empty());
+}
+
+#[test]
+fn nested_bundle() {
+    let mut bundle = OscBundle::empty((2, 3));
+    bundle.push(two_messages());
+    bundle.push(OscMessage::new("/m3", vec![]).unwrap());
+    let serialized = ser::to_vec(&bundle, Framing::Framed).unwrap();
+    assert_eq!(4 + bundle.byte_len(), serialized.len());
+
+    let deserialized: OscBundle = de::from_slice(&serialized, Framing::Framed).unwrap();
+    match *deserialized.elements() {
+        [OscPacket::Bundle(ref inner), OscPacket::Message(ref msg)] => {
+            assert_eq!(inner.len(), 2);
+            assert_eq!(msg.address(), "/m3");
+        },
+        ref other => panic!("got {:?}", other),
+    }
+    assert_eq!(ser::to_vec(&deserialized, Framing::Framed).unwrap(), serialized);
+}
+
+#[test]
+fn collect_and_extend() {
+    let messages = vec![
+        OscMessage::new("/m1", vec![]).unwrap(),
+        OscMessage::new("/m2", vec![]).unwrap(),
+    ];
+    let mut bundle = messages.clone().into_iter().collect::<OscBundle>();
+    assert!(bundle.timetag().is_immediate());
+    let collected: Vec<_> = (&bundle).into_iter().cloned().collect();
+    assert_eq!(collected, messages.iter().cloned().map(OscPacket::from).collect::<Vec<_>>());
+
+    bundle.extend(vec![OscBundle::empty((0, 1))]);
+    assert_eq!(bundle.len(), 3);
+    assert!(bundle.elements()[2].is_bundle());
+    bundle.elements_mut()[0] = OscMessage::new("/m0", vec![]).unwrap().into();
+    match bundle.elements()[0] {
+        OscPacket::Message(ref msg) => assert_eq!(msg.address(), "/m0"),
+        ref other => panic!("got {:?}", other),
+    }
+}
+
+#[test]
+fn display() {
+    let mut bundle = OscBundle::empty((1, 0x80000000));
+    bundle.push(two_messages());
+    bundle.push(OscMessage::new("/m3", vec![]).unwrap());
+    assert_eq!(format!("{}", OscPacket::from(bundle)), "#bundle @1.500000000\n  \
+        #bundle @16909060.019623221\n    \
+        /m1 ,i 1592717037\n    \
+        /m2 ,f 440\n  \
+        /m3 ,");
+    assert_eq!(format!("{}", OscBundle::empty((0, 1))), "#bundle @immediate");
+}
+
+fn msg(address: &str) -> OscMessage {
+    OscMessage::new(address, vec![]).unwrap()
+}
+
+#[test]
+fn merge() {
+    let early = OscBundle::new((5, 0), vec![msg("/a").into(), OscBundle::new((7, 0), vec![msg("/b").into()]).into()]);
+    let late = OscBundle::new((6, 0), vec![msg("/c").into()]);
+    let merged = late.clone().merge(early.clone());
+    assert_eq!(merged.timetag(), (5, 0).into());
+    assert_eq!(merged.len(), 3);
+    assert_eq!(merged.elements()[0], msg("/c").into());
+    assert_eq!(merged.elements()[2], early.elements()[1]);
+    assert_eq!(early.merge(late).timetag(), (5, 0).into());
+}
+
+#[test]
+fn sort_by_timetag() {
+    let mut bundle = OscBundle::new((5, 0), vec![
+        OscBundle::new((9, 0), vec![msg("/late").into()]).into(),
+        msg("/a").into(),
+        OscBundle::new((6, 0), vec![
+            OscBundle::new((8, 0), vec![]).into(),
+            msg("/b").into(),
+        ]).into(),
+        msg("/c").into(),
+    ]);
+    bundle.sort_by_timetag();
+    let flat: Vec<_> = bundle.flatten().into_iter().map(|(_, m)| m.address().to_owned()).collect();
+    assert_eq!(flat, ["/a", "/c", "/b", "/late"]);
+    match bundle.elements()[2] {
+        OscPacket::Bundle(ref inner) => assert!(inner.elements()[0].is_message()),
+        ref other => panic!("got {:?}", other),
+    }
+}
+
+#[test]
+fn flatten() {
+    let bundle = OscBundle::new((1, 0), vec![
+        msg("/outer").into(),
+        OscBundle::new((2, 0), vec![msg("/inner1").into(), msg("/inner2").into()]).into(),
+    ]);
+    assert_eq!(bundle.flatten(), vec![
+        ((1, 0).into(), msg("/outer")),
+        ((2, 0).into(), msg("/inner1")),
+        ((2, 0).into(), msg("/inner2")),
+    ]);
+    assert!(OscBundle::empty((0, 1)).flatten().is_empty());
+}
+
+#[test]
+fn find_messages() {
+    let bundle = OscBundle::new((1, 0), vec![
+        msg("/audio/volume").into(),
+        OscBundle::new((2, 0), vec![
+            msg("/video/fade").into(),
+            OscBundle::new((3, 0), vec![msg("/audio/pan").into(), msg("/target").into()]).into(),
+        ]).into(),
+    ]);
+    assert_eq!(bundle.find_message("/target"), Some(&msg("/target")));
+    assert_eq!(bundle.find_message("/video/fade"), Some(&msg("/video/fade")));
+    assert_eq!(bundle.find_message("/missing"), None);
+    // Exact matches only: no pattern matching.
+    assert_eq!(bundle.find_message("/tar*"), None);
+
+    assert_eq!(bundle.find_all_messages("/audio/*"), vec![&msg("/audio/volume"), &msg("/audio/pan")]);
+    assert!(bundle.find_all_messages("/nothing/*").is_empty());
+}
@@ -0,0 +1,322 @@
+use proptest::prelude::*;
+use serde_bytes::ByteBuf;
+use serde_osc::{de, ser, to_udp_payload, Framing, MessageBuilder, OscArg, OscColor, OscMessage, OscPacket, OscTimeTag};
+use serde_osc::error::Error;
+
+/// Serialize `msg`, check it against `byte_len`, and deserialize it again.
+fn roundtrip(msg: &OscMessage) -> OscMessage {
+    let serialized = ser::to_vec(msg, Framing::Framed).unwrap();
+    // byte_len excludes the length prefix.
+    assert_eq!(serialized.len(), 4 + msg.byte_len());
+    let deserialized: OscMessage = de::from_slice(&serialized, Framing::Framed).unwrap();
+    // Serializing again must produce the same packet.
+    assert_eq!(ser::to_vec(&deserialized, Framing::Framed).unwrap(), serialized);
+    deserialized
+}
+
+#[test]
+fn no_args() {
+    let msg = OscMessage::new("/empty", vec![]).unwrap();
+    assert_eq!(ser::to_vec(&msg, Framing::Framed).unwrap(), b"\x00\x00\x00\x0c/empty\0\0,\0\0\0".to_vec());
+    let deserialized = roundtrip(&msg);
+    assert_eq!(deserialized.address(), "/empty");
+    assert_eq!(deserialized.args_count(), 0);
+}
+
+#[test]
+fn one_arg() {
+    let msg = OscMessage::new("/one".to_owned(), vec![OscArg::I32(-1)]).unwrap();
+    // Matches the equivalent typed message.
+    assert_eq!(ser::to_vec(&msg, Framing::Framed).unwrap(), ser::to_vec(&("/one".to_owned(), (-1i32,)), Framing::Framed).unwrap());
+    let deserialized = roundtrip(&msg);
+    assert_eq!(deserialized.address(), "/one");
+    match *deserialized.args() {
+        [OscArg::I32(-1)] => (),
+        ref other => panic!("got {:?}", other),
+    }
+}
+
+#[test]
+fn mixed_args() {
+    let msg = OscMessage::new("/mixed", vec![
+        OscArg::F32(0.5),
+        OscArg::String("str".to_owned()),
+        OscArg::Blob(vec![1, 2, 3, 4, 5]),
+        OscArg::Array(vec![OscArg::Bool(true), OscArg::Nil, OscArg::I64(7)]),
+    ]).unwrap();
+    let deserialized = roundtrip(&msg);
+    assert_eq!(deserialized.args_count(), 4);
+    match deserialized.args()[2] {
+        OscArg::Blob(ref b) => assert_eq!(b, &ByteBuf::from(vec![1, 2, 3, 4, 5]).to_vec()),
+        ref other => panic!("got {:?}", other),
+    }
+}
+
+#[test]
+fn byte_len_timetag() {
+    // An array of exactly two u32s is serialized as a timetag.
+    let msg = OscMessage::new("/t", vec![OscArg::Array(vec![OscArg::U32(1), OscArg::U32(2)])]).unwrap();
+    roundtrip(&msg);
+}
+
+#[test]
+fn bad_address() {
+    match OscMessage::new("no/slash", vec![]) {
+        Err(Error::InvalidAddress{ position: 0, invalid_char: 'n', .. }) => (),
+        other => panic!("expected InvalidAddress, got {:?}", other),
+    }
+    // Patterns are allowed, and serialize like any other address.
+    for pattern in &["/*/volume", "/synth/*/freq", "/synth/{1,2}/[a-c]?"] {
+        let msg = OscMessage::new(*pattern, vec![OscArg::F32(1.0)]).unwrap();
+        let bytes: Vec<u8> = msg.clone().into();
+        assert_eq!(de::from_slice::<OscMessage>(&bytes, Framing::Framed).unwrap(), msg);
+    }
+    // Anything the serializer would reject is rejected up front.
+    for address in &["/a b", "/a#", "/a,b", "/a/[b", "/a/{b"] {
+        assert!(OscMessage::new(*address, vec![]).is_err(), "{:?}", address);
+        assert!(MessageBuilder::new(address).is_err(), "{:?}", address);
+        assert!(OscMessage::from_parts(address, b",", b"").is_err(), "{:?}", address);
+    }
+}
+
+#[test]
+fn equality() {
+    let args = vec![OscArg::I32(1), OscArg::String("two".to_owned()), OscArg::F32(3.0)];
+    let msg = OscMessage::new("/a", args.clone()).unwrap();
+    assert_eq!(msg, OscMessage::new("/a", args).unwrap());
+    assert_eq!(roundtrip(&msg), msg);
+
+    let differing = OscMessage::new("/a", vec![OscArg::I32(1), OscArg::String("two".to_owned()), OscArg::F32(3.5)]).unwrap();
+    assert_ne!(msg, differing);
+
+    // Floats compare by their bits: NaN is equal to itself, but the zeros differ.
+    let nan = OscMessage::new("/a", vec![OscArg::F32(f32::NAN)]).unwrap();
+    assert_eq!(nan, nan.clone());
+    assert_eq!(roundtrip(&nan), nan);
+    assert_ne!(OscMessage::new("/a", vec![OscArg::F64(0.0)]).unwrap(),
+        OscMessage::new("/a", vec![OscArg::F64(-0.0)]).unwrap());
+}
+
+#[test]
+fn hash() {
+    use std::collections::HashMap;
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    let hash = |msg: &OscMessage| {
+        let mut hasher = DefaultHasher::new();
+        msg.hash(&mut hasher);
+        hasher.finish()
+    };
+    let msg = OscMessage::new("/a", vec![OscArg::I32(1), OscArg::F64(2.0), OscArg::Array(vec![OscArg::Nil])]).unwrap();
+    assert_eq!(hash(&msg), hash(&msg));
+    assert_eq!(hash(&msg), hash(&msg.clone()));
+
+    // Messages can key a map directly, including ones holding NaN.
+    let nan = OscMessage::new("/a", vec![OscArg::F32(f32::NAN)]).unwrap();
+    let mut counts = HashMap::new();
+    for m in &[msg.clone(), nan.clone(), msg.clone(), OscMessage::new("/b", vec![]).unwrap(), nan.clone()] {
+        *counts.entry(m.clone()).or_insert(0) += 1;
+    }
+    assert_eq!(counts.len(), 3);
+    assert_eq!(counts[&msg], 2);
+    assert_eq!(counts[&nan], 2);
+}
+
+#[test]
+fn indexed_args() {
+    let mut msg = OscMessage::new("/a", vec![OscArg::I32(1), OscArg::F32(2.0)]).unwrap();
+    assert_eq!(msg.args_count(), 2);
+    assert_eq!(msg.arg(1), Some(&OscArg::F32(2.0)));
+    assert_eq!(msg.arg(2), None);
+    *msg.arg_mut(0).unwrap() = OscArg::String("one".to_owned());
+    assert_eq!(msg.arg(0), Some(&OscArg::String("one".to_owned())));
+    assert!(msg.arg_mut(2).is_none());
+}
+
+#[test]
+fn type_string() {
+    let msg = OscMessage::new("/a", vec![OscArg::I32(1), OscArg::F32(2.0), OscArg::String("s".to_owned())]).unwrap();
+    assert_eq!(msg.type_string(), ",ifs");
+
+    let msg = OscMessage::new("/a", vec![
+        OscArg::Bool(true), OscArg::Bool(false), OscArg::Nil,
+        OscArg::Array(vec![OscArg::I64(1), OscArg::Array(vec![])]),
+        OscArg::Array(vec![OscArg::U32(2), OscArg::U32(3)]),
+        OscArg::Blob(vec![4]),
+    ]).unwrap();
+    let deserialized = roundtrip(&msg);
+    // The type tag follows the 4-byte length prefix and the 4-byte address.
+    let serialized = ser::to_vec(&msg, Framing::Framed).unwrap();
+    let wire_tag = &serialized[8..8 + msg.type_string().len()];
+    assert_eq!(deserialized.type_string().as_bytes(), wire_tag);
+    assert_eq!(deserialized.type_string(), ",TFN[h[]]tb");
+}
+
+#[test]
+fn display() {
+    let msg = OscMessage::new("/address", vec![
+        OscArg::I32(42), OscArg::F32(2.5), OscArg::String("hello".to_owned()),
+    ]).unwrap();
+    let formatted = format!("{}", msg);
+    assert_eq!(formatted, r#"/address ,ifs 42 2.5 "hello""#);
+    assert_eq!(format!("{}", OscArg::I32(42)), "42i");
+    assert_eq!(format!("{}", OscArg::F32(2.5)), "2.5f");
+    assert_eq!(format!("{}", OscArg::String("hello".to_owned())), r#""hello""#);
+    assert_eq!(format!("{}", OscArg::Blob(vec![0; 5])), "<5 bytes blob>");
+    assert_eq!(format!("{}", OscArg::Array(vec![OscArg::I64(1), OscArg::Nil])), "[1h nil]");
+}
+
+#[test]
+fn build_incrementally() {
+    let mut msg = OscMessage::new("/build", vec![]).unwrap();
+    msg.push_arg(OscArg::F32(440.0));
+    msg.push_arg(OscArg::Blob(vec![1, 2, 3]));
+    msg.insert_arg(0, OscArg::I32(0x01020304));
+    msg.extend(vec![OscArg::Nil, OscArg::String("s".to_owned())]);
+    msg.push_arg(OscArg::Bool(true));
+    assert_eq!(msg.pop_arg(), Some(OscArg::Bool(true)));
+    assert_eq!(msg.remove_arg(3), OscArg::Nil);
+
+    let expected = b"/build\0\0,ifbs\0\0\0\x01\x02\x03\x04\x43\xdc\x00\x00\x00\x00\x00\x03\x01\x02\x03\0s\0\0\0".to_vec();
+    assert_eq!(to_udp_payload(&msg).unwrap(), expected);
+    let prebuilt = OscMessage::new("/build", vec![
+        OscArg::I32(0x01020304), OscArg::F32(440.0), OscArg::Blob(vec![1, 2, 3]), OscArg::String("s".to_owned()),
+    ]).unwrap();
+    assert_eq!(msg, prebuilt);
+
+    let mut empty = OscMessage::new("/a", vec![]).unwrap();
+    assert_eq!(empty.pop_arg(), None);
+}
+
+#[test]
+fn map_and_retain() {
+    let msg = OscMessage::new("/mixer/volume", vec![OscArg::I32(3), OscArg::F32(0.8)]).unwrap();
+    let scaled = msg.map_arg(1, |arg| OscArg::F32(arg.as_f32().unwrap() * 0.5)).unwrap();
+    assert_eq!(scaled.args(), &[OscArg::I32(3), OscArg::F32(0.4)][..]);
+    // The original is untouched.
+    assert_eq!(msg.arg(1), Some(&OscArg::F32(0.8)));
+    match msg.map_arg(2, |arg| arg) {
+        Err(Error::ArgMiscount{ expected: 3, found: 2 }) => {},
+        other => panic!("expected ArgMiscount, got {:?}", other),
+    }
+
+    let mut mixed = OscMessage::new("/mixed", vec![
+        OscArg::I32(1), OscArg::String("a".to_owned()), OscArg::I32(2), OscArg::F32(2.5), OscArg::I64(3),
+    ]).unwrap();
+    mixed.retain_args(|arg| arg.as_i32().is_none());
+    assert_eq!(mixed.args(), &[OscArg::String("a".to_owned()), OscArg::F32(2.5), OscArg::I64(3)][..]);
+}
+
+#[test]
+fn try_match() {
+    let messages = [
+        OscMessage::new("/video/brightness", vec![OscArg::F32(0.5)]).unwrap(),
+        OscMessage::new("/audio/gain", vec![OscArg::F32(0.5), OscArg::I32(2)]).unwrap(),
+        OscMessage::new("/audio/pan", vec![OscArg::String("left".to_owned())]).unwrap(),
+    ];
+    let results: Vec<Option<Result<(f32, i32), Error>>> = messages.iter()
+        .map(|msg| msg.try_match("/audio/*"))
+        .collect();
+    assert!(results[0].is_none());
+    match results[1] {
+        Some(Ok((gain, channel))) => assert_eq!((gain, channel), (0.5, 2)),
+        ref other => panic!("got {:?}", other),
+    }
+    match results[2] {
+        Some(Err(_)) => (),
+        ref other => panic!("got {:?}", other),
+    }
+    assert!(messages[1].try_match::<(f32, i32)>("/audio/[").is_none());
+}
+
+#[test]
+fn deserialize_args_into() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Gain {
+        level: f32,
+        channel: i32,
+    }
+    let msg = OscMessage::new("/foo", vec![OscArg::F32(2.0), OscArg::I32(1)]).unwrap();
+    assert_eq!(msg.deserialize_args_into::<(f32, i32)>().unwrap(), (2.0, 1));
+    assert_eq!(msg.deserialize_args_into::<Gain>().unwrap(), Gain{ level: 2.0, channel: 1 });
+    match msg.deserialize_args_into::<(f32, i32, String)>() {
+        Err(Error::ArgMiscount{ expected: 3, found: 2 }) => (),
+        other => panic!("expected ArgMiscount, got {:?}", other),
+    }
+}
+
+fn arb_arg() -> impl Strategy<Value = OscArg> {
+    let leaf = prop_oneof![
+        any::<i32>().prop_map(OscArg::I32),
+        any::<f32>().prop_map(OscArg::F32),
+        "[a-z ]{0,9}".prop_map(OscArg::String),
+        prop::collection::vec(any::<u8>(), 0..9).prop_map(OscArg::Blob),
+        any::<i64>().prop_map(OscArg::I64),
+        any::<f64>().prop_map(OscArg::F64),
+        (0x20u8..0x7f).prop_map(|c| OscArg::Char(c as char)),
+        any::<[u8; 4]>().prop_map(|c| OscArg::Color(OscColor(c))),
+        any::<bool>().prop_map(OscArg::Bool),
+        Just(OscArg::Nil),
+        Just(OscArg::Impulse),
+        "[a-z]{0,9}".prop_map(OscArg::Symbol),
+        any::<(u32, u32)>().prop_map(|t| OscArg::TimeTag(OscTimeTag::from(t))),
+        any::<u32>().prop_map(OscArg::U32),
+    ];
+    leaf.prop_recursive(2, 8, 4, |inner| prop::collection::vec(inner, 0..4).prop_map(OscArg::Array))
+}
+
+proptest! {
+    #[test]
+    fn byte_len_matches_payload(address in "(/[a-z]{1,6}){1,3}", args in prop::collection::vec(arb_arg(), 0..6)) {
+        let msg = OscMessage::new(address, args).unwrap();
+        prop_assert_eq!(msg.byte_len(), to_udp_payload(&msg).unwrap().len());
+    }
+
+    #[test]
+    fn measured_size_matches_serialized(address in "(/[a-z]{1,6}){1,3}", args in prop::collection::vec(arb_arg(), 0..6)) {
+        let msg = OscMessage::new(address, args).unwrap();
+        for &framing in &[Framing::Framed, Framing::Unframed, Framing::Slip] {
+            prop_assert_eq!(ser::measure_packet_size(&msg, framing).unwrap(), ser::to_vec(&msg, framing).unwrap().len());
+        }
+    }
+}
+
+#[test]
+fn wire_size() {
+    assert_eq!(OscArg::I32(0).wire_size(), 4);
+    assert_eq!(OscArg::String("abc".to_owned()).wire_size(), 4);
+    assert_eq!(OscArg::String("abcd".to_owned()).wire_size(), 8);
+    assert_eq!(OscArg::Blob(vec![]).wire_size(), 4);
+    assert_eq!(OscArg::Blob(vec![1; 5]).wire_size(), 12);
+    assert_eq!(OscArg::Nil.wire_size(), 0);
+}
+
+#[test]
+fn osc_message_macro() {
+    let msg = ::serde_osc::osc_message!("/a/b", 42i32, 3.25f32, "hello", b"\xde\xad", 7i64, true);
+    let explicit = OscMessage::new("/a/b", vec![
+        OscArg::I32(42), OscArg::F32(3.25), OscArg::String("hello".to_owned()),
+        OscArg::Blob(b"\xde\xad".to_vec()), OscArg::I64(7), OscArg::Bool(true),
+    ]).unwrap();
+    assert_eq!(msg, explicit);
+    assert_eq!(to_udp_payload(&msg).unwrap(), to_udp_payload(&explicit).unwrap());
+    assert_eq!(::serde_osc::osc_message!("/empty").args_count(), 0);
+}
+
+#[test]
+fn without_length_prefix() {
+    let msg = OscMessage::new("/a", vec![OscArg::I32(1), OscArg::String("two".to_owned())]).unwrap();
+    let mut output = b"header".to_vec();
+    msg.serialize_without_length_prefix(&mut output).unwrap();
+    assert_eq!(&output[..6], b"header");
+    assert_eq!(output[6..], to_udp_payload(&msg).unwrap()[..]);
+
+    // Arrays, and addresses that aren't checked until serialization, behave as with to_write.
+    let nested = OscMessage::new("/n", vec![OscArg::Array(vec![OscArg::Nil, OscArg::F64(0.5)])]).unwrap();
+    let mut output = Vec::new();
+    nested.serialize_without_length_prefix(&mut output).unwrap();
+    assert_eq!(output, to_udp_payload(&nested).unwrap());
+    let renamed = OscPacket::from(msg).transform_addresses(|_| Ok::<_, ()>("no slash".to_owned())).unwrap();
+    let renamed = renamed.as_message().unwrap();
+    assert!(matches!(renamed.serialize_without_length_prefix(&mut Vec::new()), Err(Error::InvalidAddress{ .. })));
+}
@@ -0,0 +1,7 @@
+//! Tests for the concrete packet types.
+mod bundle;
+mod message;
+#[cfg(feature = "testing")]
+mod arbitrary;
+mod osc_packet;
+mod roundtrip_prop;
@@ -0,0 +1,85 @@
+use std::convert::TryFrom;
+use std::io::Cursor;
+use serde_osc::{de, ser, Framing, OscArg, OscBundle, OscMessage, OscPacket};
+
+#[test]
+fn accessors() {
+    let msg: OscPacket = OscMessage::new("/m", vec![]).unwrap().into();
+    let bundle: OscPacket = OscBundle::empty((0, 1)).into();
+
+    assert!(msg.is_message() && !msg.is_bundle());
+    assert!(bundle.is_bundle() && !bundle.is_message());
+    assert_eq!(msg.as_message().unwrap().address(), "/m");
+    assert!(msg.as_bundle().is_none());
+    assert_eq!(bundle.as_bundle().unwrap().timetag(), (0, 1).into());
+    assert!(bundle.as_message().is_none());
+    assert_eq!(msg.address(), Some("/m"));
+    assert_eq!(bundle.address(), None);
+    assert_eq!(msg.timetag(), None);
+    assert_eq!(bundle.timetag(), Some((0, 1).into()));
+}
+
+#[test]
+fn try_from() {
+    let msg: OscPacket = OscMessage::new("/m", vec![]).unwrap().into();
+    assert!(OscBundle::try_from(msg.clone()).unwrap_err().is_format());
+    assert_eq!(OscMessage::try_from(msg).unwrap().address(), "/m");
+
+    let bundle: OscPacket = OscBundle::empty((0, 1)).into();
+    assert!(OscMessage::try_from(bundle.clone()).unwrap_err().is_format());
+    assert_eq!(OscBundle::try_from(bundle).unwrap().timetag(), (0, 1).into());
+}
+
+#[test]
+fn read_mixed_stream() {
+    let msg = OscMessage::new("/m", vec![OscArg::I32(1)]).unwrap();
+    let mut bundle = OscBundle::empty((0, 1));
+    bundle.push(msg.clone());
+
+    let mut stream = ser::to_vec(&msg, Framing::Framed).unwrap();
+    stream.extend(ser::to_vec(&bundle, Framing::Framed).unwrap());
+
+    // We don't know ahead of time whether each packet is a message or a bundle.
+    let mut read = Cursor::new(stream);
+    let first: OscPacket = de::from_read(&mut read, Framing::Framed).unwrap();
+    let second: OscPacket = de::from_read(&mut read, Framing::Framed).unwrap();
+    assert!(first.is_message());
+    assert_eq!(second.as_bundle().unwrap().len(), 1);
+}
+
+#[test]
+fn bytes_conversion() {
+    let msg = OscMessage::new("/m", vec![OscArg::I32(1)]).unwrap();
+    let packet = OscPacket::from(msg.clone());
+    assert_eq!(OscMessage::try_from(packet.clone()).unwrap(), msg);
+
+    let bytes = Vec::from(msg.clone());
+    assert_eq!(bytes, ser::to_vec(&msg, Framing::Framed).unwrap());
+    assert_eq!(OscPacket::try_from(bytes).unwrap(), packet);
+    assert!(OscPacket::try_from(vec![0, 0, 0, 4]).is_err());
+}
+
+#[test]
+#[should_panic]
+fn bytes_conversion_invalid() {
+    let msg = OscMessage::new("/has space", vec![]).unwrap();
+    let _ = Vec::from(msg);
+}
+
+#[test]
+fn transform_addresses() {
+    let msg = |address: &str, arg| OscPacket::from(OscMessage::new(address, vec![OscArg::I32(arg)]).unwrap());
+    let inner = OscBundle::new((5, 0), vec![msg("/b", 2), msg("/c", 3)]);
+    let packet = OscPacket::Bundle(OscBundle::new((1, 0), vec![msg("/a", 1), inner.into()]));
+
+    let proxied = packet.transform_addresses(|address| Ok::<_, ()>(format!("/proxy{}", address))).unwrap();
+    let inner = OscBundle::new((5, 0), vec![msg("/proxy/b", 2), msg("/proxy/c", 3)]);
+    assert_eq!(proxied, OscPacket::Bundle(OscBundle::new((1, 0), vec![msg("/proxy/a", 1), inner.into()])));
+
+    // The first error is returned.
+    let result = packet.transform_addresses(|address| match address {
+        "/a" => Ok(address.to_owned()),
+        other => Err(other.to_owned()),
+    });
+    assert_eq!(result, Err("/b".to_owned()));
+}
@@ -0,0 +1,48 @@
+//! Property-based roundtrip tests, aimed at padding and alignment bugs.
+use proptest::prelude::*;
+use serde_osc::{from_udp_payload, to_udp_payload, OscArg, OscBundle, OscMessage, OscTimeTag};
+
+/// Arguments of the four OSC 1.0 types, favoring lengths around
+/// the 4-byte padding boundary.
+fn arb_arg() -> impl Strategy<Value = OscArg> {
+    prop_oneof![
+        prop_oneof![Just(i32::MIN), Just(i32::MAX), Just(0), any::<i32>()].prop_map(OscArg::I32),
+        // NaN would never compare equal to itself.
+        any::<f32>().prop_filter("NaN", |f| !f.is_nan()).prop_map(OscArg::F32),
+        "[a-zA-Z0-9 ]{0,5}".prop_map(OscArg::String),
+        "[a-zA-Z0-9 ]{0,40}".prop_map(OscArg::String),
+        prop::collection::vec(any::<u8>(), 0..6).prop_map(OscArg::Blob),
+        prop::collection::vec(any::<u8>(), 0..40).prop_map(OscArg::Blob),
+    ]
+}
+
+fn arb_message() -> impl Strategy<Value = OscMessage> {
+    ("(/[a-z]{0,5}){1,3}", prop::collection::vec(arb_arg(), 0..8))
+        .prop_map(|(address, args)| OscMessage::new(address, args).unwrap())
+}
+
+fn arb_bundle() -> impl Strategy<Value = OscBundle> {
+    (any::<(u32, u32)>(), prop::collection::vec(arb_message(), 0..=10))
+        .prop_map(|(timetag, messages)| messages.into_iter().fold(
+            OscBundle::empty(OscTimeTag::from(timetag)),
+            |mut bundle, msg| {
+                bundle.push(msg);
+                bundle
+            }))
+}
+
+proptest! {
+    #[test]
+    fn message_roundtrip(msg in arb_message()) {
+        let payload = to_udp_payload(&msg).unwrap();
+        prop_assert_eq!(payload.len() % 4, 0);
+        prop_assert_eq!(from_udp_payload::<OscMessage>(&payload).unwrap(), msg);
+    }
+
+    #[test]
+    fn bundle_roundtrip(bundle in arb_bundle()) {
+        let payload = to_udp_payload(&bundle).unwrap();
+        prop_assert_eq!(payload.len() % 4, 0);
+        prop_assert_eq!(from_udp_payload::<OscBundle>(&payload).unwrap(), bundle);
+    }
+}
@@ -0,0 +1,143 @@
+use serde_osc::{match_osc_pattern, OscAddressPattern};
+use serde_osc::error::Error;
+
+fn assert_matches(pattern: &str, address: &str) {
+    assert!(match_osc_pattern(pattern, address), "{:?} should match {:?}", pattern, address);
+}
+fn assert_no_match(pattern: &str, address: &str) {
+    assert!(!match_osc_pattern(pattern, address), "{:?} shouldn't match {:?}", pattern, address);
+}
+
+#[test]
+fn literal() {
+    assert_matches("/a/b", "/a/b");
+    assert_no_match("/a/b", "/a/bc");
+    assert_no_match("/a/b", "/a");
+    assert_no_match("/a", "/a/b");
+}
+
+#[test]
+fn any_char() {
+    assert_matches("/a?c", "/abc");
+    assert_no_match("/a?c", "/ac");
+    assert_no_match("/a?", "/a/");
+}
+
+#[test]
+fn any_sequence() {
+    assert_matches("/*", "/anything");
+    assert_matches("/*", "/");
+    assert_matches("/a*z", "/az");
+    assert_matches("/a*z", "/abcz");
+    assert_no_match("/a*z", "/abc");
+    // Wildcards don't cross '/'.
+    assert_no_match("/*", "/a/b");
+    assert_matches("/*/*", "/a/b");
+}
+
+#[test]
+fn adjacent_wildcards() {
+    assert_matches("/**", "/abc");
+    assert_matches("/*?", "/a");
+    assert_no_match("/*?", "/");
+    assert_matches("/?*?", "/ab");
+    assert_no_match("/??*", "/a");
+}
+
+#[test]
+fn char_set() {
+    assert_matches("/[abc]", "/b");
+    assert_no_match("/[abc]", "/d");
+    assert_no_match("/[abc]", "/ab");
+    assert_no_match("/[]", "/a");
+}
+
+#[test]
+fn negated_char_set() {
+    assert_matches("/[!abc]", "/d");
+    assert_no_match("/[!abc]", "/a");
+    // A '!' anywhere else is literal.
+    assert_matches("/[a!]", "/!");
+}
+
+#[test]
+fn char_range() {
+    assert_matches("/[a-z]", "/m");
+    assert_no_match("/[a-z]", "/M");
+    assert_matches("/[0-9a-f]", "/c");
+    assert_no_match("/[!0-9]", "/5");
+    // A trailing '-' is literal.
+    assert_matches("/[a-]", "/-");
+}
+
+#[test]
+fn alternatives() {
+    assert_matches("/{foo,bar}", "/foo");
+    assert_matches("/{foo,bar}", "/bar");
+    assert_no_match("/{foo,bar}", "/baz");
+    assert_matches("/{foo,foobar}", "/foobar");
+    assert_matches("/x{,y}", "/x");
+    assert_matches("/x{,y}", "/xy");
+}
+
+#[test]
+fn empty_alternatives() {
+    assert_matches("/a{}b", "/ab");
+    assert_no_match("/a{}b", "/axb");
+}
+
+#[test]
+fn combinations() {
+    assert_matches("/synth/[0-9]/{volume,pan}", "/synth/3/pan");
+    assert_no_match("/synth/[0-9]/{volume,pan}", "/synth/x/pan");
+    assert_matches("/*/osc?/{freq,phase}*", "/voice/osc1/frequency");
+    assert_matches("/[!a-c]*{1,2}", "/d-long-name2");
+    assert_no_match("/[!a-c]*{1,2}", "/a-long-name2");
+}
+
+#[test]
+fn compile() {
+    let pattern = OscAddressPattern::compile("/?*[!a-z]/{a,b}").unwrap();
+    assert!(pattern.matches("/xyZ/b"));
+    assert!(!pattern.matches("/xyz/b"));
+    assert_eq!(&*pattern, "/?*[!a-z]/{a,b}");
+}
+
+#[test]
+fn compile_errors() {
+    let cases = [
+        ("no/slash", 0, 'n'),
+        ("/a b", 2, ' '),
+        ("/a#", 2, '#'),
+        ("/a,b", 2, ','),
+        ("/a]", 2, ']'),
+        ("/a}", 2, '}'),
+        ("/[ab", 1, '['),
+        ("/{a,b", 1, '{'),
+        ("/[a/b]", 3, '/'),
+        ("/{a,[b]}", 4, '['),
+    ];
+    for &(pattern, position, c) in &cases {
+        match OscAddressPattern::compile(pattern) {
+            Err(Error::InvalidAddress{ position: p, invalid_char, .. }) => {
+                assert_eq!((p, invalid_char), (position, c), "for {:?}", pattern);
+            },
+            other => panic!("expected InvalidAddress for {:?}, got {:?}", pattern, other),
+        }
+    }
+}
+
+#[test]
+fn many_wildcards() {
+    // Each '*' could match at many places, which must not be tried
+    // in every combination: this would otherwise take far too long.
+    use std::time::{Duration, Instant};
+    let start = Instant::now();
+    let name = "a".repeat(40);
+    let pattern = format!("/{}b", "*a".repeat(30));
+    assert_no_match(&pattern, &format!("/{}", name));
+    assert_matches(&format!("/{}*", "*a".repeat(30)), &format!("/{}", name));
+    assert_no_match(&format!("/{}", "*".repeat(10_000)), "/a/b");
+    assert_matches(&format!("/{}", "{a,,aa}".repeat(50)), &format!("/{}", name));
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
@@ -0,0 +1,122 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use serde_osc::{OscArg, OscBundle, OscDispatcher, OscError, OscMessage, OscPacket, OscRouter};
+
+type Log = Rc<RefCell<Vec<(usize, String)>>>;
+
+/// A router whose handlers record which of them was called, and with what address.
+fn recording_router(patterns: &[&str]) -> (OscRouter, Log) {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut router = OscRouter::new();
+    for (index, pattern) in patterns.iter().enumerate() {
+        let log = log.clone();
+        router.add_route(pattern, move |msg| {
+            log.borrow_mut().push((index, msg.address().to_owned()));
+            Ok(())
+        }).unwrap();
+    }
+    (router, log)
+}
+
+fn msg(address: &str) -> OscMessage {
+    OscMessage::new(address, vec![OscArg::I32(1)]).unwrap()
+}
+
+#[test]
+fn first_match_wins() {
+    let (router, log) = recording_router(&["/audio/*", "/*"]);
+    assert!(router.dispatch(&msg("/audio/gain")).unwrap());
+    assert!(router.dispatch(&msg("/video")).unwrap());
+    assert!(!router.dispatch(&msg("/video/brightness")).unwrap());
+    assert_eq!(*log.borrow(), [(0, "/audio/gain".to_owned()), (1, "/video".to_owned())]);
+
+    // Registered the other way round, the general pattern shadows nothing
+    // here, since '*' doesn't match across a '/'.
+    let (router, log) = recording_router(&["/*", "/audio/*"]);
+    assert!(router.dispatch(&msg("/audio/gain")).unwrap());
+    assert_eq!(*log.borrow(), [(1, "/audio/gain".to_owned())]);
+
+    let (router, log) = recording_router(&["/audio/*", "/audio/{gain,pan}"]);
+    assert!(router.dispatch(&msg("/audio/gain")).unwrap());
+    assert_eq!(*log.borrow(), [(0, "/audio/gain".to_owned())]);
+}
+
+#[test]
+fn handler_error() {
+    let mut router = OscRouter::new();
+    router.add_route("/fail", |_| Err(OscError::Message("handler failed".to_owned()))).unwrap();
+    match router.dispatch(&msg("/fail")) {
+        Err(OscError::Message(ref m)) if m == "handler failed" => (),
+        other => panic!("got {:?}", other),
+    }
+}
+
+#[test]
+fn bad_pattern() {
+    let mut router = OscRouter::new();
+    match router.add_route("/a/[b", |_| Ok(())) {
+        Err(OscError::InvalidAddress{ position: 3, invalid_char: '[', .. }) => (),
+        other => panic!("got {:?}", other),
+    }
+    assert!(!router.dispatch(&msg("/a/b")).unwrap());
+    assert_eq!(format!("{:?}", router), "OscRouter { routes: [] }");
+}
+
+/// A dispatcher whose handlers record which of them was called, and with what address.
+fn recording_dispatcher(patterns: &[&str]) -> (OscDispatcher, Log) {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut dispatcher = OscDispatcher::new();
+    for (index, pattern) in patterns.iter().enumerate() {
+        let log = log.clone();
+        dispatcher.add_handler(pattern, move |msg| {
+            log.borrow_mut().push((index, msg.address().to_owned()));
+        }).unwrap();
+    }
+    (dispatcher, log)
+}
+
+#[test]
+fn dispatcher_calls_every_match() {
+    let (mut dispatcher, log) = recording_dispatcher(&["/audio/*", "/audio/play"]);
+    assert_eq!(dispatcher.dispatch(&msg("/audio/play")), 2);
+    assert_eq!(dispatcher.dispatch(&msg("/video/play")), 0);
+    assert_eq!(*log.borrow(), [(0, "/audio/play".to_owned()), (1, "/audio/play".to_owned())]);
+}
+
+#[test]
+fn dispatcher_remove_handler() {
+    let mut dispatcher = OscDispatcher::new();
+    let count = Rc::new(RefCell::new(0));
+    let general = {
+        let count = count.clone();
+        dispatcher.add_handler("/audio/*", move |_| *count.borrow_mut() += 1).unwrap()
+    };
+    {
+        let count = count.clone();
+        dispatcher.add_handler("/audio/play", move |_| *count.borrow_mut() += 10).unwrap();
+    }
+    dispatcher.remove_handler(general);
+    // Removing twice is harmless.
+    dispatcher.remove_handler(general);
+    assert_eq!(dispatcher.dispatch(&msg("/audio/play")), 1);
+    assert_eq!(*count.borrow(), 10);
+    assert_eq!(format!("{:?}", dispatcher), "OscDispatcher { handlers: [OscAddressPattern(\"/audio/play\")] }");
+}
+
+#[test]
+fn dispatcher_packet() {
+    let (mut dispatcher, log) = recording_dispatcher(&["/audio/*"]);
+    let packet = OscPacket::Bundle(OscBundle::new((1, 0), vec![
+        msg("/audio/gain").into(),
+        OscBundle::new((2, 0), vec![msg("/video/gain").into(), msg("/audio/pan").into()]).into(),
+    ]));
+    dispatcher.dispatch_packet(&packet);
+    dispatcher.dispatch_packet(&msg("/audio/play").into());
+    assert_eq!(*log.borrow(), [
+        (0, "/audio/gain".to_owned()), (0, "/audio/pan".to_owned()), (0, "/audio/play".to_owned()),
+    ]);
+    match dispatcher.add_handler("/a/{b", |_| ()) {
+        Err(OscError::InvalidAddress{ position: 3, invalid_char: '{', .. }) => (),
+        other => panic!("got {:?}", other),
+    }
+}
@@ -0,0 +1,44 @@
+use std::cell::RefCell;
+use std::time::Duration;
+use serde_osc::{BundleDispatcher, OscArg, OscBundle, OscMessage, OscTimeTag};
+
+fn msg(address: &str) -> OscMessage {
+    OscMessage::new(address, vec![OscArg::I32(1)]).unwrap()
+}
+
+#[test]
+fn only_due_messages_fire() {
+    let handled = RefCell::new(Vec::new());
+    let mut dispatcher = BundleDispatcher::new(|msg: &OscMessage| handled.borrow_mut().push(msg.address().to_owned()));
+    let soon = OscTimeTag::IMMEDIATE + Duration::from_millis(100);
+    dispatcher.schedule(OscBundle::new(soon, vec![msg("/later").into()]));
+    dispatcher.schedule(OscBundle::new(OscTimeTag::IMMEDIATE, vec![msg("/now").into()]));
+    assert_eq!(dispatcher.next_timetag(), Some(OscTimeTag::IMMEDIATE));
+
+    assert_eq!(dispatcher.poll(OscTimeTag::IMMEDIATE + Duration::from_millis(50)), 1);
+    assert_eq!(*handled.borrow(), vec!["/now"]);
+    assert_eq!(dispatcher.next_timetag(), Some(soon));
+
+    assert_eq!(dispatcher.poll(soon), 1);
+    assert_eq!(*handled.borrow(), vec!["/now", "/later"]);
+    assert_eq!(dispatcher.next_timetag(), None);
+}
+
+#[test]
+fn nested_timetags() {
+    let handled = RefCell::new(Vec::new());
+    let mut dispatcher = BundleDispatcher::new(|msg: &OscMessage| handled.borrow_mut().push(msg.address().to_owned()));
+    let t = |secs| OscTimeTag::IMMEDIATE + Duration::from_secs(secs);
+    // The inner bundle is due before the outer one, and the two
+    // messages due at t(2) must be handled in the order they were scheduled.
+    dispatcher.schedule(OscBundle::new(t(2), vec![
+        msg("/outer/a").into(),
+        OscBundle::new(t(1), vec![msg("/inner").into()]).into(),
+        msg("/outer/b").into(),
+    ]));
+    dispatcher.schedule(OscBundle::new(t(2), vec![msg("/second").into()]));
+
+    assert_eq!(dispatcher.poll(t(1)), 1);
+    assert_eq!(dispatcher.poll(t(3)), 3);
+    assert_eq!(*handled.borrow(), vec!["/inner", "/outer/a", "/outer/b", "/second"]);
+}
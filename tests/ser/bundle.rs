@@ -1,5 +1,6 @@
 use std::io::{Cursor, Write};
 use serde::Serialize;
+use serde_osc::{ser, Framing, OscArg, OscBundle, OscMessage};
 use serde_osc::ser::Serializer;
 
 #[test]
@@ -43,3 +44,24 @@ fn bundle() {
     }
     assert_eq!(output.into_inner(), expected);
 }
+
+#[test]
+fn streamed_matches_buffered() {
+    // to_write streams bundles to the output, whereas a Serializer with
+    // framing must buffer them; both must produce the same bytes.
+    let inner = OscBundle::new((5, 6), vec![OscMessage::new("/inner", vec![OscArg::Blob(vec![1, 2, 3])]).unwrap().into()]);
+    let outer = OscBundle::new((1, 2), vec![
+        OscMessage::new("/a", vec![OscArg::I32(1)]).unwrap().into(),
+        inner.into(),
+        OscMessage::new("/b", vec![]).unwrap().into(),
+    ]);
+    let mut buffered = Vec::new();
+    outer.serialize(&mut Serializer::new(&mut buffered)).unwrap();
+
+    let mut streamed = Vec::new();
+    ser::to_write(&mut streamed, &outer, Framing::Framed).unwrap();
+    assert_eq!(streamed, buffered);
+    assert_eq!(ser::to_vec(&outer, Framing::Framed).unwrap(), buffered);
+    assert_eq!(ser::to_vec(&outer, Framing::Unframed).unwrap(), &buffered[4..]);
+    assert_eq!(ser::measure_packet_size(&outer, Framing::Framed).unwrap(), buffered.len());
+}
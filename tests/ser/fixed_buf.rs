@@ -0,0 +1,35 @@
+use std::io::ErrorKind;
+use serde_osc::{ser, Framing, OscArg, OscMessage};
+use serde_osc::error::Error;
+use serde_osc::ser::FixedBufWriter;
+
+fn msg() -> OscMessage {
+    OscMessage::new("/fixed", vec![OscArg::I32(1), OscArg::String("buffer".to_owned())]).unwrap()
+}
+
+#[test]
+fn fits() {
+    let expected = ser::to_vec(&msg(), Framing::Unframed).unwrap();
+    let mut buf = [0u8; 64];
+    let mut writer = FixedBufWriter::new(&mut buf);
+    ser::to_write(&mut writer, &msg(), Framing::Unframed).unwrap();
+    assert_eq!(writer.written(), &expected[..]);
+
+    // Exactly the right size.
+    let mut buf = vec![0u8; expected.len()];
+    let mut writer = FixedBufWriter::new(&mut buf);
+    ser::to_write(&mut writer, &msg(), Framing::Unframed).unwrap();
+    assert_eq!(writer.written(), &expected[..]);
+}
+
+#[test]
+fn one_byte_short() {
+    let len = ser::to_vec(&msg(), Framing::Unframed).unwrap().len();
+    let mut buf = vec![0u8; len - 1];
+    let mut writer = FixedBufWriter::new(&mut buf);
+    match ser::to_write(&mut writer, &msg(), Framing::Unframed) {
+        Err(Error::Io(ref e)) if e.kind() == ErrorKind::WriteZero => (),
+        other => panic!("got {:?}", other),
+    }
+    assert_eq!(writer.written().len(), len - 1);
+}
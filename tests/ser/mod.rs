@@ -1,4 +1,5 @@
 mod auto_derive;
 mod bundle;
+mod fixed_buf;
 mod tuple;
 
@@ -43,3 +43,30 @@ fn unit_ser() {
     }
     assert_eq!(output.into_inner(), expected);
 }
+
+#[test]
+fn u32_array_ser() {
+    // Looks like a timetag until the third element, at which point
+    // the two u32s already seen must be written out as array elements.
+    let test_input = ("/u".to_owned(), ((1u32, 2u32, 3u32),));
+
+    let expected = b"\x00\x00\x00\x18/u\0\0,[uuu]\0\0\0\0\0\x01\0\0\0\x02\0\0\0\x03".to_vec();
+    let mut output = Cursor::new(Vec::new());
+
+    {
+        let mut test_de = Serializer::new(output.by_ref());
+        test_input.serialize(&mut test_de).unwrap();
+    }
+    assert_eq!(output.into_inner(), expected);
+}
+
+#[test]
+fn into_inner() {
+    // The serializer can own its output, which is then retrieved afterward.
+    let mut ser = Serializer::new(Cursor::new(Vec::new()));
+    ("/ts".to_owned(), ()).serialize(&mut ser).unwrap();
+    assert_eq!(ser.get_ref().position(), 12);
+    ser.get_mut().set_position(0);
+    ("/tt".to_owned(), ()).serialize(&mut ser).unwrap();
+    assert_eq!(ser.into_inner().into_inner(), b"\x00\x00\x00\x08/tt\0,\0\0\0".to_vec());
+}
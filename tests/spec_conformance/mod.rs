@@ -0,0 +1,88 @@
+//! Byte vectors from the OSC 1.0 specification, along with further
+//! hand-verified vectors covering the parts of the format it gives no example of.
+//! Each is checked in both directions.
+
+use std::fmt::Debug;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_osc::{from_udp_payload, to_udp_payload, OscArg, OscBundle, OscMessage, OscPacket, OscTimeTag};
+
+/// The spec's first example message: "/oscillator/4/frequency" with the float 440.0.
+const OSCILLATOR_FREQUENCY: &[u8] = b"\
+    \x2f\x6f\x73\x63\x69\x6c\x6c\x61\x74\x6f\x72\x2f\x34\x2f\x66\x72\
+    \x65\x71\x75\x65\x6e\x63\x79\x00\x2c\x66\x00\x00\x43\xdc\x00\x00";
+
+/// The spec's second example message: "/foo" with 1000, -1, "hello", 1.234 and 5.678.
+const FOO: &[u8] = b"\
+    \x2f\x66\x6f\x6f\x00\x00\x00\x00\x2c\x69\x69\x73\x66\x66\x00\x00\
+    \x00\x00\x03\xe8\xff\xff\xff\xff\x68\x65\x6c\x6c\x6f\x00\x00\x00\
+    \x3f\x9d\xf3\xb6\x40\xb5\xb2\x2d";
+
+/// The spec's example OSC-strings, "OSC" and "data", as arguments of a message.
+const STRINGS: &[u8] = b"/s\0\0,ss\0OSC\0data\0\0\0\0";
+
+/// A blob whose length isn't a multiple of 4 is padded with zeros.
+const BLOB: &[u8] = b"/b\0\0,b\0\0\x00\x00\x00\x05\x01\x02\x03\x04\x05\0\0\0";
+
+/// A message with no arguments still has a type tag, consisting of just the ','.
+const NO_ARGS: &[u8] = b"/n\0\0,\0\0\0";
+
+/// A bundle to be executed immediately, containing a single message.
+const BUNDLE: &[u8] = b"#bundle\0\x00\x00\x00\x00\x00\x00\x00\x01\
+    \x00\x00\x00\x0c/a\0\0,i\0\0\x00\x00\x00\x01";
+
+/// Types which the spec lists as nonstandard: int64, double, true, false, nil, impulse and timetag.
+const NONSTANDARD: &[u8] = b"/x\0\0,hdTFNIt\0\0\0\0\
+    \x00\x00\x00\x01\x00\x00\x00\x00\x3f\xf8\x00\x00\x00\x00\x00\x00\
+    \x00\x00\x00\x02\x80\x00\x00\x00";
+
+fn check<T>(expected_bytes: &[u8], value: T)
+    where T: Serialize + DeserializeOwned + Debug + PartialEq
+{
+    assert_eq!(to_udp_payload(&value).unwrap(), expected_bytes);
+    assert_eq!(from_udp_payload::<T>(expected_bytes).unwrap(), value);
+}
+
+fn msg(address: &str, args: Vec<OscArg>) -> OscMessage {
+    OscMessage::new(address, args).unwrap()
+}
+
+#[test]
+fn spec_examples() {
+    check(OSCILLATOR_FREQUENCY, msg("/oscillator/4/frequency", vec![OscArg::F32(440.0)]));
+    check(FOO, msg("/foo", vec![
+        OscArg::I32(1000), OscArg::I32(-1), OscArg::String("hello".to_owned()),
+        OscArg::F32(1.234), OscArg::F32(5.678),
+    ]));
+    // Statically typed messages encode identically.
+    check(FOO, ("/foo".to_owned(), (1000i32, -1i32, "hello".to_owned(), 1.234f32, 5.678f32)));
+    check(STRINGS, msg("/s", vec![OscArg::String("OSC".to_owned()), OscArg::String("data".to_owned())]));
+}
+
+#[test]
+fn additional_vectors() {
+    check(BLOB, msg("/b", vec![OscArg::Blob(vec![1, 2, 3, 4, 5])]));
+    check(NO_ARGS, msg("/n", vec![]));
+    check(BUNDLE, OscPacket::Bundle(OscBundle::new(OscTimeTag::IMMEDIATE, vec![
+        msg("/a", vec![OscArg::I32(1)]).into(),
+    ])));
+    check(NONSTANDARD, msg("/x", vec![
+        OscArg::I64(1 << 32), OscArg::F64(1.5), OscArg::Bool(true), OscArg::Bool(false),
+        OscArg::Nil, OscArg::Impulse, OscArg::TimeTag(OscTimeTag::from((2, 0x8000_0000))),
+    ]));
+}
+
+#[test]
+fn message_from_parts() {
+    // FOO, split into its address, type tag and argument data.
+    let msg = OscMessage::from_parts("/foo", b",iisff", &FOO[16..]).unwrap();
+    assert_eq!(to_udp_payload(&msg).unwrap(), FOO);
+    // The ',' is optional.
+    assert_eq!(OscMessage::from_parts("/foo", b"iisff", &FOO[16..]).unwrap(), msg);
+
+    let freq = OscMessage::from_parts("/oscillator/4/frequency", b",f", &OSCILLATOR_FREQUENCY[28..]).unwrap();
+    assert_eq!(to_udp_payload(&freq).unwrap(), OSCILLATOR_FREQUENCY);
+    // Too much or too little data for the type tag.
+    assert!(OscMessage::from_parts("/foo", b",iisf", &FOO[16..]).unwrap_err().is_format());
+    assert!(OscMessage::from_parts("/foo", b",iisff", &FOO[16..36]).unwrap_err().is_io());
+}
@@ -0,0 +1,32 @@
+use tracing_test::traced_test;
+use serde_osc::{de, ser, Framing, OscArg, OscBundle, OscMessage, OscPacket};
+
+#[test]
+#[traced_test]
+fn serialize_spans() {
+    let msg = OscMessage::new("/synth/freq", vec![OscArg::F32(440.0), OscArg::I32(1)]).unwrap();
+    ser::to_vec(&msg, Framing::Unframed).unwrap();
+
+    assert!(logs_contain("to_write{framing=Unframed}"));
+    assert!(logs_contain("write_into{framing=Unframed}"));
+    assert!(logs_contain("address=/synth/freq"));
+    assert!(logs_contain("type_string=,fi"));
+    assert!(logs_contain("packet_size_bytes=24"));
+}
+
+#[test]
+#[traced_test]
+fn deserialize_spans() {
+    let mut bundle = OscBundle::empty((0, 1));
+    bundle.push(OscMessage::new("/a", vec![OscArg::I32(1), OscArg::String("two".to_owned())]).unwrap());
+    let packet = ser::to_vec(&bundle, Framing::Framed).unwrap();
+    de::from_read::<OscPacket, _>(&packet[..], Framing::Framed).unwrap();
+
+    assert!(logs_contain("from_read{framing=Framed}"));
+    assert!(logs_contain("deserialize_any{framing=Framed depth=0}"));
+    assert!(logs_contain(&format!("packet_size_bytes={} is_bundle=true", packet.len() - 4)));
+    assert!(logs_contain("bundle_element{element_index=0}"));
+    assert!(logs_contain("packet_size_bytes=16 is_bundle=false"));
+    assert!(logs_contain("type_code=i arg_index=0"));
+    assert!(logs_contain("type_code=s arg_index=1"));
+}
@@ -0,0 +1,33 @@
+use serde_osc::{de, ser, Framing};
+use serde_osc::error::Error;
+
+#[test]
+fn nested_tuple() {
+    let test_input = ("/arr".to_owned(), (1i32, (2.0f32, 3.0f32), 4i32));
+    let expected = b"\x00\x00\x00\x20/arr\0\0\0\0,i[ff]i\0\
+        \x00\x00\x00\x01\x40\x00\x00\x00\x40\x40\x00\x00\x00\x00\x00\x04".to_vec();
+
+    let serialized = ser::to_vec(&test_input, Framing::Framed).unwrap();
+    assert_eq!(serialized, expected);
+    let deserialized: (String, (i32, (f32, f32), i32)) = de::from_slice(&serialized, Framing::Framed).unwrap();
+    assert_eq!(deserialized, test_input);
+}
+
+#[test]
+fn nested_vec() {
+    let test_input = ("/arr".to_owned(), (vec![vec![1i32], vec![], vec![2, 3]],));
+
+    let serialized = ser::to_vec(&test_input, Framing::Framed).unwrap();
+    assert_eq!(&serialized[12..24], b",[[i][][ii]]");
+    let deserialized: (String, (Vec<Vec<i32>>,)) = de::from_slice(&serialized, Framing::Framed).unwrap();
+    assert_eq!(deserialized, test_input);
+}
+
+#[test]
+fn unclosed_array() {
+    let test_input = b"\x00\x00\x00\x10/arr\0\0\0\0,[i\0\x00\x00\x00\x01";
+    match de::from_slice::<(String, (Vec<i32>,))>(test_input, Framing::Framed) {
+        Err(Error::BadFormat{ .. }) => (),
+        other => panic!("expected BadFormat, got {:?}", other),
+    }
+}
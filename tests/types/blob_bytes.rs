@@ -0,0 +1,30 @@
+use bytes::Bytes;
+use serde_osc::{de, ser, Framing, OscArg, OscMessage};
+
+#[test]
+fn clone_shares_buffer() {
+    let arg = OscArg::BlobBytes(Bytes::from(vec![0u8; 4096]));
+    let copy = arg.clone();
+    // Cloning bumps a reference count, rather than copying the data.
+    assert_eq!(arg.as_blob().unwrap().as_ptr(), copy.as_blob().unwrap().as_ptr());
+}
+
+#[test]
+fn serializes_as_blob() {
+    let data = vec![1u8, 2, 3, 4, 5];
+    let shared = OscMessage::new("/b", vec![OscArg::BlobBytes(Bytes::from(data.clone()))]).unwrap();
+    let owned = OscMessage::new("/b", vec![OscArg::Blob(data)]).unwrap();
+    let serialized = ser::to_vec(&shared, Framing::Framed).unwrap();
+    assert_eq!(serialized, ser::to_vec(&owned, Framing::Framed).unwrap());
+    assert_eq!(serialized.len(), 4 + shared.byte_len());
+    assert_eq!(shared.type_string(), ",b");
+
+    // Deserializing produces a Blob, whose buffer can be taken over without copying.
+    let mut deserialized: OscMessage = de::from_slice(&serialized, Framing::Framed).unwrap();
+    assert_eq!(deserialized, owned);
+    let blob = deserialized.remove_arg(0);
+    let ptr = blob.as_blob().unwrap().as_ptr();
+    let bytes = blob.into_bytes().unwrap();
+    assert_eq!(bytes.as_ptr(), ptr);
+    assert_eq!(&bytes[..], &[1, 2, 3, 4, 5]);
+}
@@ -0,0 +1,24 @@
+use serde_osc::{de, ser, Framing};
+
+#[test]
+fn bool_has_no_data() {
+    let without_args = ser::to_vec(&("/b".to_owned(), (1i32,)), Framing::Framed).unwrap();
+    let with_bool = ser::to_vec(&("/b".to_owned(), (1i32, true)), Framing::Framed).unwrap();
+    // ",i" and ",iT" both pad to 4 bytes, and 'T' carries no data.
+    assert_eq!(with_bool.len(), without_args.len());
+}
+
+#[test]
+fn bool_wire_format() {
+    let test_input = ("/b".to_owned(), (true, false));
+    let expected = b"\x00\x00\x00\x08/b\0\0,TF\0".to_vec();
+    assert_eq!(ser::to_vec(&test_input, Framing::Framed).unwrap(), expected);
+}
+
+#[test]
+fn bool_roundtrip() {
+    let test_input = ("/b".to_owned(), (false, 7i32, true));
+    let serialized = ser::to_vec(&test_input, Framing::Framed).unwrap();
+    let deserialized: (String, (bool, i32, bool)) = de::from_slice(&serialized, Framing::Framed).unwrap();
+    assert_eq!(deserialized, test_input);
+}
@@ -0,0 +1,39 @@
+use serde_osc::{de, ser, Framing, OscArg, OscMessage};
+use serde_osc::error::Error;
+
+#[test]
+fn char_roundtrip() {
+    for &(value, byte) in [('a', b'a'), ('~', b'~'), ('\0', 0), ('\x7f', 0x7f)].iter() {
+        let test_input = ("/key".to_owned(), (value,));
+        let mut expected = b"\x00\x00\x00\x10/key\0\0\0\0,c\0\0\0\0\0".to_vec();
+        expected.push(byte);
+
+        let serialized = ser::to_vec(&test_input, Framing::Framed).unwrap();
+        assert_eq!(serialized, expected);
+        let deserialized: (String, (char,)) = de::from_slice(&serialized, Framing::Framed).unwrap();
+        assert_eq!(deserialized, test_input);
+    }
+}
+
+#[test]
+fn non_ascii_char() {
+    let test_input = ("/key".to_owned(), ('é',));
+    match ser::to_vec(&test_input, Framing::Framed) {
+        Err(Error::UnsupportedType{ type_code: b'c', arg_index: 0 }) => (),
+        other => panic!("expected UnsupportedType, got {:?}", other),
+    }
+    let test_input = ("/key".to_owned(), ('a', 1i32, 'é'));
+    match ser::to_vec(&test_input, Framing::Framed) {
+        Err(Error::UnsupportedType{ type_code: b'c', arg_index: 2 }) => (),
+        other => panic!("expected UnsupportedType, got {:?}", other),
+    }
+}
+
+#[test]
+fn non_ascii_osc_arg() {
+    let msg = OscMessage::new("/key", vec![OscArg::I32(1), OscArg::Char('é')]).unwrap();
+    match ser::to_vec(&msg, Framing::Unframed) {
+        Err(Error::UnsupportedType{ type_code: b'c', arg_index: 1 }) => (),
+        other => panic!("expected UnsupportedType, got {:?}", other),
+    }
+}
@@ -0,0 +1,20 @@
+use serde_osc::{de, ser, Framing, OscColor};
+
+#[test]
+fn color_roundtrip() {
+    let test_input = ("/light".to_owned(), (OscColor([255, 0, 128, 255]),));
+    // Colors are 4 bytes long, so they carry no padding.
+    let expected = b"\x00\x00\x00\x10/light\0\0,r\0\0\xff\x00\x80\xff".to_vec();
+
+    let serialized = ser::to_vec(&test_input, Framing::Framed).unwrap();
+    assert_eq!(serialized, expected);
+    let deserialized: (String, (OscColor,)) = de::from_slice(&serialized, Framing::Framed).unwrap();
+    assert_eq!(deserialized, test_input);
+}
+
+#[test]
+fn color_from_int() {
+    // An 'i' argument has the same size as a color, but a different meaning.
+    let test_input = b"\x00\x00\x00\x10/light\0\0,i\0\0\xff\x00\x80\xff";
+    assert!(de::from_slice::<(String, (OscColor,))>(test_input, Framing::Framed).is_err());
+}
@@ -0,0 +1,34 @@
+use serde_osc::{de, ser, Framing};
+
+/// Serialize `value` as the sole argument of a message, check its wire
+/// format, and return the value obtained by deserializing it again.
+fn roundtrip(value: f64) -> f64 {
+    let test_input = ("/f64".to_owned(), (value,));
+    let mut expected = b"\x00\x00\x00\x14/f64\0\0\0\0,d\0\0".to_vec();
+    expected.extend_from_slice(&value.to_bits().to_be_bytes());
+
+    let serialized = ser::to_vec(&test_input, Framing::Framed).unwrap();
+    assert_eq!(serialized, expected);
+    let deserialized: (String, (f64,)) = de::from_slice(&serialized, Framing::Framed).unwrap();
+    (deserialized.1).0
+}
+
+#[test]
+fn f64_wire_format() {
+    // 440.0 fits losslessly in an f32, but must still be sent as a 'd'.
+    let test_input = ("/f64".to_owned(), (440.0f64,));
+    let expected = b"\x00\x00\x00\x14/f64\0\0\0\0,d\0\0\x40\x7b\x80\x00\x00\x00\x00\x00".to_vec();
+    assert_eq!(ser::to_vec(&test_input, Framing::Framed).unwrap(), expected);
+}
+
+#[test]
+fn f64_roundtrip() {
+    for &value in [440.0, f64::MAX, f64::MIN_POSITIVE, f64::INFINITY, f64::NEG_INFINITY].iter() {
+        assert_eq!(roundtrip(value), value);
+    }
+}
+
+#[test]
+fn f64_nan_roundtrip() {
+    assert_eq!(roundtrip(f64::NAN).to_bits(), f64::NAN.to_bits());
+}
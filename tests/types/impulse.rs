@@ -0,0 +1,25 @@
+use serde_osc::{de, ser, Framing};
+use serde_osc::types::OscImpulse;
+
+#[test]
+fn impulse_has_no_data() {
+    let test_input = ("/bang".to_owned(), (OscImpulse,));
+    // The message is only the address and ",I"; there are no argument bytes.
+    let expected = b"\x00\x00\x00\x0c/bang\0\0\0,I\0\0".to_vec();
+    assert_eq!(ser::to_vec(&test_input, Framing::Framed).unwrap(), expected);
+}
+
+#[test]
+fn impulse_roundtrip() {
+    let test_input = ("/bang".to_owned(), (1i32, OscImpulse, 2i32));
+    let serialized = ser::to_vec(&test_input, Framing::Framed).unwrap();
+    let deserialized: (String, (i32, OscImpulse, i32)) = de::from_slice(&serialized, Framing::Framed).unwrap();
+    assert_eq!(deserialized, test_input);
+}
+
+#[test]
+fn impulse_is_not_nil() {
+    let serialized = ser::to_vec(&("/bang".to_owned(), ((),)), Framing::Framed).unwrap();
+    let deserialized: Result<(String, (OscImpulse,)), _> = de::from_slice(&serialized, Framing::Framed);
+    assert!(deserialized.is_err());
+}
@@ -0,0 +1,28 @@
+use serde_osc::{de, ser, Framing};
+
+#[test]
+fn i64_wire_format() {
+    let test_input = ("/i64".to_owned(), (0x0102030405060708i64,));
+    let expected = b"\x00\x00\x00\x14/i64\0\0\0\0,h\0\0\x01\x02\x03\x04\x05\x06\x07\x08".to_vec();
+    assert_eq!(ser::to_vec(&test_input, Framing::Framed).unwrap(), expected);
+}
+
+#[test]
+fn i64_roundtrip() {
+    let cases: [(i64, &[u8; 8]); 4] = [
+        (i64::MAX, b"\x7f\xff\xff\xff\xff\xff\xff\xff"),
+        (i64::MIN, b"\x80\x00\x00\x00\x00\x00\x00\x00"),
+        (0, b"\x00\x00\x00\x00\x00\x00\x00\x00"),
+        (-1, b"\xff\xff\xff\xff\xff\xff\xff\xff"),
+    ];
+    for &(value, bytes) in cases.iter() {
+        let test_input = ("/i64".to_owned(), (value,));
+        let mut expected = b"\x00\x00\x00\x14/i64\0\0\0\0,h\0\0".to_vec();
+        expected.extend_from_slice(bytes);
+
+        let serialized = ser::to_vec(&test_input, Framing::Framed).unwrap();
+        assert_eq!(serialized, expected);
+        let deserialized: (String, (i64,)) = de::from_slice(&serialized, Framing::Framed).unwrap();
+        assert_eq!(deserialized, test_input);
+    }
+}
@@ -0,0 +1,56 @@
+use serde_bytes::ByteBuf;
+use serde_json;
+use serde_osc::{de, ser, Framing, OscArg, OscColor, OscTimeTag};
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Msg {
+    address: String,
+    args: Vec<OscArg>,
+}
+
+#[test]
+fn tagged() {
+    assert_eq!(serde_json::to_string(&OscArg::I32(42)).unwrap(), r#"{"type":"i","value":42}"#);
+    assert_eq!(serde_json::to_string(&OscArg::F32(2.5)).unwrap(), r#"{"type":"f","value":2.5}"#);
+    assert_eq!(serde_json::to_string(&OscArg::String("hi".to_owned())).unwrap(), r#"{"type":"s","value":"hi"}"#);
+    assert_eq!(serde_json::to_string(&OscArg::Blob(b"hello".to_vec())).unwrap(), r#"{"type":"b","value":"aGVsbG8="}"#);
+    assert_eq!(serde_json::to_string(&OscArg::Bool(true)).unwrap(), r#"{"type":"T"}"#);
+
+    let arg: OscArg = serde_json::from_str(r#"{"type":"i","value":42}"#).unwrap();
+    assert_eq!(arg, OscArg::I32(42));
+    let arg: OscArg = serde_json::from_str(r#"{"type":"b","value":"aGVsbG8="}"#).unwrap();
+    assert_eq!(arg, OscArg::Blob(b"hello".to_vec()));
+    let arg: OscArg = serde_json::from_str(r#"{"type":"N"}"#).unwrap();
+    assert_eq!(arg, OscArg::Nil);
+}
+
+#[test]
+fn roundtrip() {
+    let args = vec![
+        OscArg::I64(-1), OscArg::F64(0.5), OscArg::Char('x'), OscArg::Color(OscColor([1, 2, 3, 4])),
+        OscArg::Impulse, OscArg::Symbol("sym".to_owned()), OscArg::TimeTag(OscTimeTag::from((5, 6))),
+        OscArg::U32(7), OscArg::Array(vec![OscArg::Bool(false), OscArg::Array(vec![])]),
+    ];
+    let json = serde_json::to_string(&args).unwrap();
+    let parsed: Vec<OscArg> = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, args);
+}
+
+#[test]
+fn invalid() {
+    assert!(serde_json::from_str::<OscArg>(r#"{"value":42,"type":"i"}"#).is_err());
+    assert!(serde_json::from_str::<OscArg>(r#"{"type":"i"}"#).is_err());
+    assert!(serde_json::from_str::<OscArg>(r#"{"type":"x","value":42}"#).is_err());
+    assert!(serde_json::from_str::<OscArg>(r#"{"type":"b","value":"not base64!"}"#).is_err());
+}
+
+#[test]
+fn osc_unaffected() {
+    // Within a packet, arguments remain untagged.
+    let msg = Msg{ address: "/a".to_owned(), args: vec![OscArg::I32(1), OscArg::Blob(vec![2])] };
+    let serialized = ser::to_vec(&msg, Framing::Unframed).unwrap();
+    let typed = ("/a".to_owned(), (1i32, ByteBuf::from(vec![2u8])));
+    assert_eq!(serialized, ser::to_vec(&typed, Framing::Unframed).unwrap());
+    let deserialized: Msg = de::from_slice(&serialized, Framing::Unframed).unwrap();
+    assert_eq!(deserialized.args, msg.args);
+}
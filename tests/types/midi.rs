@@ -0,0 +1,27 @@
+use serde_osc::{de, ser, Framing, OscColor, OscMidi};
+
+#[test]
+fn midi_roundtrip() {
+    // Note-on, middle C, full velocity
+    let test_input = ("/midi".to_owned(), (OscMidi([0, 0x90, 60, 127]),));
+    let expected = b"\x00\x00\x00\x10/midi\0\0\0,m\0\0\x00\x90\x3c\x7f".to_vec();
+
+    let serialized = ser::to_vec(&test_input, Framing::Framed).unwrap();
+    assert_eq!(serialized, expected);
+    let deserialized: (String, (OscMidi,)) = de::from_slice(&serialized, Framing::Framed).unwrap();
+    assert_eq!(deserialized, test_input);
+}
+
+#[test]
+fn midi_has_no_padding() {
+    let without_args = ser::to_vec(&("/midi".to_owned(), ()), Framing::Framed).unwrap();
+    let with_midi = ser::to_vec(&("/midi".to_owned(), (OscMidi([0, 0x90, 60, 127]),)), Framing::Framed).unwrap();
+    // Same typetag length after padding; the only extra bytes are the MIDI data.
+    assert_eq!(with_midi.len() - without_args.len(), 4);
+}
+
+#[test]
+fn midi_from_color() {
+    let serialized = ser::to_vec(&("/midi".to_owned(), (OscColor([0, 0x90, 60, 127]),)), Framing::Framed).unwrap();
+    assert!(de::from_slice::<(String, (OscMidi,))>(&serialized, Framing::Framed).is_err());
+}
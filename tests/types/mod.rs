@@ -0,0 +1,19 @@
+//! Roundtrip tests for the optional OSC 1.1 argument types.
+mod array;
+#[cfg(feature = "bytes")]
+mod blob_bytes;
+mod boolean;
+mod character;
+mod color;
+mod double;
+mod impulse;
+mod int64;
+#[cfg(feature = "json")]
+mod json;
+mod midi;
+mod nil;
+mod osc_arg;
+mod osc_time_tag;
+mod symbol;
+mod timetag;
+mod uint32;
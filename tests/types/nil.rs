@@ -0,0 +1,41 @@
+use serde_osc::{de, ser, Framing, OscArg, OscMessage};
+
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+struct Msg {
+    address: String,
+    args: (Option<i32>, Option<i32>),
+}
+
+#[test]
+fn option_roundtrip() {
+    let test_input = Msg {
+        address: "/opt".to_owned(),
+        args: (None, Some(42)),
+    };
+    // None becomes a data-less 'N'; Some(42) becomes an 'i'.
+    let expected = b"\x00\x00\x00\x10/opt\0\0\0\0,Ni\0\x00\x00\x00\x2a".to_vec();
+
+    let serialized = ser::to_vec(&test_input, Framing::Framed).unwrap();
+    assert_eq!(serialized, expected);
+    let deserialized: Msg = de::from_slice(&serialized, Framing::Framed).unwrap();
+    assert_eq!(deserialized, test_input);
+}
+
+#[test]
+fn option_as_osc_arg() {
+    let serialized = ser::to_vec(&("/opt".to_owned(), (None::<i32>, Some(42i32))), Framing::Framed).unwrap();
+    let msg: OscMessage = de::from_slice(&serialized, Framing::Framed).unwrap();
+    assert_eq!(msg.type_string(), ",Ni");
+    assert_eq!(msg.args(), &[OscArg::Nil, OscArg::I32(42)][..]);
+}
+
+#[test]
+fn unit_roundtrip() {
+    let test_input = ("/unit".to_owned(), ((), 1i32));
+    let expected = b"\x00\x00\x00\x10/unit\0\0\0,Ni\0\x00\x00\x00\x01".to_vec();
+
+    let serialized = ser::to_vec(&test_input, Framing::Framed).unwrap();
+    assert_eq!(serialized, expected);
+    let deserialized: (String, ((), i32)) = de::from_slice(&serialized, Framing::Framed).unwrap();
+    assert_eq!(deserialized, test_input);
+}
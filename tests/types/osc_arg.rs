@@ -0,0 +1,113 @@
+use serde_bytes::ByteBuf;
+use serde_osc::{de, ser, Framing, OscArg, OscColor, OscImpulse, OscMidi, OscSymbol, OscTimeTag, OscTypeTag};
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Msg {
+    address: String,
+    args: Vec<OscArg>,
+}
+
+#[test]
+fn dynamic_args() {
+    let typed = ("/dyn".to_owned(), (
+        1i32, 2.0f32, "three".to_owned(), ByteBuf::from(vec![4u8]),
+        (5u32, 6u32), OscSymbol("seven".to_owned()), OscColor([8, 8, 8, 8]),
+        None::<i32>, OscImpulse, vec![9i64, 10], true,
+    ));
+    let serialized = ser::to_vec(&typed, Framing::Framed).unwrap();
+
+    let msg: Msg = de::from_slice(&serialized, Framing::Framed).unwrap();
+    assert_eq!(msg.address, "/dyn");
+    assert_eq!(msg.args.len(), 11);
+    match msg.args[0] { OscArg::I32(1) => (), ref other => panic!("got {:?}", other) }
+    match msg.args[1] { OscArg::F32(f) => assert_eq!(f, 2.0), ref other => panic!("got {:?}", other) }
+    match msg.args[2] { OscArg::String(ref s) if s == "three" => (), ref other => panic!("got {:?}", other) }
+    match msg.args[3] { OscArg::Blob(ref b) if b == &[4] => (), ref other => panic!("got {:?}", other) }
+    match msg.args[4] { OscArg::TimeTag(OscTimeTag{ seconds: 5, fraction: 6 }) => (), ref other => panic!("got {:?}", other) }
+    match msg.args[5] { OscArg::Symbol(ref s) if s == "seven" => (), ref other => panic!("got {:?}", other) }
+    match msg.args[6] { OscArg::Color(OscColor([8, 8, 8, 8])) => (), ref other => panic!("got {:?}", other) }
+    match msg.args[7] { OscArg::Nil => (), ref other => panic!("got {:?}", other) }
+    match msg.args[8] { OscArg::Impulse => (), ref other => panic!("got {:?}", other) }
+    match msg.args[9] {
+        OscArg::Array(ref a) => match a[..] {
+            [OscArg::I64(9), OscArg::I64(10)] => (),
+            ref other => panic!("got {:?}", other),
+        },
+        ref other => panic!("got {:?}", other),
+    }
+    match msg.args[10] { OscArg::Bool(true) => (), ref other => panic!("got {:?}", other) }
+
+    // Serializing the dynamic arguments gives back the original message.
+    assert_eq!(ser::to_vec(&msg, Framing::Framed).unwrap(), serialized);
+}
+
+#[test]
+fn no_args() {
+    let msg = Msg { address: "/empty".to_owned(), args: vec![] };
+    let serialized = ser::to_vec(&msg, Framing::Framed).unwrap();
+    let deserialized: Msg = de::from_slice(&serialized, Framing::Framed).unwrap();
+    assert_eq!(deserialized.address, "/empty");
+    assert!(deserialized.args.is_empty());
+}
+
+#[test]
+fn accessors() {
+    let all = vec![
+        OscArg::I32(1), OscArg::F32(2.0), OscArg::String("three".to_owned()), OscArg::Blob(vec![4]),
+        OscArg::I64(5), OscArg::F64(6.0), OscArg::Char('7'), OscArg::Color(OscColor([8, 8, 8, 8])),
+        OscArg::Midi(OscMidi([9, 9, 9, 9])), OscArg::Bool(true), OscArg::Nil, OscArg::Impulse,
+        OscArg::Symbol("ten".to_owned()), OscArg::TimeTag(OscTimeTag::IMMEDIATE), OscArg::U32(11),
+        OscArg::Array(vec![OscArg::I32(12)]),
+    ];
+    for (i, arg) in all.into_iter().enumerate() {
+        assert_eq!(arg.as_i32(), if i == 0 { Some(1) } else { None });
+        assert_eq!(arg.as_f32(), if i == 1 { Some(2.0) } else { None });
+        assert_eq!(arg.as_str(), if i == 2 { Some("three") } else { None });
+        assert_eq!(arg.as_blob(), if i == 3 { Some(&[4u8][..]) } else { None });
+        assert_eq!(arg.clone().into_i32(), if i == 0 { Some(1) } else { None });
+        assert_eq!(arg.clone().into_f32(), if i == 1 { Some(2.0) } else { None });
+        assert_eq!(arg.clone().into_string(), if i == 2 { Some("three".to_owned()) } else { None });
+        assert_eq!(arg.into_blob(), if i == 3 { Some(vec![4]) } else { None });
+    }
+}
+
+#[test]
+fn numeric_coercion() {
+    assert_eq!(OscArg::I32(-7).coerce_to_f64(), Some(-7.0));
+    assert_eq!(OscArg::F32(0.1).coerce_to_f64(), Some(f64::from(0.1f32)));
+    assert_eq!(OscArg::I64(1 << 53).coerce_to_f64(), Some(9007199254740992.0));
+    assert_eq!(OscArg::F64(0.5).coerce_to_f64(), Some(0.5));
+    assert_eq!(OscArg::U32(u32::MAX).coerce_to_f64(), Some(4294967295.0));
+
+    assert_eq!(OscArg::F64(0.1).coerce_to_f32(), Some(0.1));
+    assert_eq!(OscArg::I32(3).coerce_to_f32(), Some(3.0));
+
+    assert_eq!(OscArg::U32(u32::MAX).coerce_to_i64(), Some(4294967295));
+    assert_eq!(OscArg::F32(-2.9).coerce_to_i64(), Some(-2));
+    assert_eq!(OscArg::F64(1e300).coerce_to_i64(), Some(i64::MAX));
+    assert_eq!(OscArg::F64(f64::NAN).coerce_to_i64(), None);
+
+    for arg in &[OscArg::String("1".to_owned()), OscArg::Blob(vec![1]), OscArg::Bool(true), OscArg::Nil] {
+        assert_eq!(arg.coerce_to_f64(), None);
+        assert_eq!(arg.coerce_to_f32(), None);
+        assert_eq!(arg.coerce_to_i64(), None);
+    }
+}
+
+#[test]
+fn type_tags() {
+    for c in "ifsbhdcrmTFNIStu[]".chars() {
+        let tag = OscTypeTag::from_char(c).unwrap();
+        assert_eq!(tag.to_char(), c);
+        assert_eq!(tag.is_zero_width(), "TFNI[]".contains(c));
+    }
+    assert_eq!(OscTypeTag::from_char('x'), None);
+    assert_eq!(OscTypeTag::all_v10().iter().map(OscTypeTag::to_char).collect::<String>(), "ifsb");
+    assert_eq!(OscTypeTag::I32.data_size(), Some(4));
+    assert_eq!(OscTypeTag::F64.data_size(), Some(8));
+    assert_eq!(OscTypeTag::Blob.data_size(), None);
+
+    assert_eq!(OscArg::Bool(false).type_tag(), OscTypeTag::False);
+    assert_eq!(OscArg::Array(vec![OscArg::I32(1)]).type_tag(), OscTypeTag::ArrayStart);
+    assert_eq!(OscArg::Array(vec![OscArg::U32(1), OscArg::U32(2)]).type_tag(), OscTypeTag::TimeTag);
+}
@@ -0,0 +1,79 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use serde_osc::{de, ser, Framing, OscTimeTag};
+
+#[test]
+fn unix_epoch() {
+    let tag = OscTimeTag::from(UNIX_EPOCH);
+    // 70 years (including 17 leap days) after the NTP epoch.
+    assert_eq!(tag, OscTimeTag{ seconds: 2_208_988_800, fraction: 0 });
+    assert_eq!(SystemTime::from(tag), UNIX_EPOCH);
+}
+
+#[test]
+fn system_time_roundtrip() {
+    let before = UNIX_EPOCH - Duration::new(1, 500_000_000);
+    assert_eq!(SystemTime::from(OscTimeTag::from(before)), before);
+    let after = UNIX_EPOCH + Duration::new(1_500_000_000, 123_456_789);
+    assert_eq!(SystemTime::from(OscTimeTag::from(after)), after);
+    // Past the 2036 overflow of the seconds field.
+    let overflowed = UNIX_EPOCH + Duration::from_secs(2_100_000_000);
+    assert_eq!(SystemTime::from(OscTimeTag::from(overflowed)), overflowed);
+}
+
+#[test]
+fn arithmetic() {
+    let tag = OscTimeTag{ seconds: 5, fraction: 0x8000_0000 };
+    // Half a second carries into the seconds field.
+    let later = tag + Duration::from_millis(500);
+    assert_eq!(later, OscTimeTag{ seconds: 6, fraction: 0 });
+    assert_eq!(later - tag, Some(Duration::from_millis(500)));
+    assert_eq!(tag - later, None);
+    assert_eq!(tag - tag, Some(Duration::from_secs(0)));
+}
+
+#[test]
+fn arithmetic_edge_cases() {
+    // 100ms is 0x1999999A in units of 2^-32 seconds (rounded up).
+    let tag = OscTimeTag{ seconds: 5, fraction: 0xFFFF_0000 };
+    assert_eq!(tag + Duration::from_millis(100), OscTimeTag{ seconds: 6, fraction: 0x1998_999A });
+    // 1ns is a little over 4 units, which rounds up to 5.
+    let tag = OscTimeTag{ seconds: 5, fraction: 0 };
+    let later = tag + Duration::from_nanos(1);
+    assert_eq!(later, OscTimeTag{ seconds: 5, fraction: 5 });
+    assert_eq!(later - tag, Some(Duration::from_nanos(1)));
+}
+
+#[test]
+fn ordering() {
+    assert!(OscTimeTag::IMMEDIATE < OscTimeTag::now());
+    assert!(OscTimeTag::IMMEDIATE < OscTimeTag::from((0, 2)));
+    assert!(OscTimeTag::from((0, 1)) < OscTimeTag::from((0, 2)));
+    assert!(OscTimeTag::from((1, 0)) > OscTimeTag::from((0, u32::MAX)));
+
+    let mut tags: Vec<OscTimeTag> = vec![(3, 0).into(), (0, 5).into(), (1, u32::MAX).into(), (1, 0).into()];
+    tags.sort();
+    let expected: Vec<OscTimeTag> = vec![(0, 5).into(), (1, 0).into(), (1, u32::MAX).into(), (3, 0).into()];
+    assert_eq!(tags, expected);
+
+    let earlier = UNIX_EPOCH + Duration::new(1_500_000_000, 999_999_999);
+    let later = UNIX_EPOCH + Duration::new(1_500_000_001, 0);
+    assert!(OscTimeTag::from(earlier) < OscTimeTag::from(later));
+}
+
+#[test]
+fn immediate() {
+    assert!(OscTimeTag::IMMEDIATE.is_immediate());
+    assert!(OscTimeTag::from((0, 1)).is_immediate());
+    assert!(!OscTimeTag::from((0, 2)).is_immediate());
+    assert!(!OscTimeTag::now().is_immediate());
+}
+
+#[test]
+fn serialize_as_tuple() {
+    let tag = OscTimeTag{ seconds: 0x01020304, fraction: 0x05060708 };
+    let test_input = ("/sync".to_owned(), (tag,));
+    let serialized = ser::to_vec(&test_input, Framing::Framed).unwrap();
+    assert_eq!(serialized, ser::to_vec(&("/sync".to_owned(), ((0x01020304u32, 0x05060708u32),)), Framing::Framed).unwrap());
+    let deserialized: (String, (OscTimeTag,)) = de::from_slice(&serialized, Framing::Framed).unwrap();
+    assert_eq!(deserialized, test_input);
+}
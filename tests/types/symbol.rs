@@ -0,0 +1,41 @@
+use serde_osc::{de, ser, Framing, OscSymbol};
+
+#[test]
+fn symbol_roundtrip() {
+    let test_input = ("/sym".to_owned(), (OscSymbol("hello".to_owned()),));
+    // Same encoding as a string, but with an 'S' tag.
+    let expected = b"\x00\x00\x00\x14/sym\0\0\0\0,S\0\0hello\0\0\0".to_vec();
+
+    let serialized = ser::to_vec(&test_input, Framing::Framed).unwrap();
+    assert_eq!(serialized, expected);
+    let deserialized: (String, (OscSymbol,)) = de::from_slice(&serialized, Framing::Framed).unwrap();
+    assert_eq!(deserialized, test_input);
+}
+
+#[test]
+fn symbol_from_string() {
+    let test_input = b"\x00\x00\x00\x14/sym\0\0\0\0,s\0\0hello\0\0\0";
+    assert!(de::from_slice::<(String, (OscSymbol,))>(test_input, Framing::Framed).is_err());
+}
+
+#[test]
+fn string_from_symbol() {
+    // Symbols may still be read as plain strings.
+    let test_input = b"\x00\x00\x00\x14/sym\0\0\0\0,S\0\0hello\0\0\0";
+    let deserialized: (String, (String,)) = de::from_slice(test_input, Framing::Framed).unwrap();
+    assert_eq!(deserialized.1, ("hello".to_owned(),));
+}
+
+#[test]
+fn untagged_from_symbol() {
+    // Self-describing targets see a symbol as a plain string.
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(untagged)]
+    enum Value {
+        Int(i32),
+        Text(String),
+    }
+    let test_input = b"\x00\x00\x00\x14/sym\0\0\0\0,S\0\0hello\0\0\0";
+    let deserialized: (String, (Value,)) = de::from_slice(test_input, Framing::Framed).unwrap();
+    assert_eq!(deserialized.1, (Value::Text("hello".to_owned()),));
+}
@@ -0,0 +1,23 @@
+use serde_osc::{de, ser, Framing};
+
+#[test]
+fn timetag_roundtrip() {
+    let test_input = ("/sync".to_owned(), ((0x01020304u32, 0x05060708u32),));
+    let expected = b"\x00\x00\x00\x14/sync\0\0\0,t\0\0\x01\x02\x03\x04\x05\x06\x07\x08".to_vec();
+
+    let serialized = ser::to_vec(&test_input, Framing::Framed).unwrap();
+    assert_eq!(serialized, expected);
+    let deserialized: (String, ((u32, u32),)) = de::from_slice(&serialized, Framing::Framed).unwrap();
+    assert_eq!(deserialized, test_input);
+}
+
+#[test]
+fn timetag_among_args() {
+    type Args = (i32, (u32, u32), [i32; 2]);
+    let test_input = ("/sync".to_owned(), (1i32, (2u32, 3u32), [4i32, 5i32]));
+    let serialized = ser::to_vec(&test_input, Framing::Framed).unwrap();
+    // Only a pair of u32s is a timetag; other pairs are still arrays.
+    assert_eq!(&serialized[12..20], b",it[ii]\0");
+    let deserialized: (String, Args) = de::from_slice(&serialized, Framing::Framed).unwrap();
+    assert_eq!(deserialized, test_input);
+}
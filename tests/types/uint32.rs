@@ -0,0 +1,23 @@
+use serde_osc::{de, ser, Framing};
+
+#[test]
+fn u32_roundtrip() {
+    let test_input = ("/dmx".to_owned(), (u32::MAX,));
+    // Same bits as an i32 of -1, but tagged 'u' rather than 'i'.
+    let expected = b"\x00\x00\x00\x10/dmx\0\0\0\0,u\0\0\xff\xff\xff\xff".to_vec();
+
+    let serialized = ser::to_vec(&test_input, Framing::Framed).unwrap();
+    assert_eq!(serialized, expected);
+    let deserialized: (String, (u32,)) = de::from_slice(&serialized, Framing::Framed).unwrap();
+    assert_eq!(deserialized, test_input);
+}
+
+#[test]
+fn u32_array() {
+    // Only a pair of u32s is a timetag; any other number of them is an array.
+    let test_input = ("/dmx".to_owned(), (vec![1u32], vec![1u32, 2, 3]));
+    let serialized = ser::to_vec(&test_input, Framing::Framed).unwrap();
+    assert_eq!(&serialized[12..24], b",[u][uuu]\0\0\0");
+    let deserialized: (String, (Vec<u32>, Vec<u32>)) = de::from_slice(&serialized, Framing::Framed).unwrap();
+    assert_eq!(deserialized, test_input);
+}
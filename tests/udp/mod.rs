@@ -0,0 +1,19 @@
+use std::time::Duration;
+use serde_osc::{UdpOscReceiver, UdpOscSender};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Note {
+    address: String,
+    args: (i32, f32),
+}
+
+#[test]
+fn loopback() {
+    let receiver = UdpOscReceiver::bind(([127, 0, 0, 1], 0).into()).unwrap();
+    receiver.socket().set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+    let sender = UdpOscSender::connect(receiver.local_addr().unwrap()).unwrap();
+
+    let note = Note { address: "/note".to_owned(), args: (60, 0.5) };
+    sender.send(&note).unwrap();
+    assert_eq!(receiver.recv::<Note>().unwrap(), note);
+}